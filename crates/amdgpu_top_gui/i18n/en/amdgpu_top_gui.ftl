@@ -49,8 +49,18 @@ memory_clock = Memory Clock
 resizable_bar = ResizableBAR
 ecc_memory = ECC Memory
 ecc_memory_error_count = ECC Memory Error Count
+dpm_levels = DPM Levels
+dpm_residency = DPM Residency
 corrected = Corrected
 uncorrected = UnCorrected
+ras_error_count = ECC/RAS Error Count
+ras_block = Block
+delta = (delta)
+vram_bad_pages = VRAM Bad Pages
+retired = Retired
+pending = Pending
+failed = Failed
+retired_page_list = Retired Page List
 
 l1_cache_per_cu = L1 Cache (per CU)
 gl1_cache_per_sa = GL1 Cache (per SA/SH)
@@ -77,6 +87,10 @@ power_cap_default = Power Cap. (Default)
 pcie_link_speed = PCIe Link Speed
 pci_power_state = PCI Power State
 power_profile = Power Profile
+perf_level = Performance Level
+power_control = Power Control
+run_as_root = Please run amdgpu_top as root to change the setting.
+power_profile_requires_manual = Some GPUs require the "manual" Performance Level to change the Power Profile.
 # Dynamic Power Management (DPM)
 dpm = DPM
 max = Max
@@ -109,6 +123,11 @@ vbios_version = Version
 vbios_date = Date
 vbios_size = Size (Byte)
 
+firmware_info = Firmware Info
+fw_type = Type
+fw_feature = Feature
+fw_version = Version
+
 connector_info = Connector Info
 
 # CentralPanel
@@ -195,5 +214,48 @@ avg_activity = Average Activity
 activity = Activity
 throttle_status = Throttle Status
 throttling_log = Throttling Log
+throttle_events = Throttle Events
+start_time = Start
+throttler = Throttler
+category = Category
+duration = Duration
+onset_sclk = SCLK at Onset (MHz)
+onset_temp = Temp. at Onset (C)
+onset_power = Power at Onset (W)
+event_log = Event Log
+energy = Energy
+energy_session = Session
+energy_counter = Counter
+energy_source = Source
+gfxoff = GFXOFF
+gfxoff_status = Status
+gfxoff_status_changes = Status changes (sampled per refresh)
+gfxoff_entries = Entries (since boot)
+gfxoff_residency = Residency (debugfs)
+reset = Reset
+est_power = Power (Est.)
+est_energy = Energy (Est.)
+displays = Displays
+connector = Connector
+connection = Connection
+display_mode = Mode
+vrr_capable = VRR Capable
+vrr_enabled = VRR
+max_bpc = max bpc
+colorspace = Colorspace
+hdr = HDR
+monitor_name = Monitor Name
+manufacturer = Manufacturer
+product_code = Product Code
+serial = Serial
+manufactured = Manufactured (Week, Year)
+physical_size = Physical Size
+range_limits = Range Limits
+vrr_range = FreeSync/VRR Range
+eotf = EOTF
+max_luminance = Max Luminance
+max_frame_avg_luminance = Max Frame-Avg Luminance
+min_luminance = Min Luminance
+timings = Timings
 
 failed_to_set_up_gui = Failed to set up a graphics context.
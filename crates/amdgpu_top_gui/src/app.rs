@@ -1,15 +1,17 @@
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use eframe::wgpu::AdapterInfo;
 use crate::egui::{self, RichText, util::History};
 use crate::{BASE, MEDIUM, HISTORY_LENGTH};
 use crate::{GuiAppData, util::*, fl};
-use egui_plot::{Corner, Legend, Line, Plot, PlotPoint, PlotPoints};
+use egui_plot::{Bar, BarChart, Corner, Legend, Line, Plot, PlotPoint, PlotPoints};
 
 use libamdgpu_top::{
     AMDGPU::RasErrorCount,
+    control::{DeviceControl, PerformanceLevel},
     DevicePath,
     PCI,
-    stat::{FdInfoSortType, PerfCounter},
+    stat::{on_off, BadPageStatus, DpmLevels, FdInfoSortType, GfxoffResidency, PerfCounter, RasErrorStat},
 };
 
 const SPACING: [f32; 2] = [16.0; 2];
@@ -26,6 +28,8 @@ pub struct MyApp {
     pub buf_data: GuiAppData,
     pub buf_vec_data: Vec<GuiAppData>,
     pub arc_data: Arc<Mutex<Vec<GuiAppData>>>,
+    pub arc_device_path_list: Arc<Mutex<Vec<DevicePath>>>,
+    pub reset_energy: Arc<AtomicBool>,
     pub device_path_list: Vec<DevicePath>,
     pub show_sidepanel: bool,
     pub wgpu_adapter_info: Option<AdapterInfo>,
@@ -34,6 +38,7 @@ pub struct MyApp {
     pub no_pc: bool,
     pub pause: bool,
     pub full_fdinfo_list: bool,
+    pub control_error: Option<String>,
 }
 
 pub fn grid(ui: &mut egui::Ui, v: &[(&str, &str)]) {
@@ -62,6 +67,82 @@ impl GuiMemoryErrorCount for RasErrorCount {
     }
 }
 
+pub trait GuiRasErrorStat {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiRasErrorStat for RasErrorStat {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("RAS Error Count").show(ui, |ui| {
+            ui.label(fl!("ras_block"));
+            ui.label(fl!("corrected"));
+            ui.label(fl!("delta"));
+            ui.label(fl!("uncorrected"));
+            ui.label(fl!("delta"));
+            ui.end_row();
+
+            for block in &self.blocks {
+                ui.label(block.name.as_str());
+                ui.label(block.corrected.to_string());
+                ui.label(format!("+{}", block.delta_corrected));
+                ui.label(block.uncorrected.to_string());
+                ui.label(format!("+{}", block.delta_uncorrected));
+                ui.end_row();
+            }
+        });
+
+        ui.label(format!(
+            "{}: {} ({}: {}, {}: {}, {}: {})",
+            fl!("vram_bad_pages"),
+            self.bad_pages.len(),
+            fl!("retired"),
+            self.count_bad_pages(BadPageStatus::Reserved),
+            fl!("pending"),
+            self.count_bad_pages(BadPageStatus::Pending),
+            fl!("failed"),
+            self.count_bad_pages(BadPageStatus::Failed),
+        ));
+
+        if self.bad_pages.is_empty() { return }
+
+        collapsing(ui, &fl!("retired_page_list"), false, |ui| {
+            egui::Grid::new("Retired Page List").show(ui, |ui| {
+                for page in self.retired_pages() {
+                    ui.label(format!("PFN {:#010X}", page.pfn));
+                    ui.label(format!("{:#x} bytes", page.size));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
+
+pub trait GuiDpmLevels {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiDpmLevels for DpmLevels {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("DPM Levels").show(ui, |ui| {
+            for clk in &self.clocks {
+                ui.label(clk.type_.to_string());
+
+                for level in &clk.levels {
+                    let text = RichText::new(format!("{:>4} MHz", level.mhz)).monospace();
+
+                    if level.is_current {
+                        ui.label(text.strong().underline());
+                    } else {
+                        ui.label(text.weak());
+                    }
+                }
+
+                ui.end_row();
+            }
+        });
+    }
+}
+
 impl MyApp {
     pub fn egui_perf_counter(
         &self,
@@ -249,6 +330,14 @@ impl MyApp {
                 }
             }
 
+            for (s, align) in [
+                (fl!("est_power"), 10),
+                (fl!("est_energy"), 12),
+            ] {
+                let s = format!("{s:^align$}");
+                let _ = ui.button(rt_base(s));
+            }
+
             ui.end_row();
 
             self.buf_data.stat.fdinfo.sort_proc_usage(self.fdinfo_sort, self.reverse_sort);
@@ -281,6 +370,14 @@ impl MyApp {
                     ui.label(format!("{:3} %", pu.usage.vpe));
                 }
 
+                if let Some(p) = self.buf_data.stat.proc_power.get(pu.pid) {
+                    ui.label(format!("{:5.1} W", p.power));
+                    ui.label(format!("{:7.3} Wh", p.energy_wh()));
+                } else {
+                    ui.label("");
+                    ui.label("");
+                }
+
                 ui.end_row();
             } // proc_usage
         });
@@ -482,6 +579,76 @@ impl MyApp {
                 power_profile,
             ));
         }
+
+        if let Some(perf_level) = &sensors.perf_level {
+            ui.label(format!(
+                "{}: {}",
+                fl!("perf_level"),
+                perf_level,
+            ));
+        }
+    }
+
+    pub fn egui_power_control(&mut self, ui: &mut egui::Ui) {
+        let Some(sensors) = self.buf_data.stat.sensors.as_ref() else { return };
+        let cur_profile = sensors.power_profile.map(|p| p.to_string()).unwrap_or_default();
+        let cur_level = sensors.perf_level;
+        let mut new_profile: Option<String> = None;
+        let mut new_level: Option<PerformanceLevel> = None;
+
+        egui::Grid::new("Power Control").show(ui, |ui| {
+            ui.label(fl!("power_profile"));
+            egui::ComboBox::from_id_salt("Power Profile")
+                .selected_text(&cur_profile)
+                .show_ui(ui, |ui| for profile in &self.buf_data.device_info.power_profiles {
+                    let profile = profile.to_string();
+
+                    if ui.selectable_label(profile == cur_profile, &profile).clicked() && profile != cur_profile {
+                        new_profile = Some(profile);
+                    }
+                });
+            ui.end_row();
+
+            ui.label(fl!("perf_level"));
+            egui::ComboBox::from_id_salt("Performance Level")
+                .selected_text(cur_level.map_or("", |level| level.as_str()))
+                .show_ui(ui, |ui| for level in PerformanceLevel::ALL {
+                    let is_current = Some(level) == cur_level;
+
+                    if ui.selectable_label(is_current, level.as_str()).clicked() && !is_current {
+                        new_level = Some(level);
+                    }
+                });
+            ui.end_row();
+        });
+
+        let Some(device_path) = self.device_path_list.iter().find(|d| d.pci == self.selected_pci_bus) else {
+            return;
+        };
+        let is_profile = new_profile.is_some();
+
+        let r = if let Some(profile) = new_profile {
+            Some(DeviceControl::from_device_path(device_path).set_power_profile(&profile))
+        } else {
+            new_level.map(|level| DeviceControl::from_device_path(device_path).set_perf_level(level))
+        };
+
+        match r {
+            Some(Ok(())) => self.control_error = None,
+            Some(Err(err)) if err.is_permission_denied() => {
+                self.control_error = Some(format!("{err}\n{}", fl!("run_as_root")));
+            },
+            // some ASICs require the "manual" performance level for changing the power profile
+            Some(Err(err)) if is_profile && cur_level != Some(PerformanceLevel::Manual) => {
+                self.control_error = Some(format!("{err}\n{}", fl!("power_profile_requires_manual")));
+            },
+            Some(Err(err)) => self.control_error = Some(err.to_string()),
+            None => {},
+        }
+
+        if let Some(err) = &self.control_error {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
     }
 
     pub fn egui_temp_plot(&self, ui: &mut egui::Ui) {
@@ -690,6 +857,175 @@ impl MyApp {
             });
     }
 
+    pub fn egui_energy(&self, ui: &mut egui::Ui) {
+        let energy = &self.buf_data.stat.energy;
+
+        egui::Grid::new("Energy").show(ui, |ui| {
+            ui.label(fl!("energy_session"));
+            ui.label(format!("{:.3} Wh", energy.session_wh()));
+            ui.end_row();
+
+            ui.label(fl!("energy_counter"));
+            ui.label(format!("{:.3} Wh", energy.counter_wh()));
+            if ui.button(fl!("reset")).clicked() {
+                self.reset_energy.store(true, Ordering::Relaxed);
+            }
+            ui.end_row();
+
+            if let Some(source) = energy.source {
+                ui.label(fl!("energy_source"));
+                ui.label(source.to_string());
+                ui.end_row();
+            }
+        });
+    }
+
+    pub fn egui_gfxoff(&self, ui: &mut egui::Ui) {
+        let Some(gfxoff) = &self.buf_data.stat.gfxoff else { return };
+        let r = &gfxoff.residency;
+
+        egui::Grid::new("GFXOFF").show(ui, |ui| {
+            ui.label(fl!("gfxoff_status"));
+            ui.label(gfxoff.status.to_string());
+            ui.end_row();
+
+            for status in GfxoffResidency::STATUS_LIST {
+                ui.label(status.to_string());
+                ui.label(format!(
+                    "{:5.1} % ({:.1}s)",
+                    r.percentage(status),
+                    r.time(status).as_secs_f32(),
+                ));
+                ui.end_row();
+            }
+
+            ui.label(fl!("gfxoff_status_changes"));
+            ui.label(r.observed_status_changes.to_string());
+            ui.end_row();
+
+            if let (Some(entries), Some(count)) = (r.entries(), r.entry_count) {
+                ui.label(fl!("gfxoff_entries"));
+                ui.label(format!("{entries} ({count})"));
+                ui.end_row();
+            }
+
+            if let Some(residency) = r.debugfs_residency {
+                ui.label(fl!("gfxoff_residency"));
+                ui.label(format!("{residency:.2} %"));
+                ui.end_row();
+            }
+        });
+    }
+
+    pub fn egui_throttle_events(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("Throttle Events").striped(true).show(ui, |ui| {
+            for label in [
+                fl!("start_time"),
+                fl!("throttler"),
+                fl!("category"),
+                fl!("duration"),
+                fl!("onset_sclk"),
+                fl!("onset_temp"),
+                fl!("onset_power"),
+            ] {
+                ui.label(label);
+            }
+            ui.end_row();
+
+            // newest first
+            for ev in self.buf_data.stat.throttle_log.events.iter().rev() {
+                let duration = format!(
+                    "{:.1}s{}",
+                    ev.duration.as_secs_f32(),
+                    if ev.is_active { "+" } else { "" },
+                );
+                let [sclk, temp, power] = [
+                    ev.onset.sclk.map(|v| v as i64),
+                    ev.onset.junction_temp.or(ev.onset.edge_temp),
+                    ev.onset.power.map(|v| v as i64),
+                ].map(|v| v.map_or("_".to_string(), |v| v.to_string()));
+
+                ui.label(ev.start_time.as_str());
+                ui.label(ev.throttler.as_str());
+                ui.label(ev.category.to_string());
+                ui.label(duration);
+                ui.label(sclk);
+                ui.label(temp);
+                ui.label(power);
+                ui.end_row();
+            }
+        });
+    }
+
+    pub fn egui_displays(&self, ui: &mut egui::Ui) {
+        let Some(monitor) = &self.buf_data.stat.displays else { return };
+
+        egui::Grid::new("Displays").striped(true).show(ui, |ui| {
+            for label in [
+                fl!("connector"),
+                fl!("connection"),
+                fl!("display_mode"),
+                fl!("vrr_capable"),
+                fl!("vrr_enabled"),
+                fl!("max_bpc"),
+                fl!("colorspace"),
+                fl!("hdr"),
+            ] {
+                ui.label(label);
+            }
+            ui.end_row();
+
+            for disp in &monitor.displays {
+                ui.label(disp.name.as_str());
+                ui.label(if disp.connected { "On" } else { "Off" });
+                ui.label(disp.mode.map_or("_".to_string(), |mode| mode.to_string()));
+                ui.label(on_off(disp.vrr_capable));
+                ui.label(on_off(disp.vrr_enabled));
+                ui.label(disp.max_bpc.map_or("_".to_string(), |v| v.to_string()));
+                ui.label(disp.colorspace.as_deref().unwrap_or("_"));
+                ui.label(on_off(disp.hdr_output_metadata));
+                ui.end_row();
+            }
+        });
+
+        // newest first
+        for ev in monitor.events.iter().rev() {
+            ui.label(ev.to_string());
+        }
+    }
+
+    pub fn egui_dpm_residency(&self, ui: &mut egui::Ui, dpm_levels: &DpmLevels) {
+        let mut n = 1;
+
+        egui::Grid::new("DPM Residency").spacing(SPACING).show(ui, |ui| {
+            for r in &dpm_levels.residency {
+                let id = format!("{} Residency", r.type_);
+
+                egui::Grid::new(&id).show(ui, |ui| {
+                    ui.label(r.type_.to_string());
+                    ui.end_row();
+
+                    let bars: Vec<Bar> = r.levels.iter().zip(r.percentages()).enumerate()
+                        .map(|(i, (level, per))| {
+                            let index = level.index.map_or("S".to_string(), |i| i.to_string());
+                            Bar::new(i as f64, per).name(format!("{index}: {} MHz", level.mhz))
+                        })
+                        .collect();
+                    let chart = BarChart::new(bars).width(0.7).name(r.type_.to_string());
+
+                    default_plot(&id)
+                        .include_y(100.0)
+                        .show_axes([true, false])
+                        .height(SENSORS_HEIGHT)
+                        .width(SENSORS_WIDTH / 2.0)
+                        .show(ui, |plot_ui| plot_ui.bar_chart(chart));
+                });
+
+                n += 1;
+                if n % 4 == 1 { ui.end_row(); }
+            }
+        });
+    }
 }
 
 fn default_plot(id: &str) -> Plot {
@@ -9,6 +9,7 @@ use libamdgpu_top::app::{
 use libamdgpu_top::AMDGPU::{MetricsInfo, ThrottleStatus};
 use libamdgpu_top::stat::{
     FdInfoUsage,
+    PerfCounter,
     Sensors,
     gpu_metrics_util,
 };
@@ -17,6 +18,7 @@ use libamdgpu_top::stat::{
 pub struct HistoryData {
     pub grbm_history: Vec<History<u8>>,
     pub grbm2_history: Vec<History<u8>>,
+    pub custom_pc_history: Vec<Vec<History<u8>>>,
     pub vram_history: History<u64>,
     pub gtt_history: History<u64>,
     pub fdinfo_history: History<FdInfoUsage>,
@@ -110,9 +112,11 @@ impl GuiAppData {
         let sensors_history = SensorsHistory::default();
         let pcie_bw_history: History<(u64, u64)> = History::new(HISTORY_LENGTH, f32::INFINITY);
         let throttling_history = History::new(HISTORY_LENGTH, f32::INFINITY);
-        let [grbm_history, grbm2_history] = [&app.stat.grbm, &app.stat.grbm2].map(|pc| {
+        let pc_history = |pc: &PerfCounter| {
             vec![History::<u8>::new(HISTORY_LENGTH, f32::INFINITY); pc.pc_index.len()]
-        });
+        };
+        let [grbm_history, grbm2_history] = [&app.stat.grbm, &app.stat.grbm2].map(pc_history);
+        let custom_pc_history = app.stat.custom_pc.iter().map(pc_history).collect();
         let gfx_activity = History::new(HISTORY_LENGTH, f32::INFINITY);
         let umc_activity = History::new(HISTORY_LENGTH, f32::INFINITY);
         let media_activity = History::new(HISTORY_LENGTH, f32::INFINITY);
@@ -145,6 +149,7 @@ impl GuiAppData {
             history: HistoryData {
                 grbm_history,
                 grbm2_history,
+                custom_pc_history,
                 vram_history,
                 gtt_history,
                 fdinfo_history,
@@ -184,10 +189,12 @@ impl GuiAppData {
         }
 
         if !no_pc {
+            let custom_pc = self.stat.custom_pc.iter().zip(self.history.custom_pc_history.iter_mut());
+
             for (pc, history) in [
                 (&self.stat.grbm, &mut self.history.grbm_history),
                 (&self.stat.grbm2, &mut self.history.grbm2_history),
-            ] {
+            ].into_iter().chain(custom_pc) {
                 for (pc_index, h) in pc.pc_index.iter().zip(history.iter_mut()) {
                     h.add(secs, pc_index.usage);
                 }
@@ -7,7 +7,7 @@ use crate::{
     fl,
 };
 
-use libamdgpu_top::{ConnectorInfo, ModeProp, drmModeModeInfo, drmModePropType};
+use libamdgpu_top::{ConnectorInfo, ModeProp, drmModeModeInfo, drmModePropType, edid::Edid, firmware::FirmwareInfo};
 use libamdgpu_top::AMDGPU::{
     GPU_INFO,
     HW_IP::HwIpInfo,
@@ -140,6 +140,28 @@ impl GuiVbiosInfo for VbiosInfo {
     }
 }
 
+pub trait GuiFirmwareInfo {
+    fn ui(&self, ui: &mut egui::Ui);
+}
+
+impl GuiFirmwareInfo for FirmwareInfo {
+    fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("firmware_info").show(ui, |ui| {
+            ui.label(fl!("fw_type"));
+            ui.label(fl!("fw_feature"));
+            ui.label(fl!("fw_version"));
+            ui.end_row();
+
+            for fw in &self.list {
+                ui.label(&fw.name).highlight();
+                ui.label(fw.feature.to_string());
+                ui.label(format!("{:#X}", fw.version));
+                ui.end_row();
+            }
+        });
+    }
+}
+
 pub trait GuiInfo {
     fn ui(&self, ui: &mut egui::Ui, wgpu_adapter_info: &Option<AdapterInfo>, rocm_version: &Option<String>);
     fn device_info(&self, ui: &mut egui::Ui, wgpu_adapater_info: &Option<AdapterInfo>, rocm_version: &Option<String>);
@@ -415,6 +437,10 @@ impl GuiConnectorInfo for ConnectorInfo {
                 });
             }
 
+            if let Some(edid) = &self.edid {
+                collapsing_with_id(ui, "EDID", &format!("EDID {title}"), true, |ui| edid.ui(&title, ui));
+            }
+
             for mode_prop in &self.mode_props {
                 mode_prop.ui(&title, ui);
             }
@@ -422,6 +448,74 @@ impl GuiConnectorInfo for ConnectorInfo {
     }
 }
 
+pub trait GuiEdid {
+    fn ui(&self, conn_name: &str, ui: &mut egui::Ui);
+}
+
+impl GuiEdid for Edid {
+    fn ui(&self, conn_name: &str, ui: &mut egui::Ui) {
+        egui::Grid::new(format!("EDID {conn_name}")).show(ui, |ui| {
+            let mut rows = vec![
+                (fl!("monitor_name"), self.monitor_name.clone().unwrap_or_default()),
+                (fl!("manufacturer"), self.manufacturer.clone()),
+                (fl!("product_code"), format!("{:#06X}", self.product_code)),
+                (
+                    fl!("serial"),
+                    match &self.serial_string {
+                        Some(s) => format!("{:#010X} ({s})", self.serial_number),
+                        None => format!("{:#010X}", self.serial_number),
+                    },
+                ),
+                (fl!("manufactured"), format!("{}, {}", self.week, self.year)),
+                (fl!("version"), format!("{}.{}", self.version.0, self.version.1)),
+            ];
+
+            if let Some((h, v)) = self.physical_size {
+                rows.push((fl!("physical_size"), format!("{h}x{v} cm")));
+            }
+
+            if let Some(r) = &self.range_limits {
+                rows.push((
+                    fl!("range_limits"),
+                    format!(
+                        "{}-{} Hz, {}-{} kHz, {} MHz",
+                        r.min_v_rate,
+                        r.max_v_rate,
+                        r.min_h_rate,
+                        r.max_h_rate,
+                        r.max_pixel_clock,
+                    ),
+                ));
+            }
+
+            if let Some((min, max)) = self.vrr_range() {
+                rows.push((fl!("vrr_range"), format!("{min}-{max} Hz")));
+            }
+
+            if let Some(hdr) = self.hdr_static_metadata() {
+                let lum = |v: Option<f32>| v.map_or("_".to_string(), |v| format!("{v:.4} cd/m^2"));
+
+                rows.push((fl!("eotf"), hdr.eotf_list().join(", ")));
+                rows.push((fl!("max_luminance"), lum(hdr.max_luminance)));
+                rows.push((fl!("max_frame_avg_luminance"), lum(hdr.max_frame_avg_luminance)));
+                rows.push((fl!("min_luminance"), lum(hdr.min_luminance)));
+            }
+
+            for (name, val) in rows {
+                ui.label(name);
+                ui.label(val);
+                ui.end_row();
+            }
+        });
+
+        collapsing_with_id(ui, &fl!("timings"), &format!("EDID Timings {conn_name}"), false, |ui| {
+            for timing in self.all_timings() {
+                ui.label(timing.to_string());
+            }
+        });
+    }
+}
+
 pub trait GuiModeInfo {
     fn ui(&self, ui: &mut egui::Ui);
 }
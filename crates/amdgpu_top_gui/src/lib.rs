@@ -1,4 +1,5 @@
 use std::sync::{Arc, Mutex, LazyLock};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use std::ops::Range;
 use eframe::{egui, egui_wgpu, wgpu};
@@ -11,11 +12,13 @@ use libamdgpu_top::{
         GpuMetrics,
         MetricsInfo,
     },
-    app::AppAmdgpuTop,
+    app::{AppAmdgpuTop, AppOption},
     stat::{
         self,
         PerfCounter,
     },
+    summary,
+    uevent::{self, DeviceEventKind},
     AppDeviceInfo,
     DevicePath,
     GuiWgpuBackend,
@@ -28,13 +31,13 @@ mod gui_app_data;
 use gui_app_data::GuiAppData;
 
 mod app;
-use app::{GuiMemoryErrorCount, MyApp};
+use app::{GuiDpmLevels, GuiMemoryErrorCount, GuiRasErrorStat, MyApp};
 
 mod gui_gpu_metrics;
 use gui_gpu_metrics::GuiGpuMetrics;
 
 mod gui_device_info;
-use gui_device_info::{GuiInfo, GuiConnectorInfo, GuiHwIpInfo, GuiIpDiscovery, GuiVbiosInfo, GuiVideoCapsInfo, GuiXdnaInfo};
+use gui_device_info::{GuiInfo, GuiConnectorInfo, GuiHwIpInfo, GuiIpDiscovery, GuiFirmwareInfo, GuiVbiosInfo, GuiVideoCapsInfo, GuiXdnaInfo};
 
 mod util;
 use util::*;
@@ -73,6 +76,10 @@ pub fn run(
         no_pc,
         is_dark_mode,
         gui_wgpu_backend,
+        power_attribution,
+        summary,
+        pc_config,
+        pc_sampler,
         ..
     }: UiArgs,
 ) {
@@ -84,9 +91,18 @@ pub fn run(
         eprintln!("Error while loading languages for library_fluent {error}");
     }
 
+    let app_opt = AppOption {
+        power_attribution,
+        summary: summary.then(Default::default),
+        gfxoff_residency_logging: true,
+        pc_config,
+        pc_sampler: (!no_pc).then_some(pc_sampler),
+        ..Default::default()
+    };
+    let summary_list = app_opt.summary.clone();
     let (mut vec_app, mut suspended_devices) = AppAmdgpuTop::create_app_and_suspended_list(
         &device_path_list,
-        &Default::default(),
+        &app_opt,
     );
 
     for app in vec_app.iter_mut() {
@@ -132,6 +148,8 @@ pub fn run(
         buf_data: data,
         buf_vec_data: vec_data.clone(),
         arc_data: Arc::new(Mutex::new(vec_data.clone())),
+        arc_device_path_list: Arc::new(Mutex::new(device_path_list.clone())),
+        reset_energy: Arc::new(AtomicBool::new(false)),
         device_path_list,
         show_sidepanel: true,
         wgpu_adapter_info: None,
@@ -140,6 +158,7 @@ pub fn run(
         no_pc,
         pause: false,
         full_fdinfo_list: false,
+        control_error: None,
     };
 
     let options = eframe::NativeOptions {
@@ -162,32 +181,61 @@ pub fn run(
     {
         let now = std::time::Instant::now();
         let share_data = gui_app.arc_data.clone();
+        let share_device_path_list = gui_app.arc_device_path_list.clone();
+        let reset_energy = gui_app.reset_energy.clone();
+        let uevent_rx = uevent::spawn_uevent_monitor();
 
         std::thread::spawn(move || loop {
-            if !no_pc {
-                for _ in 0..sample.count {
-                    for app in vec_app.iter_mut() {
-                        app.update_pc();
-                    }
+            for uevent in uevent_rx.iter().flat_map(|rx| rx.try_iter()) {
+                let Some(kind) = DeviceEventKind::from_uevent(&uevent) else { continue };
+                let Some(pci) = uevent.pci_bus() else { continue };
+
+                match kind {
+                    DeviceEventKind::Added => {
+                        if vec_app.iter().any(|app| app.device_path.pci == pci)
+                        || suspended_devices.iter().any(|dev| dev.pci == pci)
+                        {
+                            continue;
+                        }
 
-                    std::thread::sleep(sample.delay);
-                }
+                        let Some(device_path) = DevicePath::from_pci(pci) else { continue };
 
-                for app in vec_app.iter_mut() {
-                    app.update_pc_usage();
+                        stat::spawn_update_index_thread(vec![device_path.clone()], update_process_index);
+                        share_device_path_list.lock().unwrap().push(device_path.clone());
+                        suspended_devices.push(device_path);
+                    },
+                    DeviceEventKind::Removed => {
+                        if let Some(pos) = vec_app.iter().position(|app| app.device_path.pci == pci) {
+                            vec_app.remove(pos);
+                            vec_data.remove(pos);
+                        }
+
+                        suspended_devices.retain(|dev| dev.pci != pci);
+                        share_device_path_list.lock().unwrap().retain(|dev| dev.pci != pci);
+                    },
+                    _ => {
+                        if let Some(app) = vec_app.iter_mut().find(|app| app.device_path.pci == pci) {
+                            app.handle_device_event(kind);
+                        }
+                    },
                 }
-            } else {
-                std::thread::sleep(sample.to_duration());
             }
 
+            std::thread::sleep(sample.to_duration());
+
+            let is_reset_energy = reset_energy.swap(false, Ordering::Relaxed);
+
             for app in vec_app.iter_mut() {
                 app.update(sample.to_duration());
+
+                if is_reset_energy {
+                    app.stat.energy.reset();
+                }
             }
 
             for (app, data) in vec_app.iter_mut().zip(vec_data.iter_mut()) {
                 data.stat = app.stat.clone();
                 data.update_history(now.elapsed().as_secs_f64(), no_pc);
-                if !no_pc { app.clear_pc(); }
             }
 
             {
@@ -205,7 +253,7 @@ pub fn run(
                     let Some(app) = AppAmdgpuTop::new(
                         amdgpu_dev,
                         dev.clone(),
-                        &Default::default(),
+                        &app_opt,
                     ) else { return true };
                     vec_data.push(GuiAppData::new(&app));
                     vec_app.push(app);
@@ -305,6 +353,10 @@ pub fn run(
         eprintln!("{err}");
         panic!();
     });
+
+    if let Some(list) = &summary_list {
+        summary::print_session_summary_list(list);
+    }
 }
 
 impl MyApp {
@@ -385,6 +437,11 @@ impl MyApp {
                 collapsing(ui, &fl!("vbios_info"), false, |ui| vbios.ui(ui));
             }
 
+            if !self.buf_data.device_info.firmware.list.is_empty() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("firmware_info"), false, |ui| self.buf_data.device_info.firmware.ui(ui));
+            }
+
             if !self.buf_data.vec_connector_info.is_empty() {
                 ui.add_space(SPACE);
 
@@ -417,6 +474,11 @@ impl MyApp {
                     &self.buf_data.history.grbm2_history,
                 ));
                 ui.add_space(SPACE);
+
+                for (pc, history) in self.buf_data.stat.custom_pc.iter().zip(&self.buf_data.history.custom_pc_history) {
+                    collapsing(ui, &pc.name, true, |ui| self.egui_perf_counter(ui, &pc.name, pc, history));
+                    ui.add_space(SPACE);
+                }
             }
 
             collapsing(ui, &fl!("vram"), true, |ui| self.egui_vram(ui));
@@ -433,6 +495,18 @@ impl MyApp {
             if self.buf_data.stat.sensors.is_some() {
                 ui.add_space(SPACE);
                 collapsing(ui, &fl!("sensor"), true, |ui| self.egui_sensors(ui));
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("power_control"), false, |ui| self.egui_power_control(ui));
+            }
+
+            if self.buf_data.stat.energy.source.is_some() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("energy"), true, |ui| self.egui_energy(ui));
+            }
+
+            if self.buf_data.stat.gfxoff.is_some() {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("gfxoff"), true, |ui| self.egui_gfxoff(ui));
             }
 
             if self.buf_data.support_pcie_bw {
@@ -440,7 +514,23 @@ impl MyApp {
                 collapsing(ui, &fl!("pcie_bw"), true, |ui| self.egui_pcie_bw(ui));
             }
 
-            if let Some(ecc) = &self.buf_data.stat.memory_error_count {
+            if let Some(dpm_levels) = &self.buf_data.stat.dpm_levels {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("dpm_levels"), true, |ui| {
+                    dpm_levels.ui(ui);
+                    collapsing_plot(
+                        ui,
+                        &fl!("dpm_residency"),
+                        true,
+                        |ui| self.egui_dpm_residency(ui, dpm_levels),
+                    );
+                });
+            }
+
+            if let Some(ras) = &self.buf_data.stat.ras {
+                ui.add_space(SPACE);
+                collapsing(ui, &fl!("ras_error_count"), true, |ui| ras.ui(ui));
+            } else if let Some(ecc) = &self.buf_data.stat.memory_error_count {
                 ui.add_space(SPACE);
                 collapsing(ui, &fl!("ecc_memory_error_count"), true, |ui| ecc.ui(ui));
             }
@@ -496,6 +586,22 @@ impl MyApp {
                 }
             }
 
+            if !self.buf_data.stat.event_log.is_empty() {
+                collapsing(ui, &fl!("event_log"), true, |ui| {
+                    for ev in self.buf_data.stat.event_log.events.iter().rev() {
+                        ui.label(ev.to_string());
+                    }
+                });
+            }
+
+            if !self.buf_data.stat.throttle_log.is_empty() {
+                collapsing(ui, &fl!("throttle_events"), true, |ui| self.egui_throttle_events(ui));
+            }
+
+            if self.buf_data.stat.displays.is_some() {
+                collapsing(ui, &fl!("displays"), true, |ui| self.egui_displays(ui));
+            }
+
             collapsing(ui, &fl!("throttling_log"), false, |ui| {
                 for (time, value) in self.buf_data.history.throttling_history.iter() {
                     ui.label(format!("{time:.1}s: {:?}", value.get_all_throttler()));
@@ -517,14 +623,23 @@ impl eframe::App for MyApp {
                 }
             }
 
-            self.buf_data = self.buf_vec_data
+            {
+                let lock = self.arc_device_path_list.try_lock();
+                if let Ok(device_path_list) = lock {
+                    self.device_path_list.clone_from(&device_path_list);
+                }
+            }
+
+            // the selected device may have been removed
+            let data = self.buf_vec_data
                 .iter()
                 .find(|&d| self.selected_pci_bus == d.pci_bus)
-                .unwrap_or_else(|| {
-                    eprintln!("invalid PCI bus: {}", self.selected_pci_bus);
-                    panic!();
-                })
-                .clone();
+                .or_else(|| self.buf_vec_data.first());
+
+            if let Some(data) = data {
+                self.selected_pci_bus = data.pci_bus;
+                self.buf_data = data.clone();
+            }
         }
 
         {
@@ -5,6 +5,9 @@ use libamdgpu_top::{
         GpuMetrics,
     },
     app::AppAmdgpuTop,
+    pp_table::{PPTableFieldDiff, PPTables},
+    atom::AtomBios,
+    gpu_metrics::GpuMetricsDecode,
     DevicePath,
 };
 use serde_json::{json, Map, Value};
@@ -15,11 +18,19 @@ pub fn drm_info_json(device_path_list: &[DevicePath]) {
         let vec_conn_info = libamdgpu_top::connector_info(device_path);
         let vec_conn_info = vec_conn_info.iter().map(|conn| conn.json()).collect();
         let node = device_path.card.display();
-
-        json!({
+        let mut m = json!({
             "Node": node.to_string(),
             "Connectors": Value::Array(vec_conn_info),
-        })
+        });
+
+        if let (Some(m), Some(Value::Object(mut objects))) = (
+            m.as_object_mut(),
+            libamdgpu_top::drm_mode_objects(device_path).map(|o| o.json()),
+        ) {
+            m.append(&mut objects);
+        }
+
+        m
     }).collect();
 
     println!("{}", Value::Array(vec_drm_info_json));
@@ -38,6 +49,86 @@ pub fn gpu_metrics_json(_title: &str, device_path_list: &[DevicePath]) {
     println!("{}", Value::Array(vec_metrics_json));
 }
 
+pub fn pp_table_json(device_path_list: &[DevicePath]) {
+    let vec_pp_table_json: Vec<Value> = device_path_list.iter().filter_map(|device_path| {
+        let amdgpu_dev = device_path.init().ok()?;
+        let pp_tables = PPTables::get(device_path, &amdgpu_dev);
+        let mut m = Map::new();
+
+        m.insert("device_path".to_string(), device_path.json());
+        m.insert(
+            "SMU (MP1) version".to_string(),
+            pp_tables.smu_version
+                .map(|(major, minor, rev)| format!("{major}.{minor}.{rev}"))
+                .into(),
+        );
+
+        for dump in &pp_tables.tables {
+            let v = dump.bytes.as_ref().map(|bytes| json!({
+                "size": bytes.len(),
+                "raw": bytes.iter().map(|b| format!("{b:02x}")).collect::<String>(),
+                "PPTable": dump.tree().map(|tree| tree.json()),
+            }));
+
+            m.insert(dump.source.to_string(), v.into());
+        }
+
+        Some(m.into())
+    }).collect();
+
+    println!("{}", Value::Array(vec_pp_table_json));
+}
+
+pub fn pp_table_diff_json(label_a: &str, label_b: &str, diff: &[PPTableFieldDiff]) {
+    let diff: Vec<Value> = diff.iter().map(|d| d.json()).collect();
+
+    println!("{}", json!({
+        "A": label_a,
+        "B": label_b,
+        "diff": diff,
+    }));
+}
+
+pub fn vbios_tables_json(list: &[(DevicePath, Option<AtomBios>)]) {
+    let vec_json: Vec<Value> = list.iter().map(|(device_path, atom)| json!({
+        "device_path": device_path.json(),
+        "ATOM BIOS": atom.as_ref().map(|atom| atom.json()),
+    })).collect();
+
+    println!("{}", Value::Array(vec_json));
+}
+
+pub fn decoded_gpu_metrics_json(list: &[GpuMetricsDecode]) {
+    let vec_json: Vec<Value> = list.iter().map(|d| {
+        let header = d.header.map(|h| json!({
+            "structure_size": h.structure_size,
+            "format_revision": h.format_revision,
+            "content_revision": h.content_revision,
+        }));
+        let (metrics, error, raw) = match &d.metrics {
+            Ok(m) => (m.json(), Value::Null, Value::Null),
+            Err(err) => (
+                Value::Null,
+                Value::String(err.clone()),
+                d.raw_fields().iter().map(|(offset, val)| json!({
+                    "offset": offset,
+                    "value": val,
+                })).collect::<Vec<_>>().into(),
+            ),
+        };
+
+        json!({
+            "source": d.source,
+            "header": header,
+            "gpu_metrics": metrics,
+            "error": error,
+            "raw_fields": raw,
+        })
+    }).collect();
+
+    println!("{}", Value::Array(vec_json));
+}
+
 pub fn dump_json(device_path_list: &[DevicePath]) {
     let vec_json_info: Vec<Value> = device_path_list.iter().filter_map(|device_path| {
         let amdgpu_dev = device_path.init().ok()?;
@@ -186,6 +277,7 @@ impl JsonInfo for AppAmdgpuTop {
             "L3 Cache": self.device_info.total_l3_cache_size_mib << 20,
             "Power Cap": power_cap,
             "VBIOS": vbios,
+            "Firmware": self.device_info.firmware.json(),
             "Video Caps": video_caps,
             "PCIe Link": link_speed_width,
             "Power Profiles": power_profiles,
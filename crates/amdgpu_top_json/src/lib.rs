@@ -1,13 +1,15 @@
-use libamdgpu_top::{DevicePath, stat};
+use libamdgpu_top::{DevicePath, stat, summary};
 use libamdgpu_top::app::*;
+use libamdgpu_top::uevent::{self, DeviceEventKind, Uevent};
 use serde_json::{json, Value};
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 use std::path::PathBuf;
 use std::io::Write;
 
 mod output_json;
 mod dump;
-pub use dump::{dump_json, drm_info_json, gpu_metrics_json, JsonInfo};
+pub use dump::{dump_json, decoded_gpu_metrics_json, drm_info_json, gpu_metrics_json, pp_table_json, pp_table_diff_json, vbios_tables_json, JsonInfo};
 
 pub fn version_json(title: &str) {
     let version = json!({
@@ -35,12 +37,14 @@ pub struct JsonApp {
     pub base_time: Instant,
     pub interval: Duration,
     pub duration_time: Duration,
-    pub delay: Duration,
     pub iterations: u32,
     pub no_pc: bool,
     pub amdgpu_top_version: Value,
     pub rocm_version: Value,
     pub title: String,
+    pub update_process_index_interval: u64,
+    pub app_opt: AppOption,
+    pub uevent_rx: Option<mpsc::Receiver<Uevent>>,
 }
 
 impl JsonApp {
@@ -51,11 +55,11 @@ impl JsonApp {
         update_process_index_interval: u64,
         iterations: u32,
         no_pc: bool,
+        app_opt: AppOption,
     ) -> Self {
         let interval = Duration::from_millis(refresh_period);
-        let delay = interval / 100;
         let (mut vec_device_info, sus_app_list) =
-            JsonDeviceInfo::from_device_path_list(device_path_list);
+            JsonDeviceInfo::from_device_path_list(device_path_list, &app_opt);
 
         for device in vec_device_info.iter_mut() {
             device.app.stat.fdinfo.interval = interval;
@@ -84,36 +88,61 @@ impl JsonApp {
             base_time,
             duration_time,
             interval,
-            delay,
             iterations,
             no_pc,
             amdgpu_top_version: amdgpu_top_version(),
             rocm_version: libamdgpu_top::get_rocm_version().map_or(Value::Null, Value::String),
             title: title.to_string(),
+            update_process_index_interval,
+            app_opt,
+            uevent_rx: uevent::spawn_uevent_monitor(),
         }
     }
 
-    pub fn update(&mut self) {
-        if !self.no_pc {
-            for device in self.vec_device_info.iter_mut() {
-                device.app.clear_pc();
+    fn handle_uevents(&mut self) {
+        let Some(rx) = &self.uevent_rx else { return };
+        let uevents: Vec<Uevent> = rx.try_iter().collect();
+
+        for uevent in uevents {
+            let Some(kind) = DeviceEventKind::from_uevent(&uevent) else { continue };
+            let Some(pci) = uevent.pci_bus() else { continue };
+
+            match kind {
+                DeviceEventKind::Added => {
+                    if self.vec_device_info.iter().any(|d| d.app.device_path.pci == pci)
+                    || self.sus_app_list.iter().any(|d| d.pci == pci)
+                    {
+                        continue;
+                    }
+
+                    let Some(device_path) = DevicePath::from_pci(pci) else { continue };
+
+                    stat::spawn_update_index_thread(
+                        vec![device_path.clone()],
+                        self.update_process_index_interval,
+                    );
+                    self.sus_app_list.push(device_path);
+                },
+                DeviceEventKind::Removed => {
+                    self.vec_device_info.retain(|d| d.app.device_path.pci != pci);
+                    self.sus_app_list.retain(|d| d.pci != pci);
+                },
+                _ => {
+                    if let Some(d) = self.vec_device_info
+                        .iter_mut()
+                        .find(|d| d.app.device_path.pci == pci)
+                    {
+                        d.app.handle_device_event(kind);
+                    }
+                },
             }
         }
+    }
 
-        if !self.no_pc {
-            for _ in 0..100 {
-                for device in self.vec_device_info.iter_mut() {
-                    device.app.update_pc();
-                }
-                std::thread::sleep(self.delay);
-            }
+    pub fn update(&mut self) {
+        self.handle_uevents();
 
-            for device in self.vec_device_info.iter_mut() {
-                device.app.update_pc_usage();
-            }
-        } else {
-            std::thread::sleep(self.delay * 100);
-        }
+        std::thread::sleep(self.interval);
 
         for device in self.vec_device_info.iter_mut() {
             device.app.update(self.interval);
@@ -127,7 +156,7 @@ impl JsonApp {
                 let Some(mut app) = AppAmdgpuTop::new(
                     amdgpu_dev,
                     sus_device.clone(),
-                    &Default::default(),
+                    &self.app_opt,
                 ) else { return true };
                 let info = app.json_info();
                 self.vec_device_info.push(JsonDeviceInfo { app, info });
@@ -157,6 +186,16 @@ impl JsonApp {
                 "duration": self.duration_time.as_millis(),
                 "unit": "ms",
             },
+            "perf_counter_sampling": self.app_opt.pc_sampler.map(|config| json!({
+                "rate": {
+                    "value": config.rate(),
+                    "unit": "Hz",
+                },
+                "window": {
+                    "value": config.window.as_millis(),
+                    "unit": "ms",
+                },
+            })),
             "devices": devices,
             "suspended_devices": sus_devices,
             "devices_len": devices.len(),
@@ -167,12 +206,34 @@ impl JsonApp {
         })
     }
 
+    pub fn summary_json(&self) -> Value {
+        let devices: Vec<Value> = self.vec_device_info
+            .iter()
+            .map(|device| device.summary_json())
+            .collect();
+
+        json!({
+            "summary": {
+                "period": {
+                    "duration": self.duration_time.as_millis(),
+                    "unit": "ms",
+                },
+                "devices": devices,
+            },
+        })
+    }
+
     pub fn run(&mut self) {
         let mut n = 0;
 
+        // stop the loop on SIGINT/SIGTERM to print the summary and stop the GFXOFF residency logging
+        summary::set_terminate_handler();
+
         loop {
             self.update();
 
+            if summary::is_terminated() { break }
+
             let s = self.json().to_string();
 
             println!("{s}");
@@ -182,24 +243,41 @@ impl JsonApp {
                 if self.iterations == n { break; }
             }
         }
+
+        if self.app_opt.summary.is_some() {
+            println!("{}", self.summary_json());
+        }
     }
 
     pub fn run_fifo(&mut self, fifo_path: PathBuf) {
+        // stop the loop on SIGINT/SIGTERM to print the summary and stop the GFXOFF residency logging
+        summary::set_terminate_handler();
+
         loop {
             self.update();
 
+            if summary::is_terminated() { break }
+
             let s = self.json().to_string();
 
-            let mut f = std::fs::OpenOptions::new()
-                .read(true)
-                .write(true)
-                .open(&fifo_path)
-                .unwrap();
+            Self::write_fifo(&fifo_path, &s);
+        }
 
-            f.write_all(s.as_bytes()).unwrap();
-            f.flush().unwrap();
+        if self.app_opt.summary.is_some() {
+            Self::write_fifo(&fifo_path, &self.summary_json().to_string());
         }
     }
+
+    fn write_fifo(fifo_path: &PathBuf, s: &str) {
+        let mut f = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(fifo_path)
+            .unwrap();
+
+        f.write_all(s.as_bytes()).unwrap();
+        f.flush().unwrap();
+    }
 }
 
 pub struct JsonDeviceInfo {
@@ -208,13 +286,16 @@ pub struct JsonDeviceInfo {
 }
 
 impl JsonDeviceInfo {
-    pub fn from_device_path_list(device_path_list: &[DevicePath]) -> (
+    pub fn from_device_path_list(
+        device_path_list: &[DevicePath],
+        app_opt: &AppOption,
+    ) -> (
         Vec<Self>,
         Vec<DevicePath>,
     ) {
         let (vec_app, sus_app_list) = AppAmdgpuTop::create_app_and_suspended_list(
             device_path_list,
-            &Default::default(),
+            app_opt,
         );
         let vec_json_device = vec_app
             .into_iter()
@@ -233,6 +314,15 @@ impl JsonDeviceInfo {
             "Info": self.info,
             "GRBM": if !no_pc { self.app.stat.grbm.json() } else { Value::Null },
             "GRBM2": if !no_pc { self.app.stat.grbm2.json() } else { Value::Null },
+            "Custom Perf Counters": if !no_pc {
+                self.app.stat.custom_pc
+                    .iter()
+                    .map(|pc| (pc.name.clone(), pc.json()))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into()
+            } else {
+                Value::Null
+            },
             "VRAM": self.app.stat.vram_usage.json(),
             "Sensors": self.app.stat.sensors.as_ref().map(|s| s.json()),
             "fdinfo": self.app.stat.fdinfo.json(),
@@ -240,6 +330,34 @@ impl JsonDeviceInfo {
             "Total fdinfo": self.app.stat.fdinfo.fold_fdinfo_usage().json(),
             "gpu_metrics": self.app.stat.metrics.as_ref().map(|m| m.json()),
             "gpu_activity": self.app.stat.activity.json(),
+            "DPM Levels": self.app.stat.dpm_levels.as_ref().map(|d| d.json()),
+            "RAS": self.app.stat.ras.as_ref().map(|r| r.json()),
+            "Events": self.app.stat.event_log.json(),
+            "Throttle Events": self.app.stat.throttle_log.json(),
+            "Energy": self.app.stat.energy.json(),
+            "GFXOFF": self.app.stat.gfxoff.as_ref().map(|g| g.json()),
+            "Process Power": self.app.stat.proc_power.json(),
+            "Displays": self.app.stat.displays.as_ref().map(|d| d.json()),
+        })
+    }
+
+    pub fn summary_json(&self) -> Value {
+        let dpm_residency = self.app.stat.dpm_levels.as_ref().map(|d| {
+            let m: serde_json::Map<String, Value> = d.residency
+                .iter()
+                .map(|r| (r.type_.to_string(), r.json()))
+                .collect();
+
+            Value::from(m)
+        });
+
+        json!({
+            "pci": self.app.device_path.pci.to_string(),
+            "DeviceName": self.app.device_path.device_name,
+            "DPM Residency": dpm_residency,
+            "Energy": self.app.stat.energy.json(),
+            "GFXOFF": self.app.stat.gfxoff.as_ref().map(|g| g.json()),
+            "Statistics": self.app.summary.as_ref().map(|s| s.lock().unwrap().json()),
         })
     }
 }
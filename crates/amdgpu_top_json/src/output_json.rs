@@ -8,8 +8,17 @@ use libamdgpu_top::{
     ConnectorInfo,
     drmModePropType,
     drmModeModeInfo,
+    summary::SessionSummary,
+    uevent::DeviceEventLog,
+    edid::Edid,
+    pp_table::{DebugValue, PPTableFieldDiff, Scalar},
+    atom::{AtomBios, AtomTableHeader},
+    firmware::FirmwareInfo,
+    DrmModeObjects,
+    fourcc_string,
+    modifier_string,
 };
-use stat::{FdInfoStat, FdInfoUsage, GpuActivity, Sensors, PerfCounter, ProcUsage};
+use stat::{DpmLevels, DpmResidency, RasErrorStat, ThrottleEventLog, EnergyMeter, DisplayMonitor, GfxoffMonitor, GfxoffResidency, ProcPowerStat, FdInfoStat, FdInfoUsage, GpuActivity, Sensors, PerfCounter, ProcUsage};
 use xdna::{XdnaFdInfoUsage, XdnaFdInfoStat};
 use serde_json::{json, Map, Value};
 use crate::OutputJson;
@@ -119,6 +128,11 @@ impl OutputJson for Sensors {
             self.power_profile.map_or(Value::Null, |pp| Value::String(pp.to_string())),
         );
 
+        m.insert(
+            "Performance Level".to_string(),
+            self.perf_level.map_or(Value::Null, |level| Value::String(level.to_string())),
+        );
+
         m.into()
     }
 }
@@ -428,6 +442,257 @@ impl OutputJson for GpuActivity {
     }
 }
 
+impl OutputJson for DpmLevels {
+    fn json(&self) -> Value {
+        let mut m = Map::new();
+
+        for clk in &self.clocks {
+            let levels: Vec<Value> = clk.levels.iter().map(|level| json!({
+                "level": level.index.map_or("S".to_string(), |i| i.to_string()),
+                "value": level.mhz,
+                "unit": "MHz",
+                "current": level.is_current,
+            })).collect();
+
+            m.insert(
+                clk.type_.to_string(),
+                json!({
+                    "current_level": clk.current_level().map(|level| level.index),
+                    "levels": levels,
+                }),
+            );
+        }
+
+        m.into()
+    }
+}
+
+impl OutputJson for DpmResidency {
+    fn json(&self) -> Value {
+        let levels: Vec<Value> = self.levels.iter().zip(self.time.iter()).zip(self.percentages())
+            .map(|((level, time), per)| json!({
+                "level": level.index.map_or("S".to_string(), |i| i.to_string()),
+                "clock": level.mhz,
+                "time": time.as_millis(),
+                "value": per,
+                "unit": "%",
+            }))
+            .collect();
+
+        json!({
+            "total_time": {
+                "value": self.total.as_millis(),
+                "unit": "ms",
+            },
+            "levels": levels,
+        })
+    }
+}
+
+impl OutputJson for RasErrorStat {
+    fn json(&self) -> Value {
+        let mut blocks = Map::new();
+
+        for block in &self.blocks {
+            blocks.insert(
+                block.name.clone(),
+                json!({
+                    "corrected": block.corrected,
+                    "uncorrected": block.uncorrected,
+                    "delta_corrected": block.delta_corrected,
+                    "delta_uncorrected": block.delta_uncorrected,
+                }),
+            );
+        }
+
+        let bad_pages: Vec<Value> = self.bad_pages.iter().map(|page| json!({
+            "pfn": page.pfn,
+            "size": page.size,
+            "status": page.status.to_string(),
+        })).collect();
+
+        json!({
+            "blocks": blocks,
+            "bad_pages": bad_pages,
+        })
+    }
+}
+
+impl OutputJson for ThrottleEventLog {
+    fn json(&self) -> Value {
+        self.events.iter().map(|ev| json!({
+            "start_time": ev.start_time,
+            "throttler": ev.throttler,
+            "category": ev.category.to_string(),
+            "duration": {
+                "value": ev.duration.as_millis(),
+                "unit": "ms",
+            },
+            "is_active": ev.is_active,
+            "onset": {
+                "SCLK": { "value": ev.onset.sclk, "unit": "MHz" },
+                "MCLK": { "value": ev.onset.mclk, "unit": "MHz" },
+                "Edge Temperature": { "value": ev.onset.edge_temp, "unit": "C" },
+                "Junction Temperature": { "value": ev.onset.junction_temp, "unit": "C" },
+                "Power": { "value": ev.onset.power, "unit": "W" },
+            },
+        })).collect()
+    }
+}
+
+impl OutputJson for DisplayMonitor {
+    fn json(&self) -> Value {
+        let displays: Vec<Value> = self.displays.iter().map(|disp| json!({
+            "connector_id": disp.connector_id,
+            "name": disp.name,
+            "connected": disp.connected,
+            "mode": disp.mode.map(|mode| json!({
+                "crtc_id": mode.crtc_id,
+                "hdisplay": mode.hdisplay,
+                "vdisplay": mode.vdisplay,
+                "refresh_rate": mode.refresh_rate,
+            })),
+            "vrr_capable": disp.vrr_capable,
+            "vrr_enabled": disp.vrr_enabled,
+            "max_bpc": disp.max_bpc,
+            "colorspace": disp.colorspace,
+            "hdr_output_metadata": disp.hdr_output_metadata,
+        })).collect();
+        let events: Vec<Value> = self.events.iter().map(|ev| json!({
+            "time": ev.time,
+            "name": ev.name,
+            "change": ev.change,
+        })).collect();
+
+        json!({
+            "displays": displays,
+            "events": events,
+        })
+    }
+}
+
+impl OutputJson for EnergyMeter {
+    fn json(&self) -> Value {
+        json!({
+            "Session": {
+                "value": self.session_wh(),
+                "unit": "Wh",
+            },
+            "Counter": {
+                "value": self.counter_wh(),
+                "unit": "Wh",
+            },
+            "source": self.source.map(|s| s.to_string()),
+        })
+    }
+}
+
+impl OutputJson for GfxoffMonitor {
+    fn json(&self) -> Value {
+        let r = &self.residency;
+        let residency: Map<String, Value> = GfxoffResidency::STATUS_LIST
+            .iter()
+            .map(|status| (status.to_string(), json!({
+                "time": r.time(*status).as_millis(),
+                "value": r.percentage(*status),
+                "unit": "%",
+            })))
+            .collect();
+
+        json!({
+            "mode": format!("{:?}", self.mode),
+            "status": self.status.to_string(),
+            "total_time": {
+                "value": r.total().as_millis(),
+                "unit": "ms",
+            },
+            "sampled_residency": residency,
+            "sampling": "refresh period",
+            "observed_status_changes": r.observed_status_changes,
+            "entries": r.entries(),
+            "entry_count": r.entry_count,
+            "debugfs_residency": r.debugfs_residency.map(|v| json!({
+                "value": v,
+                "unit": "%",
+            })),
+        })
+    }
+}
+
+impl OutputJson for ProcPowerStat {
+    fn json(&self) -> Value {
+        let m: Map<String, Value> = self.map
+            .iter()
+            .map(|(pid, p)| (pid.to_string(), json!({
+                "Power": {
+                    "value": p.power,
+                    "unit": "W",
+                },
+                "Energy": {
+                    "value": p.energy_wh(),
+                    "unit": "Wh",
+                },
+            })))
+            .collect();
+
+        json!({
+            "model": self.model.to_string(),
+            "processes": m,
+        })
+    }
+}
+
+impl OutputJson for SessionSummary {
+    fn json(&self) -> Value {
+        let mut items = Map::new();
+
+        for item in &self.items {
+            let Some(v) = item.stat.calc() else { continue };
+
+            items.insert(
+                item.label.clone(),
+                json!({
+                    "min": v.min,
+                    "mean": v.mean,
+                    "max": v.max,
+                    "p50": v.p50,
+                    "p95": v.p95,
+                    "p99": v.p99,
+                    "samples": item.stat.len(),
+                    "unit": item.unit,
+                }),
+            );
+        }
+
+        let top_processes: Vec<Value> = self.top_processes(10).iter().map(|proc| json!({
+            "pid": proc.pid,
+            "name": proc.name,
+            "GPU time": {
+                "value": proc.gpu_time.as_millis(),
+                "unit": "ms",
+            },
+        })).collect();
+
+        json!({
+            "duration": {
+                "value": self.duration.as_millis(),
+                "unit": "ms",
+            },
+            "items": items,
+            "top_processes": top_processes,
+        })
+    }
+}
+
+impl OutputJson for DeviceEventLog {
+    fn json(&self) -> Value {
+        self.events.iter().map(|ev| json!({
+            "time": ev.time,
+            "event": ev.kind.to_string(),
+        })).collect()
+    }
+}
+
 impl OutputJson for PCI::LINK {
     fn json(&self) -> Value {
         json!({
@@ -437,6 +702,227 @@ impl OutputJson for PCI::LINK {
     }
 }
 
+impl OutputJson for DebugValue {
+    fn json(&self) -> Value {
+        match self {
+            Self::Struct { fields, .. } => {
+                let m: Map<String, Value> = fields.iter().map(|(k, v)| (k.clone(), v.json())).collect();
+
+                Value::Object(m)
+            },
+            Self::Tuple { name, items } => match (name.as_str(), &items[..]) {
+                ("" | "Some", [item]) => item.json(),
+                ("", _) => Value::Array(items.iter().map(|v| v.json()).collect()),
+                (name, [item]) => json!({ name: item.json() }),
+                (name, _) => json!({ name: items.iter().map(|v| v.json()).collect::<Vec<Value>>() }),
+            },
+            Self::List(items) => Value::Array(items.iter().map(|v| v.json()).collect()),
+            Self::Map(entries) => {
+                let m: Map<String, Value> = entries.iter().map(|(k, v)| {
+                    let k = match k.json() {
+                        Value::String(s) => s,
+                        k => k.to_string(),
+                    };
+
+                    (k, v.json())
+                }).collect();
+
+                Value::Object(m)
+            },
+            Self::Scalar(s) => s.json(),
+        }
+    }
+}
+
+impl OutputJson for Scalar {
+    fn json(&self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Bool(b) => (*b).into(),
+            Self::Int(n) => (*n).into(),
+            Self::UInt(n) => (*n).into(),
+            Self::Float(n) if n.is_finite() => (*n).into(),
+            Self::Float(n) => n.to_string().into(),
+            Self::Str(s) | Self::Ident(s) => s.clone().into(),
+        }
+    }
+}
+
+impl OutputJson for PPTableFieldDiff {
+    fn json(&self) -> Value {
+        json!({
+            "path": self.path,
+            "category": self.category.to_string(),
+            "A": self.a.as_ref().map(|v| v.json()),
+            "B": self.b.as_ref().map(|v| v.json()),
+        })
+    }
+}
+
+impl OutputJson for FirmwareInfo {
+    fn json(&self) -> Value {
+        self.list.iter().map(|fw| json!({
+            "name": fw.name,
+            "type": fw.fw_type.to_string(),
+            "index": fw.index,
+            "feature": fw.feature,
+            "version": fw.version,
+            "version_hex": format!("{:#X}", fw.version),
+        })).collect::<Vec<_>>().into()
+    }
+}
+
+#[test]
+fn test_debug_value_json() {
+    let s = r#"Smu13(
+    PowerPlayTable {
+        name: "Navi \"3x\"\tA0",
+        golden_pp_id: 0,
+        overdrive: Some(
+            OverDrive(
+                1,
+                -50,
+            ),
+        ),
+        smc_pptable: SmcPptable {
+            SocketPowerLimitAc: [
+                303,
+                0,
+            ],
+            DcBtcGb: -100,
+            MaxVoltage: 18446744073709551615,
+            dBtcGbGfxDfllModelSelect: -0.25,
+            Epsilon: 1e-7,
+            FanPwmMin: None,
+            Enabled: true,
+        },
+    },
+)"#;
+    let tree = DebugValue::parse(s).unwrap();
+
+    assert_eq!(tree.json(), json!({
+        "Smu13": {
+            "name": "Navi \"3x\"\tA0",
+            "golden_pp_id": 0,
+            "overdrive": { "OverDrive": [1, -50] },
+            "smc_pptable": {
+                "SocketPowerLimitAc": [303, 0],
+                "DcBtcGb": -100,
+                "MaxVoltage": u64::MAX,
+                "dBtcGbGfxDfllModelSelect": -0.25,
+                "Epsilon": 1e-7,
+                "FanPwmMin": null,
+                "Enabled": true,
+            },
+        },
+    }));
+
+    let v = tree.json();
+    let pp = &v["Smu13"]["smc_pptable"];
+
+    assert!(pp["DcBtcGb"].is_i64());
+    assert!(pp["MaxVoltage"].is_u64());
+    assert!(pp["dBtcGbGfxDfllModelSelect"].is_f64());
+}
+
+impl OutputJson for AtomBios {
+    fn json(&self) -> Value {
+        let header = |h: &AtomTableHeader| json!({
+            "format_revision": h.format_revision,
+            "content_revision": h.content_revision,
+            "size": h.size,
+        });
+        let pairs = |v: &[(&str, u32)]| -> Value {
+            v.iter().map(|(k, v)| (k.to_string(), Value::from(*v))).collect::<Map<String, Value>>().into()
+        };
+
+        let data_tables: Vec<Value> = self.data_tables.iter().map(|t| json!({
+            "index": t.index,
+            "name": t.name,
+            "offset": t.offset,
+            "header": header(&t.header),
+        })).collect();
+        let firmware_info = self.firmware_info.as_ref().map(|fw| json!({
+            "header": header(&fw.header),
+            "firmware_revision": fw.firmware_revision,
+            "bootup_sclk_mhz": fw.bootup_sclk_mhz,
+            "bootup_mclk_mhz": fw.bootup_mclk_mhz,
+            "bootup_vddc_mv": fw.bootup_vddc_mv,
+            "bootup_vddci_mv": fw.bootup_vddci_mv,
+            "bootup_mvddc_mv": fw.bootup_mvddc_mv,
+            "bootup_vddgfx_mv": fw.bootup_vddgfx_mv,
+            "mem_module_id": fw.mem_module_id,
+            "cooling_solution_id": fw.cooling_solution_id,
+            "firmware_capability": fw.firmware_capability,
+            "capabilities": fw.capabilities(),
+        }));
+        let vram_info = self.vram_info.as_ref().map(|vram| json!({
+            "header": header(&vram.header),
+            "modules": vram.modules.iter().map(|m| json!({
+                "memory_type": m.memory_type,
+                "memory_type_name": m.memory_type_name(),
+                "vendor_id": m.vendor_id,
+                "vendor": m.vendor_name(),
+                "revision_id": m.revision_id,
+                "channel_num": m.channel_num,
+                "channel_width": m.channel_width,
+                "density": m.density,
+                "memory_size_mib": m.memory_size_mib,
+                "max_mem_clk_mhz": m.max_mem_clk_mhz,
+                "mem_voltage_mv": m.mem_voltage_mv,
+                "part_number": m.part_number,
+            })).collect::<Vec<Value>>(),
+            "gddr6_ac_timings": vram.gddr6_ac_timings.iter().map(|t| json!({
+                "mem_block_id": t.mem_block_id,
+                "max_memclk_mhz": t.max_memclk_mhz,
+                "timings": pairs(&t.timings),
+            })).collect::<Vec<Value>>(),
+        }));
+        let display_object_info = self.display_object_info.as_ref().map(|disp| json!({
+            "header": header(&disp.header),
+            "supported_devices": disp.supported_devices,
+            "paths": disp.paths.iter().map(|p| json!({
+                "display_objid": p.display_objid,
+                "connector": p.connector_name(),
+                "encoder_objid": p.encoder_objid,
+                "encoder": p.encoder_name(),
+                "device_tag": p.device_tag,
+            })).collect::<Vec<Value>>(),
+        }));
+        let integrated_system_info = self.integrated_system_info.as_ref().map(|igp| json!({
+            "header": header(&igp.header),
+            "vbios_misc": igp.vbios_misc,
+            "gpu_cap_info": igp.gpu_cap_info,
+            "system_config": igp.system_config,
+            "cpu_cap_info": igp.cpu_cap_info,
+            "gpuclk_ss_percentage": igp.gpuclk_ss_percentage as f32 / 100.0,
+            "memory_type": igp.memory_type,
+            "uma_channel_number": igp.uma_channel_number,
+            "htc_tmp_limit": igp.htc_tmp_limit,
+            "htc_hyst_limit": igp.htc_hyst_limit,
+        }));
+        let voltage_objects: Vec<Value> = self.voltage_objects.iter().map(|obj| json!({
+            "voltage_type": obj.voltage_type_name(),
+            "voltage_mode": obj.voltage_mode_name(),
+            "size": obj.size,
+            "details": pairs(&obj.details),
+            "gpio_lut_mv": obj.gpio_lut_mv,
+        })).collect();
+
+        json!({
+            "rom_header": header(&self.rom_header),
+            "subsystem_vendor_id": self.subsystem_vendor_id,
+            "subsystem_id": self.subsystem_id,
+            "data_tables": data_tables,
+            "firmware_info": firmware_info,
+            "vram_info": vram_info,
+            "display_object_info": display_object_info,
+            "integrated_system_info": integrated_system_info,
+            "voltage_objects": voltage_objects,
+        })
+    }
+}
+
 impl OutputJson for DevicePath {
     fn json(&self) -> Value {
         json!({
@@ -490,6 +976,112 @@ impl OutputJson for ConnectorInfo {
             "type_id": self.connector_type_id,
             "connection": self.connection.to_string(),
             "Properties": Value::Object(props),
+            "EDID": self.edid.as_ref().map(|edid| edid.json()),
+        })
+    }
+}
+
+impl OutputJson for DrmModeObjects {
+    fn json(&self) -> Value {
+        let crtcs: Vec<Value> = self.crtcs.iter().map(|crtc| json!({
+            "index": crtc.index,
+            "id": crtc.crtc_id,
+            "fb_id": crtc.fb_id,
+            "x": crtc.position.0,
+            "y": crtc.position.1,
+            "gamma_size": crtc.gamma_size,
+            "active": crtc.is_active(),
+            "mode": crtc.mode.as_ref().map(|m| json!({
+                "name": m.name,
+                "hdisplay": m.hdisplay,
+                "vdisplay": m.vdisplay,
+                "refresh_rate": m.refresh_rate,
+            })),
+        })).collect();
+        let encoders: Vec<Value> = self.encoders.iter().map(|encoder| json!({
+            "id": encoder.encoder_id,
+            "type": encoder.type_name(),
+            "crtc_id": encoder.crtc_id,
+            "possible_crtcs": encoder.possible_crtcs,
+        })).collect();
+        let planes: Vec<Value> = self.planes.iter().map(|plane| {
+            let in_formats: Vec<Value> = plane.in_formats.iter().map(|(modifier, formats)| json!({
+                "modifier": modifier,
+                "modifier_name": modifier_string(*modifier),
+                "formats": formats.iter().map(|f| fourcc_string(*f)).collect::<Vec<String>>(),
+            })).collect();
+
+            json!({
+                "id": plane.plane_id,
+                "type": plane.plane_type.map(|t| t.to_string()),
+                "crtc_id": plane.crtc_id,
+                "possible_crtcs": plane.possible_crtcs,
+                "formats": plane.formats.iter().map(|f| fourcc_string(*f)).collect::<Vec<String>>(),
+                "IN_FORMATS": in_formats,
+                "FB": plane.fb.as_ref().map(|fb| json!({
+                    "id": fb.fb_id,
+                    "width": fb.width,
+                    "height": fb.height,
+                    "format": fourcc_string(fb.pixel_format),
+                    "modifier": fb.modifier,
+                    "modifier_name": fb.modifier.map(modifier_string),
+                })),
+            })
+        }).collect();
+
+        json!({
+            "min_size": [self.min_size.0, self.min_size.1],
+            "max_size": [self.max_size.0, self.max_size.1],
+            "CRTCs": crtcs,
+            "Encoders": encoders,
+            "Planes": planes,
+        })
+    }
+}
+
+impl OutputJson for Edid {
+    fn json(&self) -> Value {
+        let timings: Vec<Value> = self.all_timings().map(|t| json!({
+            "hactive": t.hactive,
+            "vactive": t.vactive,
+            "refresh_rate": t.refresh_rate,
+            "interlaced": t.interlaced,
+            "source": t.source.to_string(),
+        })).collect();
+
+        json!({
+            "manufacturer": self.manufacturer,
+            "product_code": self.product_code,
+            "serial_number": self.serial_number,
+            "serial_string": self.serial_string,
+            "monitor_name": self.monitor_name,
+            "week": self.week,
+            "year": self.year,
+            "version": format!("{}.{}", self.version.0, self.version.1),
+            "physical_size": self.physical_size.map(|(h, v)| json!({
+                "width": h,
+                "height": v,
+                "unit": "cm",
+            })),
+            "range_limits": self.range_limits.map(|r| json!({
+                "min_v_rate": r.min_v_rate,
+                "max_v_rate": r.max_v_rate,
+                "min_h_rate": r.min_h_rate,
+                "max_h_rate": r.max_h_rate,
+                "max_pixel_clock": r.max_pixel_clock,
+            })),
+            "vrr_range": self.vrr_range().map(|(min, max)| json!({
+                "min": min,
+                "max": max,
+                "unit": "Hz",
+            })),
+            "hdr_static_metadata": self.hdr_static_metadata().map(|hdr| json!({
+                "eotf": hdr.eotf_list(),
+                "max_luminance": hdr.max_luminance,
+                "max_frame_avg_luminance": hdr.max_frame_avg_luminance,
+                "min_luminance": hdr.min_luminance,
+            })),
+            "timings": timings,
         })
     }
 }
@@ -1,6 +1,6 @@
 use cursive::align::HAlign;
-use cursive::views::{LinearLayout, TextView, Panel, ResizedView};
-use cursive::view::SizeConstraint;
+use cursive::views::{HideableView, LinearLayout, TextView, Panel, ResizedView};
+use cursive::view::{Nameable, SizeConstraint};
 
 use libamdgpu_top::AMDGPU::{GPU_INFO, MetricsInfo};
 use libamdgpu_top::{AppDeviceInfo, DevicePath, Sampling};
@@ -12,9 +12,9 @@ use libamdgpu_top::app::{AppAmdgpuTop, AppAmdgpuTopStat, AppOption};
 const WIDE_TERM_COLS: u16 = 150;
 
 pub const TOGGLE_HELP: &str = concat!(
-    " (g)rbm g(r)bm2 (v)ram_usage (f)dinfo se(n)sor (m)etrics (h)igh_freq (q)uit \n",
+    " (g)rbm g(r)bm2 (v)ram_usage (f)dinfo se(n)sor (m)etrics (d)pm_levels (h)igh_freq (q)uit \n",
     " (P): sort_by_pid (V): sort_by_vram (G): sort_by_gfx (M): sort_by_media (R): reverse \n",
-    " (T): switch theme (light/dark)",
+    " (T): switch theme (light/dark) (E): reset energy counter",
 );
 
 #[derive(Clone)]
@@ -23,6 +23,7 @@ pub(crate) struct AppLayout {
     // pub index: usize,
     pub grbm_view: PerfCounterView,
     pub grbm2_view: PerfCounterView,
+    pub custom_pc_views: Vec<PerfCounterView>,
     pub vram_usage_view: VramUsageView,
     pub activity_view: ActivityView,
     pub fdinfo_view: AppTextView,
@@ -30,6 +31,11 @@ pub(crate) struct AppLayout {
     pub sensors_view: AppTextView,
     pub gpu_metrics_view: AppTextView,
     pub ecc_view: AppTextView,
+    pub dpm_levels_view: AppTextView,
+    pub dpm_residency_view: AppTextView,
+    pub event_log_view: AppTextView,
+    pub throttle_log_view: AppTextView,
+    pub display_view: AppTextView,
 }
 
 impl AppLayout {
@@ -38,6 +44,7 @@ impl AppLayout {
             no_pc,
             grbm_view: PerfCounterView::reserve(index),
             grbm2_view: PerfCounterView::reserve(index),
+            custom_pc_views: Vec::new(),
             vram_usage_view: VramUsageView::new(index),
             activity_view: ActivityView::new(index),
             fdinfo_view: Default::default(),
@@ -45,6 +52,11 @@ impl AppLayout {
             sensors_view: Default::default(),
             gpu_metrics_view: Default::default(),
             ecc_view: Default::default(),
+            dpm_levels_view: Default::default(),
+            dpm_residency_view: Default::default(),
+            event_log_view: Default::default(),
+            throttle_log_view: Default::default(),
+            display_view: Default::default(),
         }
     }
 
@@ -55,11 +67,16 @@ impl AppLayout {
     ) -> Self {
         let grbm_view = PerfCounterView::new(&app_amdgpu_top.stat.grbm, index);
         let grbm2_view = PerfCounterView::new(&app_amdgpu_top.stat.grbm2, index);
+        let custom_pc_views = app_amdgpu_top.stat.custom_pc
+            .iter()
+            .map(|pc| PerfCounterView::new(pc, index))
+            .collect();
 
         Self {
             no_pc,
             grbm_view,
             grbm2_view,
+            custom_pc_views,
             vram_usage_view: VramUsageView::new(index),
             activity_view: ActivityView::new(index),
             fdinfo_view: Default::default(),
@@ -67,12 +84,18 @@ impl AppLayout {
             sensors_view: Default::default(),
             gpu_metrics_view: Default::default(),
             ecc_view: Default::default(),
+            dpm_levels_view: Default::default(),
+            dpm_residency_view: Default::default(),
+            event_log_view: Default::default(),
+            throttle_log_view: Default::default(),
+            display_view: Default::default(),
         }
     }
 
     pub fn view(
         &self,
         title: &str,
+        index: usize,
         info_bar: String,
         stat: &AppAmdgpuTopStat,
         xdna_device_path: &Option<DevicePath>,
@@ -101,6 +124,30 @@ impl AppLayout {
                 layout.add_child(grbm_view);
                 layout.add_child(grbm2_view);
             }
+
+            let custom_pc_views: Vec<_> = self.custom_pc_views
+                .iter()
+                .zip(stat.custom_pc.iter())
+                .map(|(view, pc)| view.top_view(pc, true))
+                .collect();
+
+            if is_wide_term {
+                let mut custom_pc_views = custom_pc_views.into_iter();
+
+                while let Some(view) = custom_pc_views.next() {
+                    let mut row = LinearLayout::horizontal().child(view);
+
+                    if let Some(view) = custom_pc_views.next() {
+                        row.add_child(view);
+                    }
+
+                    layout.add_child(row);
+                }
+            } else {
+                for view in custom_pc_views {
+                    layout.add_child(view);
+                }
+            }
         }
 
         {
@@ -155,7 +202,33 @@ impl AppLayout {
             }
         }
 
-        if stat.memory_error_count.is_some() {
+        if stat.dpm_levels.is_some() {
+            let dpm_levels_view = self.dpm_levels_view.text.panel("DPM Levels");
+            let dpm_residency_view = self.dpm_residency_view.text.panel("DPM Residency");
+
+            if is_wide_term {
+                layout.add_child(
+                    LinearLayout::horizontal()
+                        .child(dpm_levels_view)
+                        .child(dpm_residency_view)
+                );
+            } else {
+                layout.add_child(dpm_levels_view);
+                layout.add_child(dpm_residency_view);
+            }
+        }
+
+        if stat.metrics.as_ref().and_then(|m| m.get_indep_throttle_status()).is_some() {
+            layout.add_child(self.throttle_log_view.text.panel("Throttle Events"));
+        }
+
+        if stat.displays.is_some() {
+            layout.add_child(self.display_view.text.panel("Displays"));
+        }
+
+        if stat.ras.is_some() {
+            layout.add_child(self.ecc_view.text.panel("ECC/RAS Error Count"));
+        } else if stat.memory_error_count.is_some() {
             layout.add_child(self.ecc_view.text.panel("ECC Error Count"));
         }
 
@@ -164,6 +237,12 @@ impl AppLayout {
             layout.add_child(self.xdna_fdinfo_view.text.panel(&title));
         }
 
+        {
+            let mut event_log_view = HideableView::new(self.event_log_view.text.panel("Events"));
+            event_log_view.set_visible(!stat.event_log.is_empty());
+
+            layout.add_child(event_log_view.with_name(event_log_view_name(index)));
+        }
         layout.add_child(TextView::new(TOGGLE_HELP));
 
         ResizedView::new(SizeConstraint::Free, SizeConstraint::Full, layout)
@@ -176,15 +255,22 @@ pub(crate) struct SuspendedTuiApp {
     pub no_pc: bool,
     pub index: usize,
     pub layout: AppLayout,
+    pub app_opt: AppOption,
 }
 
 impl SuspendedTuiApp {
-    pub fn new(device_path: DevicePath, no_pc: bool, index: usize) -> Self {
+    pub fn new(
+        device_path: DevicePath,
+        no_pc: bool,
+        index: usize,
+        app_opt: AppOption,
+    ) -> Self {
         Self {
             device_path,
             no_pc,
             index,
             layout: AppLayout::new(no_pc, index),
+            app_opt,
         }
     }
 
@@ -193,7 +279,7 @@ impl SuspendedTuiApp {
         let app_amdgpu_top = AppAmdgpuTop::new(
             amdgpu_dev,
             self.device_path.clone(),
-            &AppOption { pcie_bw: true },
+            &AppOption { pcie_bw: true, ..self.app_opt.clone() },
         )?;
 
         Some(TuiApp {
@@ -235,6 +321,7 @@ impl TuiApp {
     pub fn view(&self, title: &str) -> ResizedView<LinearLayout> {
         self.layout.view(
             title,
+            self.index,
             self.app_amdgpu_top.device_info.info_bar(),
             &self.app_amdgpu_top.stat,
             &self.app_amdgpu_top.xdna_device_path,
@@ -247,6 +334,7 @@ impl TuiApp {
         if flags.fdinfo {
             let _ = self.layout.fdinfo_view.print_fdinfo(
                 &mut self.app_amdgpu_top.stat.fdinfo,
+                &self.app_amdgpu_top.stat.proc_power,
                 flags.fdinfo_sort,
                 flags.reverse_sort,
             );
@@ -274,14 +362,39 @@ impl TuiApp {
                     }
                 }
             }
+
+            let _ = self.layout.sensors_view.print_energy_meter(&self.app_amdgpu_top.stat.energy);
+
+            if let Some(gfxoff) = &self.app_amdgpu_top.stat.gfxoff {
+                let _ = self.layout.sensors_view.print_gfxoff(gfxoff);
+            }
         } else {
             self.layout.sensors_view.text.clear();
         }
 
-        if let Some(ecc) = &self.app_amdgpu_top.stat.memory_error_count {
+        if let Some(ras) = &self.app_amdgpu_top.stat.ras {
+            let _ = self.layout.ecc_view.print_ras_error_stat(ras);
+        } else if let Some(ecc) = &self.app_amdgpu_top.stat.memory_error_count {
             let _ = self.layout.ecc_view.print_memory_error_count(ecc);
         }
 
+        let _ = self.layout.event_log_view.print_event_log(&self.app_amdgpu_top.stat.event_log);
+        let _ = self.layout.throttle_log_view.print_throttle_log(&self.app_amdgpu_top.stat.throttle_log);
+
+        if let Some(displays) = &self.app_amdgpu_top.stat.displays {
+            let _ = self.layout.display_view.print_displays(displays);
+        }
+
+        if flags.dpm_levels {
+            if let Some(dpm_levels) = &self.app_amdgpu_top.stat.dpm_levels {
+                let _ = self.layout.dpm_levels_view.print_dpm_levels(dpm_levels);
+                let _ = self.layout.dpm_residency_view.print_dpm_residency(dpm_levels);
+            }
+        } else {
+            self.layout.dpm_levels_view.text.clear();
+            self.layout.dpm_residency_view.text.clear();
+        }
+
         if flags.gpu_metrics {
             if let Some(metrics) = &self.app_amdgpu_top.stat.metrics {
                 let _ = self.layout.gpu_metrics_view.print_gpu_metrics(metrics);
@@ -295,12 +408,21 @@ impl TuiApp {
         if !self.no_pc {
             self.layout.grbm_view.set_value(&self.app_amdgpu_top.stat.grbm);
             self.layout.grbm2_view.set_value(&self.app_amdgpu_top.stat.grbm2);
+
+            for (view, pc) in self.layout.custom_pc_views.iter().zip(self.app_amdgpu_top.stat.custom_pc.iter()) {
+                view.set_value(pc);
+            }
         }
 
         self.layout.sensors_view.text.set();
         self.layout.fdinfo_view.text.set();
         self.layout.xdna_fdinfo_view.text.set();
         self.layout.ecc_view.text.set();
+        self.layout.dpm_levels_view.text.set();
+        self.layout.dpm_residency_view.text.set();
+        self.layout.event_log_view.text.set();
+        self.layout.throttle_log_view.text.set();
+        self.layout.display_view.text.set();
         self.layout.gpu_metrics_view.text.set();
     }
 
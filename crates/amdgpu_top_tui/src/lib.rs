@@ -1,10 +1,12 @@
 use std::sync::{Arc, Mutex};
 use cursive::view::{Nameable, Scrollable};
-use cursive::{event::Key, menu, traits::With};
+use cursive::{event::Key, menu, traits::With, views::Dialog};
 use cursive::theme::{BorderStyle, Theme, Palette};
 
-use libamdgpu_top::{app::AppAmdgpuTop, DevicePath, Sampling, UiArgs};
+use libamdgpu_top::{app::{AppAmdgpuTop, AppOption}, DevicePath, Sampling, UiArgs};
 use libamdgpu_top::stat::{self, FdInfoSortType, PCType};
+use libamdgpu_top::uevent::{self, DeviceEventKind};
+use libamdgpu_top::summary;
 
 mod view;
 use view::*;
@@ -15,6 +17,8 @@ use app::*;
 mod smi;
 pub use smi::run_smi;
 
+mod power_control;
+
 #[derive(Debug, Clone)]
 struct ToggleOptions {
     grbm: bool,
@@ -27,6 +31,8 @@ struct ToggleOptions {
     fdinfo_sort: FdInfoSortType,
     reverse_sort: bool,
     gpu_metrics: bool,
+    dpm_levels: bool,
+    reset_energy: bool,
     select_index: usize,
     indexes: Vec<usize>,
     is_dark_mode: bool,
@@ -45,6 +51,8 @@ impl Default for ToggleOptions {
             fdinfo_sort: Default::default(),
             reverse_sort: false,
             gpu_metrics: true,
+            dpm_levels: true,
+            reset_energy: false,
             select_index: 0,
             indexes: Vec::new(),
             is_dark_mode: false,
@@ -63,6 +71,10 @@ pub fn run(
         no_pc,
         is_dark_mode,
         hide_fdinfo,
+        power_attribution,
+        summary,
+        pc_config,
+        pc_sampler,
         ..
     }: UiArgs,
 ) {
@@ -70,9 +82,18 @@ pub fn run(
     let title = title.to_string();
     let mut toggle_opt = ToggleOptions { is_dark_mode, fdinfo: !hide_fdinfo, ..Default::default() };
 
+    let app_opt = AppOption {
+        power_attribution,
+        summary: summary.then(Default::default),
+        gfxoff_residency_logging: true,
+        pc_config,
+        pc_sampler: (!no_pc).then_some(pc_sampler),
+        ..Default::default()
+    };
+    let summary_list = app_opt.summary.clone();
     let (vec_app, suspended_devices) = AppAmdgpuTop::create_app_and_suspended_list(
         &device_path_list,
-        &Default::default(),
+        &app_opt,
     );
     let mut vec_app: Vec<_> = vec_app
         .into_iter()
@@ -83,7 +104,7 @@ pub fn run(
     let mut vec_sus_app: Vec<_> = suspended_devices
         .into_iter()
         .enumerate()
-        .map(|(i, app)| SuspendedTuiApp::new(app, no_pc, app_len+i))
+        .map(|(i, app)| SuspendedTuiApp::new(app, no_pc, app_len+i, app_opt.clone()))
         .collect();
 
     for app in vec_app.iter_mut() {
@@ -140,6 +161,13 @@ pub fn run(
                 .delimiter()
                 .leaf("Quit", cursive::Cursive::quit),
         );
+
+        let (device_paths, power_profiles) = power_control::targets(&vec_app, &vec_sus_app);
+
+        menubar.add_subtree(
+            power_control::MENU_LABEL,
+            power_control::power_control_tree(device_paths, power_profiles),
+        );
     }
 
     {
@@ -185,11 +213,16 @@ pub fn run(
         siv.add_global_callback('M', AppTextView::cb_sort_by_media);
         siv.add_global_callback('n', AppTextView::cb_sensors);
         siv.add_global_callback('m', AppTextView::cb_gpu_metrics);
+        siv.add_global_callback('d', AppTextView::cb_dpm_levels);
         siv.add_global_callback('q', cursive::Cursive::quit);
         siv.add_global_callback('h', |siv| {
             let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
             opt.high_freq ^= true;
         });
+        siv.add_global_callback('E', |siv| {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.reset_energy = true;
+        });
         siv.add_global_callback('T', |siv| {
             let is_dark_mode;
             {
@@ -204,6 +237,8 @@ pub fn run(
     }
 
     let cb_sink = siv.cb_sink().clone();
+    let uevent_rx = uevent::spawn_uevent_monitor();
+    let mut next_index = vec_app.len() + vec_sus_app.len();
 
     std::thread::spawn(move || loop {
         {
@@ -213,28 +248,133 @@ pub fn run(
             }
         }
 
-        let sample = if flags.high_freq { Sampling::high() } else { Sampling::low() };
+        if flags.reset_energy {
+            for app in vec_app.iter_mut() {
+                app.app_amdgpu_top.stat.energy.reset();
+            }
 
-        {
-            let selected_app = vec_app
-                .iter_mut()
-                .find(|app| flags.select_index == app.index)
-                .unwrap();
+            flags.reset_energy = false;
+            toggle_opt.lock().unwrap().reset_energy = false;
+        }
 
-            if !no_pc {
-                for _ in 0..sample.count {
-                    selected_app.app_amdgpu_top.update_pc();
+        // rebuild the Power Control menu for hotplugged devices
+        let mut devices_changed = false;
 
-                    std::thread::sleep(sample.delay);
-                }
+        for uevent in uevent_rx.iter().flat_map(|rx| rx.try_iter()) {
+            let Some(kind) = DeviceEventKind::from_uevent(&uevent) else { continue };
+            let Some(pci) = uevent.pci_bus() else { continue };
+
+            match kind {
+                DeviceEventKind::Added => {
+                    if vec_app.iter().any(|app| app.app_amdgpu_top.device_path.pci == pci)
+                    || vec_sus_app.iter().any(|app| app.device_path.pci == pci)
+                    {
+                        continue;
+                    }
+
+                    let Some(device_path) = DevicePath::from_pci(pci) else { continue };
+
+                    stat::spawn_update_index_thread(vec![device_path.clone()], update_process_index);
+
+                    // the device will be added to the list when it becomes active
+                    let sus_app = SuspendedTuiApp::new(device_path, no_pc, next_index, app_opt.clone());
+                    let label = sus_app.label();
+                    next_index += 1;
+                    vec_sus_app.push(sus_app);
+                    devices_changed = true;
+
+                    cb_sink.send(Box::new(move |siv| {
+                        let subtree = siv.menubar().get_subtree(0).unwrap();
+                        let len = subtree.len();
+
+                        subtree.insert_leaf(len-2, label, |_siv: &mut cursive::Cursive| {});
+                    })).unwrap();
+                },
+                DeviceEventKind::Removed => {
+                    let removed = if let Some(pos) = vec_app
+                        .iter()
+                        .position(|app| app.app_amdgpu_top.device_path.pci == pci)
+                    {
+                        let app = vec_app.remove(pos);
+                        Some((app.index, app.label()))
+                    } else if let Some(pos) = vec_sus_app
+                        .iter()
+                        .position(|app| app.device_path.pci == pci)
+                    {
+                        let app = vec_sus_app.remove(pos);
+                        Some((app.index, app.label()))
+                    } else {
+                        None
+                    };
+
+                    let Some((index, label)) = removed else { continue };
+
+                    devices_changed = true;
+
+                    let new_select_index = if flags.select_index == index {
+                        vec_app.first().map(|app| app.index)
+                    } else {
+                        None
+                    };
 
-                selected_app.app_amdgpu_top.update_pc_usage();
-            } else {
-                std::thread::sleep(sample.to_duration());
+                    if let Some(i) = new_select_index {
+                        flags.select_index = i;
+                        toggle_opt.lock().unwrap().select_index = i;
+                    }
+
+                    cb_sink.send(Box::new(move |siv| {
+                        let screen = siv.screen_mut();
+
+                        if let Some(pos) = screen.find_layer_from_name(&index.to_string()) {
+                            screen.remove_layer(pos);
+                        }
+
+                        if let Some(pos) = new_select_index
+                            .and_then(|i| screen.find_layer_from_name(&i.to_string()))
+                        {
+                            screen.move_to_front(pos);
+                        }
+
+                        let subtree = siv.menubar().get_subtree(0).unwrap();
+
+                        if let Some(pos) = subtree.find_position(&label) {
+                            subtree.remove(pos);
+                        }
+
+                        siv.add_layer(Dialog::info(format!("{label} has been removed.")));
+                    })).unwrap();
+                },
+                _ => {
+                    if let Some(app) = vec_app
+                        .iter_mut()
+                        .find(|app| app.app_amdgpu_top.device_path.pci == pci)
+                    {
+                        app.app_amdgpu_top.handle_device_event(kind);
+
+                        if !app.app_amdgpu_top.stat.event_log.is_empty() {
+                            let index = app.index;
+
+                            cb_sink.send(Box::new(move |siv| show_event_log(siv, index))).unwrap();
+                        }
+                    }
+                },
             }
+        }
+
+        let sample = if flags.high_freq { Sampling::high() } else { Sampling::low() };
 
+        // only the selected device is shown
+        for app in vec_app.iter_mut() {
+            app.app_amdgpu_top.set_pc_sampling(flags.select_index == app.index);
+        }
+
+        std::thread::sleep(sample.to_duration());
+
+        if let Some(selected_app) = vec_app
+            .iter_mut()
+            .find(|app| flags.select_index == app.index)
+        {
             selected_app.update(&flags, &sample);
-            if !no_pc { selected_app.app_amdgpu_top.clear_pc(); }
         }
 
         vec_sus_app.retain(|sus_app| {
@@ -242,6 +382,7 @@ pub fn run(
 
             if is_active {
                 let title = title.clone();
+                let sus_label = sus_app.label();
                 let Some(tui_app) = sus_app.to_tui_app() else { return true };
                 let index = tui_app.index;
                 let label = tui_app.label();
@@ -251,11 +392,12 @@ pub fn run(
                 let app_layout = tui_app.layout.clone();
 
                 vec_app.push(tui_app);
+                devices_changed = true;
 
                 cb_sink.send(Box::new(move |siv| {
                     {
                         let view = app_layout
-                            .view(&title, info_bar, &stat, &xdna_device_path)
+                            .view(&title, index, info_bar, &stat, &xdna_device_path)
                             .scrollable()
                             .scroll_x(true)
                             .scroll_y(true)
@@ -270,11 +412,11 @@ pub fn run(
 
                     let menubar = siv.menubar();
                     let subtree = menubar.get_subtree(0).unwrap();
-                    let len = subtree.len();
-                    subtree.remove(len-3);
+                    let Some(pos) = subtree.find_position(&sus_label) else { return };
+                    subtree.remove(pos);
 
                     subtree.insert_leaf(
-                        len-3,
+                        pos,
                         label,
                         move |siv: &mut cursive::Cursive| {
                             let screen = siv.screen_mut();
@@ -292,10 +434,24 @@ pub fn run(
             !is_active
         });
 
+        if devices_changed {
+            let (device_paths, power_profiles) = power_control::targets(&vec_app, &vec_sus_app);
+
+            cb_sink.send(Box::new(move |siv| {
+                if let Some(tree) = siv.menubar().find_subtree(power_control::MENU_LABEL) {
+                    *tree = power_control::power_control_tree(device_paths, power_profiles);
+                }
+            })).unwrap();
+        }
+
         cb_sink.send(Box::new(cursive::Cursive::noop)).unwrap();
     });
 
     siv.run();
+
+    if let Some(list) = &summary_list {
+        summary::print_session_summary_list(list);
+    }
 }
 
 fn dark_mode() -> Theme {
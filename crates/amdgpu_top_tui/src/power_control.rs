@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use cursive::{menu, traits::With, views::Dialog};
+use libamdgpu_top::DevicePath;
+use libamdgpu_top::control::{ControlError, DeviceControl, PerformanceLevel};
+
+use crate::Opt;
+use crate::app::{SuspendedTuiApp, TuiApp};
+
+pub const MENU_LABEL: &str = "Power Control";
+
+/// (index, device path) of all devices and the power profiles supported by any of them
+pub fn targets(
+    vec_app: &[TuiApp],
+    vec_sus_app: &[SuspendedTuiApp],
+) -> (Vec<(usize, DevicePath)>, Vec<String>) {
+    let device_paths = vec_app
+        .iter()
+        .map(|app| (app.index, app.app_amdgpu_top.device_path.clone()))
+        .chain(vec_sus_app.iter().map(|app| (app.index, app.device_path.clone())))
+        .collect();
+    let mut power_profiles: Vec<String> = Vec::new();
+
+    for profile in vec_app.iter().flat_map(|app| &app.app_amdgpu_top.device_info.power_profiles) {
+        let profile = profile.to_string();
+
+        if !power_profiles.contains(&profile) {
+            power_profiles.push(profile);
+        }
+    }
+
+    (device_paths, power_profiles)
+}
+
+fn control_selected_device(
+    siv: &mut cursive::Cursive,
+    device_paths: &[(usize, DevicePath)],
+    f: impl FnOnce(&mut DeviceControl) -> Result<(), ControlError>,
+) {
+    let select_index = siv.user_data::<Opt>().unwrap().lock().unwrap().select_index;
+    let Some((_, device_path)) = device_paths.iter().find(|(i, _)| *i == select_index) else {
+        return;
+    };
+    let mut ctrl = DeviceControl::from_device_path(device_path);
+
+    if let Err(err) = f(&mut ctrl) {
+        let msg = if err.is_permission_denied() {
+            format!("{err}\n\nPlease run amdgpu_top as root to change the setting.")
+        } else {
+            err.to_string()
+        };
+
+        siv.add_layer(Dialog::info(msg).title(device_path.menu_entry()));
+    }
+}
+
+pub fn power_control_tree(
+    device_paths: Vec<(usize, DevicePath)>,
+    power_profiles: Vec<String>,
+) -> menu::Tree {
+    let device_paths = Arc::new(device_paths);
+
+    let profile_tree = menu::Tree::new().with(|tree| {
+        for profile in power_profiles {
+            let device_paths = device_paths.clone();
+
+            tree.add_leaf(profile.clone(), move |siv: &mut cursive::Cursive| {
+                control_selected_device(siv, &device_paths, |ctrl| ctrl.set_power_profile(&profile));
+            });
+        }
+    });
+
+    let perf_level_tree = menu::Tree::new().with(|tree| {
+        for level in PerformanceLevel::ALL {
+            let device_paths = device_paths.clone();
+
+            tree.add_leaf(level.as_str(), move |siv: &mut cursive::Cursive| {
+                control_selected_device(siv, &device_paths, |ctrl| ctrl.set_perf_level(level));
+            });
+        }
+    });
+
+    menu::Tree::new()
+        .subtree("Power Profile", profile_tree)
+        .subtree("Performance Level", perf_level_tree)
+}
@@ -1,39 +1,38 @@
 use std::fmt::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use cursive::align::HAlign;
 use cursive::view::{Nameable, Scrollable};
 use cursive::views::{HideableView, LinearLayout, TextContent, TextView, Panel};
 
 use libamdgpu_top::AMDGPU::MetricsInfo;
 use libamdgpu_top::{stat, DevicePath, Sampling, UiArgs};
-use stat::{GfxoffMonitor, GfxoffStatus, FdInfoSortType};
+use stat::{GfxoffStatus, FdInfoSortType};
+use libamdgpu_top::summary;
 
 use crate::{Text, AppTextView};
 
 const GPU_NAME_LEN: usize = 25;
 const LINE_LEN: usize = 150;
-const THR_LEN: usize = 48;
+const THR_LEN: usize = 38;
 const ECC_LABEL: &str = "ECC_UnCorr.";
 const ECC_LEN: usize = ECC_LABEL.len()-2;
 const PROC_TITLE: &str = "Processes";
 
-use libamdgpu_top::app::AppAmdgpuTop;
+use libamdgpu_top::app::{AppAmdgpuTop, AppOption};
 
 struct SmiApp {
     app_amdgpu_top: AppAmdgpuTop,
     index: usize,
-    gfxoff_monitor: Option<GfxoffMonitor>,
     fdinfo_view: AppTextView,
     info_text: Text,
 }
 
 impl SmiApp {
     pub fn new(app_amdgpu_top: AppAmdgpuTop, index: usize) -> Option<Self> {
-        let gfxoff_monitor = GfxoffMonitor::new(app_amdgpu_top.device_path.pci).ok();
-
         Some(Self {
             app_amdgpu_top,
             index,
-            gfxoff_monitor,
             fdinfo_view: Default::default(),
             info_text: Default::default(),
         })
@@ -43,7 +42,7 @@ impl SmiApp {
         let text = format!(concat!(
             "GPU {name:<name_len$} {pad:10}|{pci:<16}|{vram:^18}|\n",
             "SCLK    MCLK    VDDGFX  Power           | GFX% UMC%Media%|{gtt:^18}|\n",
-            "Temp    {fan:<7} {ecc} {thr:<THR_LEN$}|"
+            "Temp    {fan:<7} {ecc} {energy:<9} {thr:<THR_LEN$}|"
             ),
             name = "Name",
             name_len = GPU_NAME_LEN,
@@ -53,6 +52,7 @@ impl SmiApp {
             pad = "",
             fan = "Fan",
             ecc = "ECC_UnCorr.",
+            energy = "Energy",
             thr = "Throttle_Status",
             THR_LEN = THR_LEN,
         );
@@ -122,9 +122,7 @@ impl SmiApp {
             _ => write!(self.info_text.buf, " ___/___W ")?,
         }
 
-        if let Some(ref mut gfxoff_monitor) = self.gfxoff_monitor {
-            let _ = gfxoff_monitor.update();
-
+        if let Some(gfxoff_monitor) = &self.app_amdgpu_top.stat.gfxoff {
             match gfxoff_monitor.status {
                 GfxoffStatus::InGFXOFF => write!(self.info_text.buf, "GFXOFF |")?,
                 _ => write!(self.info_text.buf, "       |")?,
@@ -170,6 +168,14 @@ impl SmiApp {
             write!(self.info_text.buf, "[{:>ECC_LEN$}] ", "N/A")?;
         }
 
+        let energy = &self.app_amdgpu_top.stat.energy;
+
+        if energy.source.is_some() {
+            write!(self.info_text.buf, "{:>7.3}Wh ", energy.counter_wh())?;
+        } else {
+            write!(self.info_text.buf, "_______Wh ")?;
+        }
+
         if let Some(thr) = self.app_amdgpu_top.stat.metrics.as_ref().and_then(|m| m.get_throttle_status_info()) {
             let thr = format!("{:?}", thr.get_all_throttler());
             write!(
@@ -195,6 +201,7 @@ impl SmiApp {
 
         let _ = self.fdinfo_view.print_fdinfo(
             &mut self.app_amdgpu_top.stat.fdinfo,
+            &self.app_amdgpu_top.stat.proc_power,
             FdInfoSortType::default(),
             false,
         );
@@ -207,12 +214,13 @@ impl SmiApp {
 struct SuspendedSmiApp {
     device_path: DevicePath,
     index: usize,
+    app_opt: AppOption,
     fdinfo_view: AppTextView,
     info_text: Text,
 }
 
 impl SuspendedSmiApp {
-    fn new(device_path: DevicePath, index: usize) -> Self {
+    fn new(device_path: DevicePath, index: usize, app_opt: AppOption) -> Self {
         let mut info_text: Text = Default::default();
 
         if let [Some(did), Some(rid)] = [device_path.device_id, device_path.revision_id] {
@@ -236,6 +244,7 @@ impl SuspendedSmiApp {
         Self {
             device_path,
             index,
+            app_opt,
             fdinfo_view: Default::default(),
             info_text,
         }
@@ -258,13 +267,11 @@ impl SuspendedSmiApp {
 
     fn to_smi_app(&self) -> Option<SmiApp> {
         let amdgpu_dev = self.device_path.init().ok()?;
-        let app_amdgpu_top = AppAmdgpuTop::new(amdgpu_dev, self.device_path.clone(), &Default::default())?;
-        let gfxoff_monitor = GfxoffMonitor::new(self.device_path.pci).ok();
+        let app_amdgpu_top = AppAmdgpuTop::new(amdgpu_dev, self.device_path.clone(), &self.app_opt)?;
 
         Some(SmiApp {
             app_amdgpu_top,
             index: self.index,
-            gfxoff_monitor,
             fdinfo_view: self.fdinfo_view.clone(),
             info_text: self.info_text.clone(),
         })
@@ -276,13 +283,22 @@ pub fn run_smi(
     UiArgs {
         device_path_list,
         update_process_index,
+        power_attribution,
+        summary,
         ..
     }: UiArgs,
 ) {
     let sample = Sampling::low();
+    let app_opt = AppOption {
+        power_attribution,
+        summary: summary.then(Default::default),
+        gfxoff_residency_logging: true,
+        ..Default::default()
+    };
+    let summary_list = app_opt.summary.clone();
     let (vec_app, suspended) = AppAmdgpuTop::create_app_and_suspended_list(
         &device_path_list,
-        &Default::default(),
+        &app_opt,
     );
     let mut vec_app: Vec<_> = vec_app
         .into_iter()
@@ -293,7 +309,7 @@ pub fn run_smi(
     let mut sus_app_devices: Vec<_> = suspended
         .into_iter()
         .enumerate()
-        .map(|(i, device_path)| SuspendedSmiApp::new(device_path.clone(), app_len+i))
+        .map(|(i, device_path)| SuspendedSmiApp::new(device_path.clone(), app_len+i, app_opt.clone()))
         .collect();
 
     let mut siv = cursive::default();
@@ -327,7 +343,7 @@ pub fn run_smi(
             let h = HideableView::new(proc).with_name(PROC_TITLE);
             layout.add_child(Panel::new(h).title(PROC_TITLE).title_position(HAlign::Left));
         }
-        layout.add_child(TextView::new("\n(p)rocesses (q)uit"));
+        layout.add_child(TextView::new("\n(p)rocesses (E): reset energy (q)uit"));
 
         siv.add_fullscreen_layer(
             layout
@@ -347,6 +363,11 @@ pub fn run_smi(
             view.set_visible(!view.is_visible());
         });
     });
+    let reset_energy = Arc::new(AtomicBool::new(false));
+    {
+        let reset_energy = reset_energy.clone();
+        siv.add_global_callback('E', move |_| reset_energy.store(true, Ordering::Relaxed));
+    }
     siv.set_theme(cursive::theme::Theme::terminal_default());
 
     let cb_sink = siv.cb_sink().clone();
@@ -354,6 +375,12 @@ pub fn run_smi(
     std::thread::spawn(move || loop {
         std::thread::sleep(sample.to_duration()); // 1s
 
+        if reset_energy.swap(false, Ordering::Relaxed) {
+            for app in vec_app.iter_mut() {
+                app.app_amdgpu_top.stat.energy.reset();
+            }
+        }
+
         for app in vec_app.iter_mut() {
             app.update(&sample);
         }
@@ -373,4 +400,8 @@ pub fn run_smi(
     });
 
     siv.run();
+
+    if let Some(list) = &summary_list {
+        summary::print_session_summary_list(list);
+    }
 }
@@ -0,0 +1,39 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::{on_off, DisplayMonitor};
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub fn print_displays(&mut self, monitor: &DisplayMonitor) -> Result<(), fmt::Error> {
+        const MAX_EVENTS: usize = 4;
+        self.text.clear();
+
+        writeln!(
+            self.text.buf,
+            " {:<14} {:<6} {:<20} {:>12} {:>7} {:>7} {:<16} {:>3}",
+            "Connector", "State", "Mode", "VRR Capable", "VRR", "max bpc", "Colorspace", "HDR",
+        )?;
+
+        for disp in &monitor.displays {
+            writeln!(
+                self.text.buf,
+                " {:<14} {:<6} {:<20} {:>12} {:>7} {:>7} {:<16} {:>3}",
+                disp.name,
+                if disp.connected { "On" } else { "Off" },
+                disp.mode.map_or("_".to_string(), |mode| mode.to_string()),
+                on_off(disp.vrr_capable),
+                on_off(disp.vrr_enabled),
+                disp.max_bpc.map_or("_".to_string(), |v| v.to_string()),
+                disp.colorspace.as_deref().unwrap_or("_"),
+                on_off(disp.hdr_output_metadata),
+            )?;
+        }
+
+        // newest first
+        for ev in monitor.events.iter().rev().take(MAX_EVENTS) {
+            writeln!(self.text.buf, " {ev}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::DpmLevels;
+
+use crate::{AppTextView, Opt};
+
+impl AppTextView {
+    pub fn print_dpm_levels(&mut self, dpm_levels: &DpmLevels) -> Result<(), fmt::Error> {
+        self.text.clear();
+
+        for clk in &dpm_levels.clocks {
+            write!(self.text.buf, " {:<8}", clk.type_.to_string())?;
+
+            // [*]: current level
+            for level in &clk.levels {
+                if level.is_current {
+                    write!(self.text.buf, " [{:>4}]", level.mhz)?;
+                } else {
+                    write!(self.text.buf, "  {:>4} ", level.mhz)?;
+                }
+            }
+
+            writeln!(self.text.buf, " MHz")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn print_dpm_residency(&mut self, dpm_levels: &DpmLevels) -> Result<(), fmt::Error> {
+        self.text.clear();
+
+        for r in &dpm_levels.residency {
+            write!(self.text.buf, " {:<8}", r.type_.to_string())?;
+
+            for (level, per) in r.levels.iter().zip(r.percentages()) {
+                let index = level.index.map_or("S".to_string(), |i| i.to_string());
+                write!(self.text.buf, " {index:>2}: {per:5.1}%")?;
+            }
+
+            writeln!(self.text.buf)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cb_dpm_levels(siv: &mut cursive::Cursive) {
+        {
+            let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
+            opt.dpm_levels ^= true;
+        }
+    }
+}
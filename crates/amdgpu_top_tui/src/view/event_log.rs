@@ -0,0 +1,30 @@
+use std::fmt::{self, Write};
+use cursive::views::{HideableView, Panel, TextView};
+use libamdgpu_top::uevent::DeviceEventLog;
+
+use crate::AppTextView;
+
+// the panel is hidden until the first event
+pub fn event_log_view_name(index: usize) -> String {
+    format!("Events {index}")
+}
+
+pub fn show_event_log(siv: &mut cursive::Cursive, index: usize) {
+    siv.call_on_name(
+        &event_log_view_name(index),
+        |view: &mut HideableView<Panel<TextView>>| view.set_visible(true),
+    );
+}
+
+impl AppTextView {
+    pub fn print_event_log(&mut self, event_log: &DeviceEventLog) -> Result<(), fmt::Error> {
+        self.text.clear();
+
+        // newest first
+        for ev in event_log.events.iter().rev() {
+            writeln!(self.text.buf, " {ev}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::{self, Write};
 use crate::Opt;
 
-use libamdgpu_top::stat::{FdInfoStat, FdInfoSortType};
+use libamdgpu_top::stat::{FdInfoStat, FdInfoSortType, ProcPowerStat};
 
 // ref: drivers/gpu/drm/amd/amdgpu/amdgpu_fdinfo.c
 
@@ -19,6 +19,7 @@ const ENC_LABEL: &str = "ENC";
 const VCN_LABEL: &str = "VCN";
 const VPE_LABEL: &str = "VPE";
 const KFD_LABEL: &str = "KFD";
+const POWER_LABEL: &str = "Power";
 // const UVD_ENC_LABEL: &str = "UVD (ENC)";
 // const JPEG_LABEL: &str = "JPEG";
 
@@ -28,6 +29,7 @@ impl AppTextView {
     pub fn print_fdinfo(
         &mut self,
         stat: &mut FdInfoStat,
+        proc_power: &ProcPowerStat,
         sort: FdInfoSortType,
         reverse: bool,
     ) -> Result<(), fmt::Error> {
@@ -50,16 +52,22 @@ impl AppTextView {
             write!(self.text.buf, "|{VPE_LABEL:^4}|")?;
         }
 
+        write!(self.text.buf, "{POWER_LABEL:^6}|")?;
+
         writeln!(self.text.buf)?;
 
         stat.sort_proc_usage(sort, reverse);
 
-        self.print_fdinfo_usage(stat)?;
+        self.print_fdinfo_usage(stat, proc_power)?;
 
         Ok(())
     }
 
-    pub fn print_fdinfo_usage(&mut self, stat: &FdInfoStat) -> Result<(), fmt::Error> {
+    pub fn print_fdinfo_usage(
+        &mut self,
+        stat: &FdInfoStat,
+        proc_power: &ProcPowerStat,
+    ) -> Result<(), fmt::Error> {
         for pu in &stat.proc_usage {
             let utf16_count = pu.name.encode_utf16().count();
             let name_len = if pu.name.len() != utf16_count {
@@ -98,6 +106,12 @@ impl AppTextView {
                 write!(self.text.buf, "{:>3}%|", pu.usage.vpe)?;
             }
 
+            if let Some(p) = proc_power.get(pu.pid) {
+                write!(self.text.buf, "{:>5.1}W|", p.power)?;
+            } else {
+                write!(self.text.buf, "{:>6}|", "_")?;
+            }
+
             writeln!(self.text.buf)?;
         }
 
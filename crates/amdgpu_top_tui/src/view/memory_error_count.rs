@@ -1,5 +1,6 @@
 use std::fmt::{self, Write};
 use libamdgpu_top::AMDGPU::RasErrorCount;
+use libamdgpu_top::stat::{BadPageStatus, RasErrorStat};
 
 use crate::AppTextView;
 
@@ -16,4 +17,46 @@ impl AppTextView {
 
         Ok(())
     }
+
+    pub fn print_ras_error_stat(&mut self, ras: &RasErrorStat) -> Result<(), fmt::Error> {
+        const MAX_PAGES: usize = 8;
+        self.text.clear();
+
+        writeln!(
+            self.text.buf,
+            " {:<10} {:>10} {:>8} {:>12} {:>8}",
+            "Block", "Corrected", "(delta)", "UnCorrected", "(delta)",
+        )?;
+
+        for block in &ras.blocks {
+            writeln!(
+                self.text.buf,
+                " {:<10} {:>10} {:>8} {:>12} {:>8}",
+                block.name,
+                block.corrected,
+                format!("(+{})", block.delta_corrected),
+                block.uncorrected,
+                format!("(+{})", block.delta_uncorrected),
+            )?;
+        }
+
+        writeln!(
+            self.text.buf,
+            " VRAM Bad Pages: {} (Retired: {}, Pending: {}, Failed: {})",
+            ras.bad_pages.len(),
+            ras.count_bad_pages(BadPageStatus::Reserved),
+            ras.count_bad_pages(BadPageStatus::Pending),
+            ras.count_bad_pages(BadPageStatus::Failed),
+        )?;
+
+        for page in ras.retired_pages().take(MAX_PAGES) {
+            writeln!(self.text.buf, "   PFN {:#010X}, {:#x} bytes", page.pfn, page.size)?;
+        }
+
+        if ras.retired_pages().count() > MAX_PAGES {
+            writeln!(self.text.buf, "   ...")?;
+        }
+
+        Ok(())
+    }
 }
@@ -26,6 +26,15 @@ pub use activity::*;
 
 mod memory_error_count;
 
+mod dpm_levels;
+
+mod event_log;
+pub use event_log::*;
+
+mod throttle_log;
+
+mod display;
+
 #[derive(Clone, Default)]
 pub(crate) struct AppTextView {
     pub text: Text,
@@ -36,7 +36,7 @@ impl PerfCounterView {
     pub fn top_view(&self, pc: &PerfCounter, visible: bool) -> TopView {
         const LEFT_LEN: usize = PANEL_WIDTH - PC_BAR_WIDTH;
 
-        let title = pc.pc_type.to_string();
+        let title = pc.name.clone();
         let mut sub_layout = LinearLayout::vertical();
         let label = |value: usize, (_, _): (usize, usize)| -> String {
             format!("[{val:^width$}]", width = PC_BAR_WIDTH - 2, val = format!("{value:3} %"))
@@ -88,6 +88,7 @@ pub fn pc_type_cb(pc_type: PCType) -> impl Fn(&mut cursive::Cursive) {
         PCType::GRBM2 => |opt: &mut ToggleOptions| {
             opt.grbm2 ^= true;
         },
+        PCType::Custom(_) => |_: &mut ToggleOptions| {},
     };
 
     move |siv: &mut cursive::Cursive| {
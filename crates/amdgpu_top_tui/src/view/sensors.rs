@@ -2,7 +2,7 @@ use super::PANEL_WIDTH;
 use std::fmt::{self, Write};
 use crate::Opt;
 
-use libamdgpu_top::stat::{Sensors, PcieBw};
+use libamdgpu_top::stat::{Sensors, PcieBw, EnergyMeter, GfxoffMonitor, GfxoffResidency};
 
 const WIDTH: usize = PANEL_WIDTH / 2;
 
@@ -107,6 +107,10 @@ impl AppTextView {
             writeln!(self.text.buf, " Power Profile: {power_profile}")?;
         }
 
+        if let Some(perf_level) = &sensors.perf_level {
+            writeln!(self.text.buf, " Performance Level: {perf_level}")?;
+        }
+
         Ok(())
     }
 
@@ -123,6 +127,54 @@ impl AppTextView {
         Ok(())
     }
 
+    pub fn print_energy_meter(&mut self, energy: &EnergyMeter) -> Result<(), fmt::Error> {
+        let Some(source) = energy.source else { return Ok(()) };
+
+        writeln!(
+            self.text.buf,
+            " Energy => Session: {:8.3} Wh, Counter: {:8.3} Wh ({source})",
+            energy.session_wh(),
+            energy.counter_wh(),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn print_gfxoff(&mut self, gfxoff: &GfxoffMonitor) -> Result<(), fmt::Error> {
+        let r = &gfxoff.residency;
+
+        writeln!(
+            self.text.buf,
+            " GFXOFF => Status: {}, Status changes (sampled per refresh): {}",
+            gfxoff.status,
+            r.observed_status_changes,
+        )?;
+
+        for statuses in GfxoffResidency::STATUS_LIST.chunks(2) {
+            write!(self.text.buf, "  ")?;
+
+            for status in statuses {
+                write!(self.text.buf, " {:<11}: {:5.1}%", status.to_string(), r.percentage(*status))?;
+            }
+
+            writeln!(self.text.buf)?;
+        }
+
+        if let (Some(entries), Some(count)) = (r.entries(), r.entry_count) {
+            write!(self.text.buf, "   Entries: {entries} (since boot: {count})")?;
+
+            if let Some(residency) = r.debugfs_residency {
+                write!(self.text.buf, ", Residency: {residency:.2}% (debugfs)")?;
+            }
+
+            writeln!(self.text.buf)?;
+        } else if let Some(residency) = r.debugfs_residency {
+            writeln!(self.text.buf, "   Residency: {residency:.2}% (debugfs)")?;
+        }
+
+        Ok(())
+    }
+
     pub fn cb_sensors(siv: &mut cursive::Cursive) {
         {
             let mut opt = siv.user_data::<Opt>().unwrap().lock().unwrap();
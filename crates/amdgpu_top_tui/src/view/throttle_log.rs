@@ -0,0 +1,41 @@
+use std::fmt::{self, Write};
+use libamdgpu_top::stat::ThrottleEventLog;
+
+use crate::AppTextView;
+
+impl AppTextView {
+    pub fn print_throttle_log(&mut self, throttle_log: &ThrottleEventLog) -> Result<(), fmt::Error> {
+        const MAX_LINES: usize = 8;
+        self.text.clear();
+
+        writeln!(
+            self.text.buf,
+            " {:<19} {:<16} {:<8} {:>9} {:>5} {:>5} {:>5}",
+            "Start", "Throttler", "Category", "Duration", "SCLK", "Temp.", "Power",
+        )?;
+
+        // newest first
+        for ev in throttle_log.events.iter().rev().take(MAX_LINES) {
+            let duration = format!(
+                "{:.1}s{}",
+                ev.duration.as_secs_f32(),
+                if ev.is_active { "+" } else { "" },
+            );
+            let [sclk, temp, power] = [
+                ev.onset.sclk.map(|v| v as i64),
+                ev.onset.junction_temp.or(ev.onset.edge_temp),
+                ev.onset.power.map(|v| v as i64),
+            ].map(|v| v.map_or("_".to_string(), |v| v.to_string()));
+
+            writeln!(
+                self.text.buf,
+                " {:<19} {:<16} {:<8} {duration:>9} {sclk:>5} {temp:>5} {power:>5}",
+                ev.start_time,
+                ev.throttler,
+                ev.category.to_string(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
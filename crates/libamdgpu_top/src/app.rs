@@ -1,7 +1,9 @@
 use crate::drmVersion;
 use crate::AMDGPU::{DeviceHandle, GPU_INFO, GpuMetrics, RasBlock, RasErrorCount};
 use crate::{AppDeviceInfo, DevicePath, stat, xdna, VramUsage, has_vcn, has_vcn_unified, has_vpe};
-use stat::{FdInfoStat, GpuActivity, Sensors, PcieBw, PerfCounter, ProcInfo};
+use crate::uevent::{DeviceEventKind, DeviceEventLog};
+use crate::summary::{SessionSummary, SessionSummaryList};
+use stat::{DpmLevels, RasErrorStat, ThrottleEventLog, EnergyMeter, DisplayMonitor, GfxoffMonitor, PowerAttributionModel, ProcPowerStat, FdInfoStat, GpuActivity, Sensors, PcieBw, PCConfig, PCSampler, PCSamplerConfig, PerfCounter, ProcInfo};
 use xdna::XdnaFdInfoStat;
 use std::mem::ManuallyDrop;
 use std::sync::{Arc, Mutex};
@@ -14,6 +16,9 @@ pub struct AppAmdgpuTop {
     pub xdna_device_path: Option<DevicePath>,
     pub xdna_fw_version: Option<String>,
     pub stat: AppAmdgpuTopStat,
+    pub summary: Option<Arc<Mutex<SessionSummary>>>,
+    pc_sampler: Option<Arc<Mutex<PCSampler>>>,
+    pc_sampling: bool,
     buf_interval: Duration,
     no_drop_device_handle: bool,
     dynamic_no_pc: bool, // to transition the APU into GFXOFF state
@@ -23,6 +28,7 @@ pub struct AppAmdgpuTop {
 pub struct AppAmdgpuTopStat {
     pub grbm: PerfCounter,
     pub grbm2: PerfCounter,
+    pub custom_pc: Vec<PerfCounter>,
     pub vram_usage: VramUsage,
     pub sensors: Option<Sensors>,
     pub metrics: Option<GpuMetrics>,
@@ -33,10 +39,24 @@ pub struct AppAmdgpuTopStat {
     pub arc_xdna_proc_index: Arc<Mutex<Vec<ProcInfo>>>,
     pub arc_pcie_bw: Option<Arc<Mutex<PcieBw>>>,
     pub memory_error_count: Option<RasErrorCount>,
+    pub ras: Option<RasErrorStat>,
+    pub dpm_levels: Option<DpmLevels>,
+    pub event_log: DeviceEventLog,
+    pub throttle_log: ThrottleEventLog,
+    pub energy: EnergyMeter,
+    pub proc_power: ProcPowerStat,
+    pub displays: Option<DisplayMonitor>,
+    pub gfxoff: Option<GfxoffMonitor>,
 }
 
+#[derive(Clone)]
 pub struct AppOption {
     pub pcie_bw: bool,
+    pub power_attribution: PowerAttributionModel,
+    pub summary: Option<SessionSummaryList>,
+    pub pc_config: PCConfig,
+    pub pc_sampler: Option<PCSamplerConfig>, // None: no perf counters
+    pub gfxoff_residency_logging: bool, // monitor modes only
 }
 
 #[allow(clippy::derivable_impls)]
@@ -44,6 +64,11 @@ impl Default for AppOption {
     fn default() -> Self {
         Self {
             pcie_bw: false,
+            power_attribution: PowerAttributionModel::default(),
+            summary: None,
+            pc_config: PCConfig::default(),
+            pc_sampler: None,
+            gfxoff_residency_logging: false,
         }
     }
 }
@@ -76,7 +101,7 @@ impl AppAmdgpuTop {
             let app = AppAmdgpuTop::new(
                 amdgpu_dev,
                 device_path.clone(),
-                &Default::default(),
+                opt,
             ).unwrap();
             apps.push(app);
             suspended_devices = other_sus_devs.to_vec();
@@ -118,9 +143,28 @@ impl AppAmdgpuTop {
                 PerfCounter::new_with_chip_class(stat::PCType::GRBM2, chip_class),
             ]
         };
+        let custom_pc: Vec<PerfCounter> = opt.pc_config
+            .registers_for(ext_info.get_chip_class())
+            .into_iter()
+            .filter(|def| match amdgpu_dev.read_mm_registers(def.offset) {
+                Ok(_) => true,
+                Err(err) => {
+                    eprintln!("{} ({:#X}) register is not allowed. ({err}) [{pci_bus}]", def.name, def.offset);
+                    false
+                },
+            })
+            .map(PerfCounter::from_register_def)
+            .collect();
+        let pc_sampler = opt.pc_sampler.map(|config| PCSampler::spawn_sampler_thread(
+            device_path.clone(),
+            [grbm.clone(), grbm2.clone()].into_iter().chain(custom_pc.iter().cloned()).collect(),
+            config,
+        ));
 
         let vram_usage = VramUsage::new(&memory_info);
         let memory_error_count = RasErrorCount::get_from_sysfs_with_ras_block(&sysfs_path, RasBlock::UMC).ok();
+        let ras = RasErrorStat::get_from_sysfs(&sysfs_path);
+        let dpm_levels = DpmLevels::get_from_sysfs(&sysfs_path);
 
         let sensors = Sensors::new(&amdgpu_dev, &pci_bus, &ext_info);
         let metrics = GpuMetrics::get_from_sysfs_path(&device_path.sysfs_path).ok();
@@ -193,6 +237,21 @@ impl AppAmdgpuTop {
             }
         }
 
+        let displays = DisplayMonitor::new(&device_path);
+        let gfxoff = GfxoffMonitor::new(pci_bus).ok().map(|mut gfxoff| {
+            if opt.gfxoff_residency_logging {
+                gfxoff.start_residency_logging();
+            }
+
+            gfxoff
+        });
+        let summary = opt.summary.as_ref().map(|list| {
+            let summary = Arc::new(Mutex::new(SessionSummary::new(&device_path)));
+            list.lock().unwrap().push(summary.clone());
+
+            summary
+        });
+
         Some(Self {
             amdgpu_dev: ManuallyDrop::new(Some(amdgpu_dev)),
             device_info,
@@ -202,6 +261,7 @@ impl AppAmdgpuTop {
             stat: AppAmdgpuTopStat {
                 grbm,
                 grbm2,
+                custom_pc,
                 vram_usage,
                 sensors,
                 metrics,
@@ -212,7 +272,18 @@ impl AppAmdgpuTop {
                 arc_xdna_proc_index,
                 arc_pcie_bw,
                 memory_error_count,
+                ras,
+                dpm_levels,
+                event_log: Default::default(),
+                throttle_log: Default::default(),
+                energy: Default::default(),
+                proc_power: ProcPowerStat::new(opt.power_attribution),
+                displays,
+                gfxoff,
             },
+            summary,
+            pc_sampler,
+            pc_sampling: true,
             buf_interval: Duration::ZERO,
             no_drop_device_handle,
             dynamic_no_pc: false,
@@ -220,6 +291,8 @@ impl AppAmdgpuTop {
     }
 
     pub fn update(&mut self, interval: Duration) {
+        self.update_pc_usage();
+
         {
             let fdinfo_lock = self.stat.arc_proc_index.try_lock();
             let xdna_fdinfo_lock = self.stat.arc_xdna_proc_index.try_lock();
@@ -265,6 +338,16 @@ impl AppAmdgpuTop {
             }
 
             self.stat.metrics = None;
+            self.stat.throttle_log.update(&self.stat.metrics, &self.stat.sensors);
+            self.stat.energy.update_for_idle(&self.device_info.sysfs_path);
+
+            if let Some(ref mut gfxoff) = self.stat.gfxoff {
+                gfxoff.update_for_idle();
+            }
+
+            self.stat.proc_power.update(&self.stat.fdinfo.proc_usage, None, interval);
+            self.update_pc_sampler_state();
+            self.update_summary(interval);
             return;
         };
 
@@ -296,6 +379,30 @@ impl AppAmdgpuTop {
             ).ok();
         }
 
+        self.stat.throttle_log.update(&self.stat.metrics, &self.stat.sensors);
+        self.stat.energy.update(&self.stat.metrics, &self.stat.sensors, interval);
+        self.stat.proc_power.update(
+            &self.stat.fdinfo.proc_usage,
+            self.stat.sensors.as_ref().and_then(|s| s.any_hwmon_power()).map(|p| p.value),
+            interval,
+        );
+
+        if let Some(ref mut ras) = self.stat.ras {
+            ras.update();
+        }
+
+        if let Some(ref mut dpm_levels) = self.stat.dpm_levels {
+            dpm_levels.update(interval);
+        }
+
+        if let Some(ref mut displays) = self.stat.displays {
+            displays.update(&self.device_path);
+        }
+
+        if let Some(ref mut gfxoff) = self.stat.gfxoff {
+            let _ = gfxoff.update();
+        }
+
         self.stat.activity = GpuActivity::get_with_option_gpu_metrics(
             &self.device_info.sysfs_path,
             self.device_info.asic_name,
@@ -303,29 +410,68 @@ impl AppAmdgpuTop {
         );
 
         self.dynamic_no_pc = self.device_info.is_apu && self.stat.activity.is_gfx_idling();
+        self.update_pc_sampler_state();
 
         if self.stat.activity.media.is_none() || self.stat.activity.media == Some(0) {
             self.stat.activity.media = self.stat.fdinfo.fold_fdinfo_usage().media.try_into().ok();
         }
+
+        self.update_summary(interval);
     }
 
-    pub fn update_pc(&mut self) {
-        if self.dynamic_no_pc { return }
+    /// Added/Removed events are handled by the caller
+    pub fn handle_device_event(&mut self, kind: DeviceEventKind) {
+        match kind {
+            DeviceEventKind::Hotplug => if let Some(ref mut displays) = self.stat.displays {
+                displays.on_hotplug();
+            },
+            _ => self.stat.event_log.push(kind),
+        }
+    }
 
-        if let Some(dev) = self.amdgpu_dev.as_ref() {
-            self.stat.grbm.read_reg(dev);
-            self.stat.grbm2.read_reg(dev);
+    fn update_summary(&self, interval: Duration) {
+        if let Some(summary) = &self.summary {
+            summary.lock().unwrap().update(&self.stat, interval);
         }
     }
 
-    pub fn clear_pc(&mut self) {
-        self.stat.grbm.clear_pc();
-        self.stat.grbm2.clear_pc();
+    /// Pause/resume the perf counter sampler thread, e.g. for the device not shown in TUI
+    pub fn set_pc_sampling(&mut self, enable: bool) {
+        self.pc_sampling = enable;
+        self.update_pc_sampler_state();
     }
 
-    pub fn update_pc_usage(&mut self) {
-        self.stat.grbm.update_pc_usage();
-        self.stat.grbm2.update_pc_usage();
+    fn update_pc_sampler_state(&self) {
+        let Some(sampler) = &self.pc_sampler else { return };
+        let pause = !self.pc_sampling || self.dynamic_no_pc || self.amdgpu_dev.is_none();
+
+        if let Ok(mut sampler) = sampler.lock() {
+            sampler.pause = pause;
+        }
+    }
+
+    // copy the rolling usage from the sampler thread
+    fn update_pc_usage(&mut self) {
+        let Some(sampler) = &self.pc_sampler else { return };
+
+        if let Ok(sampler) = sampler.lock() {
+            let pcs = [&mut self.stat.grbm, &mut self.stat.grbm2]
+                .into_iter()
+                .chain(self.stat.custom_pc.iter_mut());
+
+            // only the usage, the names may be localized (GUI)
+            for (dst, src) in pcs.zip(&sampler.pcs) {
+                for (d, s) in dst.pc_index.iter_mut().zip(&src.pc_index) {
+                    d.usage = s.usage;
+                }
+            }
+        }
+
+        if let Some(summary) = &self.summary {
+            summary.lock().unwrap().update_pc(
+                [&self.stat.grbm, &self.stat.grbm2].into_iter().chain(&self.stat.custom_pc),
+            );
+        }
     }
 
     pub fn get_drm_version_struct(&mut self) -> Option<drmVersion> {
@@ -334,3 +480,10 @@ impl AppAmdgpuTop {
             .and_then(|dev| dev.get_drm_version_struct().ok())
     }
 }
+
+// close the device handle of a removed (hot-unplugged) device
+impl Drop for AppAmdgpuTop {
+    fn drop(&mut self) {
+        unsafe { ManuallyDrop::drop(&mut self.amdgpu_dev); }
+    }
+}
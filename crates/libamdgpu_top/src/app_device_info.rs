@@ -14,7 +14,7 @@ use crate::AMDGPU::{
     VBIOS::VbiosInfo,
     VIDEO_CAPS::{CAP_TYPE, VideoCapsInfo},
 };
-use crate::{DevicePath, get_hw_ip_info_list, PCI, stat::Sensors};
+use crate::{DevicePath, get_hw_ip_info_list, PCI, firmware::FirmwareInfo, stat::Sensors};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -43,6 +43,7 @@ pub struct AppDeviceInfo {
     pub decode: Option<VideoCapsInfo>,
     pub encode: Option<VideoCapsInfo>,
     pub vbios: Option<VbiosInfo>,
+    pub firmware: FirmwareInfo,
     pub l1_cache_size_kib_per_cu: u32,
     pub actual_num_tcc_blocks: u32,
     pub gl1_cache_size_kib_per_sa: u32,
@@ -110,6 +111,7 @@ impl AppDeviceInfo {
             decode: amdgpu_dev.get_video_caps_info(CAP_TYPE::DECODE).ok(),
             encode: amdgpu_dev.get_video_caps_info(CAP_TYPE::ENCODE).ok(),
             vbios: amdgpu_dev.get_vbios_info().ok(),
+            firmware: FirmwareInfo::get(amdgpu_dev),
             actual_num_tcc_blocks: ext_info.get_actual_num_tcc_blocks(),
             l1_cache_size_kib_per_cu: ext_info.get_l1_cache_size() >> 10,
             gl1_cache_size_kib_per_sa: ext_info.get_gl1_cache_size() >> 10,
@@ -0,0 +1,805 @@
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/include/atomfirmware.h
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/include/atombios.h
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/amdgpu/amdgpu_atomfirmware.c
+
+const OFFSET_TO_ATOM_ROM_HEADER_POINTER: usize = 0x48;
+
+// atom_master_list_of_data_tables_v2_1
+const DATA_TABLE_NAMES: [&str; 35] = [
+    "utilitypipeline",
+    "multimedia_info",
+    "smc_dpm_info",
+    "sw_datatable3",
+    "firmwareinfo",
+    "sw_datatable5",
+    "lcd_info",
+    "sw_datatable7",
+    "smu_info",
+    "sw_datatable9",
+    "sw_datatable10",
+    "vram_usagebyfirmware",
+    "gpio_pin_lut",
+    "sw_datatable13",
+    "gfx_info",
+    "powerplayinfo",
+    "sw_datatable16",
+    "sw_datatable17",
+    "sw_datatable18",
+    "sw_datatable19",
+    "sw_datatable20",
+    "sw_datatable21",
+    "displayobjectinfo",
+    "indirectioaccess",
+    "umc_info",
+    "sw_datatable25",
+    "sw_datatable26",
+    "dce_info",
+    "vram_info",
+    "sw_datatable29",
+    "integratedsysteminfo",
+    "asic_profiling_info",
+    "voltageobject_info",
+    "sw_datatable33",
+    "sw_datatable34",
+];
+
+const FIRMWAREINFO: usize = 4;
+const DISPLAYOBJECTINFO: usize = 22;
+const VRAM_INFO: usize = 28;
+const INTEGRATEDSYSTEMINFO: usize = 30;
+const VOLTAGEOBJECT_INFO: usize = 32;
+
+#[derive(Clone, Copy)]
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes<const N: usize>(&self, pos: usize) -> Option<[u8; N]> {
+        self.0.get(pos..pos+N)?.try_into().ok()
+    }
+
+    fn u8(&self, pos: usize) -> Option<u8> {
+        self.0.get(pos).copied()
+    }
+
+    fn u16(&self, pos: usize) -> Option<u16> {
+        self.bytes(pos).map(u16::from_le_bytes)
+    }
+
+    fn u32(&self, pos: usize) -> Option<u32> {
+        self.bytes(pos).map(u32::from_le_bytes)
+    }
+
+    fn string(&self, pos: usize, len: usize) -> Option<String> {
+        let s = self.0.get(pos..pos+len)?;
+        let len = s.iter().position(|c| *c == 0).unwrap_or(len);
+
+        Some(String::from_utf8_lossy(&s[..len]).trim().to_string())
+    }
+
+    fn sub(&self, pos: usize, len: usize) -> Option<Reader<'a>> {
+        self.0.get(pos..pos+len).map(Reader)
+    }
+}
+
+// atom_common_table_header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtomTableHeader {
+    pub size: u16,
+    pub format_revision: u8,
+    pub content_revision: u8,
+}
+
+impl AtomTableHeader {
+    fn read(r: Reader, pos: usize) -> Option<Self> {
+        Some(Self {
+            size: r.u16(pos)?,
+            format_revision: r.u8(pos+2)?,
+            content_revision: r.u8(pos+3)?,
+        })
+    }
+}
+
+impl std::fmt::Display for AtomTableHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "v{}.{}, {} bytes", self.format_revision, self.content_revision, self.size)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomDataTable {
+    pub index: usize,
+    pub name: &'static str,
+    pub offset: usize,
+    pub header: AtomTableHeader,
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomFirmwareInfo {
+    pub header: AtomTableHeader,
+    pub firmware_revision: u32,
+    pub bootup_sclk_mhz: u32,
+    pub bootup_mclk_mhz: u32,
+    // atom_firmware_info_v3_x
+    pub firmware_capability: Option<u32>,
+    pub bootup_vddc_mv: Option<u16>,
+    pub bootup_vddci_mv: Option<u16>,
+    pub bootup_mvddc_mv: Option<u16>,
+    pub bootup_vddgfx_mv: Option<u16>,
+    pub mem_module_id: Option<u8>,
+    pub cooling_solution_id: Option<u8>,
+}
+
+impl AtomFirmwareInfo {
+    fn parse(r: Reader, header: AtomTableHeader) -> Option<Self> {
+        let v3 = header.format_revision == 3;
+        let v3_u16 = |pos| if v3 { r.u16(pos) } else { None };
+        let v3_u8 = |pos| if v3 { r.u8(pos) } else { None };
+
+        Some(Self {
+            header,
+            firmware_revision: r.u32(4)?,
+            bootup_sclk_mhz: r.u32(8)? / 100,
+            bootup_mclk_mhz: r.u32(12)? / 100,
+            firmware_capability: if v3 { r.u32(16) } else { None },
+            bootup_vddc_mv: v3_u16(28),
+            bootup_vddci_mv: v3_u16(30),
+            bootup_mvddc_mv: v3_u16(32),
+            bootup_vddgfx_mv: v3_u16(34),
+            mem_module_id: v3_u8(36),
+            cooling_solution_id: v3_u8(37),
+        })
+    }
+
+    pub fn capabilities(&self) -> Vec<&'static str> {
+        let Some(cap) = self.firmware_capability else { return Vec::new() };
+
+        [
+            (0x1, "FIRMWARE_POSTED"),
+            (0x2, "GPU_VIRTUALIZATION"),
+            (0x40, "WMI_SUPPORT"),
+            (0x80, "HWEMU_ENABLE"),
+            (0x100, "HWEMU_UMC_CFG"),
+            (0x200, "SRAM_ECC"),
+            (0x400, "ENABLE_2STAGE_BIST_TRAINING"),
+            (0x8000, "ENABLE_2ND_USB20PORT"),
+            (0x20000, "DYNAMIC_BOOT_CFG_ENABLE"),
+        ].into_iter().filter(|(bit, _)| cap & bit != 0).map(|(_, name)| name).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomVramModule {
+    pub memory_size_mib: Option<u32>,
+    pub memory_type: u8,
+    pub vendor_id: u8,
+    pub revision_id: u8,
+    pub channel_num: u8,
+    pub channel_width: u8,
+    pub density: u8,
+    pub max_mem_clk_mhz: Option<u32>,
+    pub mem_voltage_mv: Option<u16>,
+    pub part_number: String,
+}
+
+impl AtomVramModule {
+    pub fn memory_type_name(&self) -> &'static str {
+        // atom_dgpu_vram_type
+        match self.memory_type {
+            0x50 => "GDDR5",
+            0x60 => "HBM2",
+            0x61 => "HBM2E",
+            0x70 => "GDDR6",
+            0x80 => "HBM3",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn vendor_name(&self) -> &'static str {
+        // AMDGPU_VRAM_VENDOR_*
+        match self.vendor_id {
+            0x1 => "Samsung",
+            0x2 => "Infineon",
+            0x3 => "Elpida",
+            0x4 => "Etron",
+            0x5 => "Nanya",
+            0x6 => "Hynix",
+            0x7 => "Mosel",
+            0x8 => "Winbond",
+            0x9 => "ESMT",
+            0xF => "Micron",
+            _ => "Unknown",
+        }
+    }
+}
+
+// atom_gddr6_ac_timing_v2_5
+#[derive(Debug, Clone)]
+pub struct AtomGddr6AcTiming {
+    pub mem_block_id: u8,
+    pub max_memclk_mhz: u32,
+    pub timings: Vec<(&'static str, u32)>,
+}
+
+impl AtomGddr6AcTiming {
+    const SIZE: usize = 64;
+
+    fn parse(r: Reader) -> Option<Self> {
+        let id = r.u32(0)?;
+
+        if id == 0 { return None }
+
+        let u8_fields = [
+            ("RL", 4), ("WL", 5), ("tRAS", 6), ("tRC", 7),
+            ("tRFC", 10), ("tRFCpb", 11), ("tRREFD", 12), ("tRCDRD", 13),
+            ("tRCDWR", 14), ("tRP", 15), ("tRRDS", 16), ("tRRDL", 17),
+            ("tWR", 18), ("tWTRS", 19), ("tWTRL", 20), ("tFAW", 21),
+            ("tCCDS", 22), ("tCCDL", 23), ("tCRCRL", 24), ("tCRCWL", 25),
+            ("tCKE", 26), ("tCKSRE", 27), ("tCKSRX", 28), ("tRTPS", 29),
+            ("tRTPL", 30), ("tMRD", 31), ("tMOD", 32), ("tXS", 33),
+            ("tXHP", 34), ("tXSMRS", 35),
+        ];
+        let mut timings: Vec<(&'static str, u32)> = u8_fields
+            .iter()
+            .filter_map(|(name, pos)| Some((*name, r.u8(*pos)? as u32)))
+            .collect();
+
+        timings.insert(4, ("tREFI", r.u16(8)? as u32));
+        timings.push(("tXSH", r.u32(36)?));
+
+        Some(Self {
+            mem_block_id: (id >> 24) as u8,
+            max_memclk_mhz: (id & 0xFF_FFFF) / 100,
+            timings,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomVramInfo {
+    pub header: AtomTableHeader,
+    pub modules: Vec<AtomVramModule>,
+    pub gddr6_ac_timings: Vec<AtomGddr6AcTiming>,
+}
+
+impl AtomVramInfo {
+    fn parse(r: Reader, header: AtomTableHeader) -> Option<Self> {
+        let num = r.u8(20)? as usize;
+
+        let (modules, gddr6_ac_timings) = match (header.format_revision, header.content_revision) {
+            (2, 3..=5) => {
+                let module_size = match header.content_revision {
+                    3 => 52, // atom_vram_module_v9
+                    4 => 60, // atom_vram_module_v10
+                    _ => 84, // atom_vram_module_v11
+                };
+                let modules = (0..num).filter_map(|i| {
+                    let m = r.sub(24 + i * module_size, module_size)?;
+
+                    if header.content_revision == 5 {
+                        Some(AtomVramModule {
+                            memory_size_mib: Some(m.u32(0)?),
+                            mem_voltage_mv: Some(m.u16(8)?),
+                            memory_type: m.u8(13)?,
+                            channel_num: m.u8(14)?,
+                            channel_width: m.u8(15)?,
+                            density: m.u8(16)?,
+                            vendor_id: m.u8(26)? & 0xF,
+                            revision_id: m.u8(26)? >> 4,
+                            max_mem_clk_mhz: None,
+                            part_number: m.string(44, 40)?,
+                        })
+                    } else {
+                        Some(AtomVramModule {
+                            memory_size_mib: Some(m.u32(0)?),
+                            max_mem_clk_mhz: Some(m.u32(8)? / 100),
+                            mem_voltage_mv: Some(m.u16(18)?),
+                            memory_type: m.u8(23)?,
+                            channel_num: m.u8(24)?,
+                            channel_width: m.u8(25)?,
+                            density: m.u8(26)?,
+                            vendor_id: m.u8(28)? & 0xF,
+                            revision_id: m.u8(28)? >> 4,
+                            part_number: m.string(if header.content_revision == 3 { 32 } else { 40 }, 20)?,
+                        })
+                    }
+                }).collect();
+                let timings = if header.content_revision == 5 {
+                    let base = 24 + 16 * module_size;
+
+                    (0..16)
+                        .filter_map(|i| AtomGddr6AcTiming::parse(r.sub(base + i * AtomGddr6AcTiming::SIZE, AtomGddr6AcTiming::SIZE)?))
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+
+                (modules, timings)
+            },
+            (3, 0) => {
+                let memory_type = r.u8(24)?;
+                let channel_num = r.u8(25)?;
+                let channel_width = r.u8(26)?;
+                // atom_vram_module_v3_0
+                let modules = (0..num).filter_map(|i| {
+                    let m = r.sub(64 + i * 68, 68)?;
+
+                    Some(AtomVramModule {
+                        memory_size_mib: None,
+                        memory_type,
+                        channel_num,
+                        channel_width,
+                        density: m.u8(0)?,
+                        vendor_id: m.u8(3)? & 0xF,
+                        revision_id: m.u8(3)? >> 4,
+                        max_mem_clk_mhz: None,
+                        mem_voltage_mv: None,
+                        part_number: m.string(28, 40)?,
+                    })
+                }).collect();
+
+                (modules, Vec::new())
+            },
+            _ => return None,
+        };
+
+        Some(Self { header, modules, gddr6_ac_timings })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AtomDisplayPath {
+    pub display_objid: u16,
+    pub encoder_objid: u16,
+    pub device_tag: u16,
+}
+
+impl AtomDisplayPath {
+    pub fn object_type(id: u16) -> u8 {
+        ((id >> 12) & 0xF) as u8
+    }
+
+    pub fn enum_id(id: u16) -> u8 {
+        ((id >> 8) & 0x7) as u8
+    }
+
+    pub fn connector_name(&self) -> String {
+        // GRAPH_OBJECT_TYPE_CONNECTOR
+        if Self::object_type(self.display_objid) != 3 {
+            return format!("{:#06X}", self.display_objid);
+        }
+
+        let name = match self.display_objid & 0xFF {
+            0x01 => "SINGLE_LINK_DVI_I",
+            0x02 => "DUAL_LINK_DVI_I",
+            0x03 => "SINGLE_LINK_DVI_D",
+            0x04 => "DUAL_LINK_DVI_D",
+            0x05 => "VGA",
+            0x06 => "COMPOSITE",
+            0x07 => "SVIDEO",
+            0x0C => "HDMI_TYPE_A",
+            0x0D => "HDMI_TYPE_B",
+            0x0E => "LVDS",
+            0x13 => "DISPLAYPORT",
+            0x14 => "eDP",
+            0x15 => "MXM",
+            0x16 => "LVDS_eDP",
+            0x18 => "USBC",
+            _ => "Unknown",
+        };
+
+        format!("{name}-{}", Self::enum_id(self.display_objid))
+    }
+
+    pub fn encoder_name(&self) -> String {
+        // GRAPH_OBJECT_TYPE_ENCODER
+        if Self::object_type(self.encoder_objid) != 2 {
+            return format!("{:#06X}", self.encoder_objid);
+        }
+
+        let name = match self.encoder_objid & 0xFF {
+            0x1E => "UNIPHY",
+            0x20 => "UNIPHY1",
+            0x21 => "UNIPHY2",
+            0x25 => "UNIPHY3",
+            _ => return format!("{:#06X}", self.encoder_objid),
+        };
+        // ENUM_ID_1: link A, ENUM_ID_2: link B
+        let link = match Self::enum_id(self.encoder_objid) {
+            1 => "A",
+            2 => "B",
+            _ => "?",
+        };
+
+        format!("{name} (link {link})")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomDisplayObjectInfo {
+    pub header: AtomTableHeader,
+    pub supported_devices: u16,
+    pub paths: Vec<AtomDisplayPath>,
+}
+
+impl AtomDisplayObjectInfo {
+    // display_object_info_table_v1_4, v1_5
+    fn parse(r: Reader, header: AtomTableHeader) -> Option<Self> {
+        if header.format_revision != 1 || !matches!(header.content_revision, 4 | 5) {
+            return None;
+        }
+
+        let num = r.u8(6)? as usize;
+        let paths = (0..num).filter_map(|i| {
+            let p = r.sub(8 + i * 16, 16)?;
+
+            Some(AtomDisplayPath {
+                display_objid: p.u16(0)?,
+                encoder_objid: p.u16(4)?,
+                device_tag: p.u16(12)?,
+            })
+        }).collect();
+
+        Some(Self { header, supported_devices: r.u16(4)?, paths })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomIntegratedSystemInfo {
+    pub header: AtomTableHeader,
+    pub vbios_misc: u32,
+    pub gpu_cap_info: u32,
+    pub system_config: u32,
+    pub cpu_cap_info: u32,
+    pub gpuclk_ss_percentage: u16, // 0.01%
+    pub memory_type: u8,
+    pub uma_channel_number: u8,
+    pub htc_tmp_limit: Option<u8>,
+    pub htc_hyst_limit: Option<u8>,
+}
+
+impl AtomIntegratedSystemInfo {
+    // atom_integrated_system_info_v1_11, v1_12, v2_1, v2_2
+    fn parse(r: Reader, header: AtomTableHeader) -> Option<Self> {
+        let (memory_type_pos, htc) = match (header.format_revision, header.content_revision) {
+            (1, 11..=12) => (42, false),
+            (2, 1..=2) => (26, true),
+            _ => return None,
+        };
+
+        Some(Self {
+            header,
+            vbios_misc: r.u32(4)?,
+            gpu_cap_info: r.u32(8)?,
+            system_config: r.u32(12)?,
+            cpu_cap_info: r.u32(16)?,
+            gpuclk_ss_percentage: r.u16(20)?,
+            memory_type: r.u8(memory_type_pos)?,
+            uma_channel_number: r.u8(memory_type_pos+1)?,
+            htc_hyst_limit: if htc { r.u8(28) } else { None },
+            htc_tmp_limit: if htc { r.u8(29) } else { None },
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomVoltageObject {
+    pub voltage_type: u8,
+    pub voltage_mode: u8,
+    pub size: u16,
+    pub details: Vec<(&'static str, u32)>,
+    pub gpio_lut_mv: Vec<u16>, // VOLTAGE_OBJ_GPIO_LUT
+}
+
+impl AtomVoltageObject {
+    pub fn voltage_type_name(&self) -> &'static str {
+        // atom_voltage_type
+        match self.voltage_type {
+            1 => "VDDC",
+            2 => "MVDDC",
+            3 => "MVDDQ",
+            4 => "VDDCI",
+            5 => "VDDGFX",
+            6 => "PCC",
+            7 => "MVPP",
+            8 => "LEDDPM",
+            9 => "PCC_MVDD",
+            10 => "PCIE_VDDC",
+            11 => "PCIE_VDDR",
+            0x11..=0x17 => "GENERIC_I2C",
+            _ => "Unknown",
+        }
+    }
+
+    pub fn voltage_mode_name(&self) -> &'static str {
+        // atom_voltage_object_mode
+        match self.voltage_mode {
+            0 => "GPIO_LUT",
+            3 => "VR_I2C_INIT_SEQ",
+            4 => "PHASE_LUT",
+            7 => "SVID2",
+            8 => "EVV",
+            9 => "MERGED_POWER",
+            _ => "Unknown",
+        }
+    }
+
+    fn parse(r: Reader, v4: bool) -> Option<Self> {
+        let voltage_mode = r.u8(1)?;
+        let field = |name, pos| Some((name, r.u8(pos)? as u32));
+        let mut gpio_lut_mv = Vec::new();
+        let details = match (v4, voltage_mode) {
+            // atom_gpio_voltage_object_v4
+            (true, 0) => {
+                let num = r.u8(5)? as usize;
+
+                gpio_lut_mv = (0..num).filter_map(|i| r.u16(12 + i * 6 + 4)).collect();
+
+                [field("gpio_control_id", 4), field("phase_delay_us", 6)].into_iter().flatten().collect()
+            },
+            // atom_i2c_voltage_object_v4
+            (true, 3) => [field("regulator_id", 4), field("i2c_id", 5)].into_iter().flatten().collect(),
+            // atom_svid2_voltage_object_v4
+            (true, 7) => [
+                field("loadline_psi1", 4),
+                field("psi0_l_vid_thresd", 5),
+                field("psi0_enable", 6),
+                field("maxvstep", 7),
+                field("telemetry_offset", 8),
+                field("telemetry_gain", 9),
+            ].into_iter().flatten().collect(),
+            _ => Vec::new(),
+        };
+
+        Some(Self {
+            voltage_type: r.u8(0)?,
+            voltage_mode,
+            size: r.u16(2)?,
+            details,
+            gpio_lut_mv,
+        })
+    }
+
+    // atom_voltage_objects_info_v4_1, ATOM_VOLTAGE_OBJECT_INFO_V3_1
+    fn parse_table(r: Reader, header: AtomTableHeader) -> Vec<Self> {
+        if !matches!(header.format_revision, 3 | 4) { return Vec::new() }
+
+        let mut vec = Vec::new();
+        let mut pos = 4;
+
+        while let Some(size) = r.u16(pos + 2) {
+            let size = size as usize;
+
+            if size < 4 { break }
+
+            let Some(obj) = r.sub(pos, size).and_then(|o| Self::parse(o, header.format_revision == 4)) else { break };
+
+            vec.push(obj);
+            pos += size;
+        }
+
+        vec
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtomBios {
+    pub rom_header: AtomTableHeader,
+    pub subsystem_vendor_id: u16,
+    pub subsystem_id: u16,
+    pub data_tables: Vec<AtomDataTable>,
+    pub firmware_info: Option<AtomFirmwareInfo>,
+    pub vram_info: Option<AtomVramInfo>,
+    pub display_object_info: Option<AtomDisplayObjectInfo>,
+    pub integrated_system_info: Option<AtomIntegratedSystemInfo>,
+    pub voltage_objects: Vec<AtomVoltageObject>,
+}
+
+impl AtomBios {
+    pub fn parse(vbios: &[u8]) -> Option<Self> {
+        let r = Reader(vbios);
+
+        if r.bytes(0)? != [0x55, 0xAA] { return None }
+
+        // atom_rom_header_v2_2
+        let rom = r.u16(OFFSET_TO_ATOM_ROM_HEADER_POINTER)? as usize;
+
+        if &r.bytes::<4>(rom + 4)? != b"ATOM" { return None }
+
+        let rom_header = AtomTableHeader::read(r, rom)?;
+        let subsystem_vendor_id = r.u16(rom + 24)?;
+        let subsystem_id = r.u16(rom + 26)?;
+        let master_data_table = r.u16(rom + 32)? as usize;
+        let master_header = AtomTableHeader::read(r, master_data_table)?;
+        let num = (master_header.size as usize).saturating_sub(4) / 2;
+
+        let data_tables: Vec<AtomDataTable> = (0..num.min(DATA_TABLE_NAMES.len())).filter_map(|index| {
+            let offset = r.u16(master_data_table + 4 + index * 2)? as usize;
+
+            if offset == 0 { return None }
+
+            Some(AtomDataTable {
+                index,
+                name: DATA_TABLE_NAMES[index],
+                offset,
+                header: AtomTableHeader::read(r, offset)?,
+            })
+        }).collect();
+
+        let table = |index: usize| -> Option<(Reader, AtomTableHeader)> {
+            let t = data_tables.iter().find(|t| t.index == index)?;
+            let len = (t.header.size as usize).min(vbios.len() - t.offset);
+
+            Some((r.sub(t.offset, len)?, t.header))
+        };
+
+        Some(Self {
+            rom_header,
+            subsystem_vendor_id,
+            subsystem_id,
+            firmware_info: table(FIRMWAREINFO).and_then(|(r, h)| AtomFirmwareInfo::parse(r, h)),
+            vram_info: table(VRAM_INFO).and_then(|(r, h)| AtomVramInfo::parse(r, h)),
+            display_object_info: table(DISPLAYOBJECTINFO).and_then(|(r, h)| AtomDisplayObjectInfo::parse(r, h)),
+            integrated_system_info: table(INTEGRATEDSYSTEMINFO).and_then(|(r, h)| AtomIntegratedSystemInfo::parse(r, h)),
+            voltage_objects: table(VOLTAGEOBJECT_INFO).map_or(Vec::new(), |(r, h)| AtomVoltageObject::parse_table(r, h)),
+            data_tables,
+        })
+    }
+}
+
+#[cfg(test)]
+fn test_vbios() -> Vec<u8> {
+    let mut v = vec![0u8; 0x800];
+    let mut put = |pos: usize, bytes: &[u8]| v[pos..pos+bytes.len()].copy_from_slice(bytes);
+    let header = |size: u16, format: u8, content: u8| {
+        let [s0, s1] = size.to_le_bytes();
+        [s0, s1, format, content]
+    };
+
+    put(0, &[0x55, 0xAA]);
+    put(OFFSET_TO_ATOM_ROM_HEADER_POINTER, &0x100u16.to_le_bytes());
+
+    // atom_rom_header_v2_2
+    put(0x100, &header(0x30, 2, 2));
+    put(0x104, b"ATOM");
+    put(0x118, &0x1002u16.to_le_bytes());
+    put(0x11A, &0x0E3Au16.to_le_bytes());
+    put(0x120, &0x200u16.to_le_bytes());
+
+    // master data table
+    put(0x200, &header(4 + 35 * 2, 2, 1));
+    for (index, offset) in [
+        (FIRMWAREINFO, 0x300u16),
+        (VRAM_INFO, 0x400),
+        (DISPLAYOBJECTINFO, 0x500),
+        (INTEGRATEDSYSTEMINFO, 0x600),
+        (VOLTAGEOBJECT_INFO, 0x700),
+    ] {
+        put(0x204 + index * 2, &offset.to_le_bytes());
+    }
+
+    // atom_firmware_info_v3_1
+    put(0x300, &header(0x40, 3, 1));
+    put(0x304, &0x0001_0203u32.to_le_bytes());
+    put(0x308, &130_000u32.to_le_bytes());
+    put(0x30C, &87_500u32.to_le_bytes());
+    put(0x310, &0x201u32.to_le_bytes());
+    put(0x31C, &900u16.to_le_bytes());
+    put(0x324, &[1, 0]);
+
+    // atom_vram_info_header_v2_4, atom_vram_module_v10
+    put(0x400, &header(24 + 60, 2, 4));
+    put(0x414, &[1]);
+    put(0x418, &8192u32.to_le_bytes());
+    put(0x420, &200_000u32.to_le_bytes());
+    put(0x42A, &1350u16.to_le_bytes());
+    put(0x42F, &[0x50, 8, 32, 0x12]);
+    put(0x434, &[0x16]);
+    put(0x440, b"H5GC8H24AJR");
+
+    // display_object_info_table_v1_4
+    put(0x500, &header(8 + 2 * 16, 1, 4));
+    put(0x504, &[0x0F, 0x00, 2]);
+    put(0x508, &0x3113u16.to_le_bytes());
+    put(0x50C, &0x211Eu16.to_le_bytes());
+    put(0x514, &0x0004u16.to_le_bytes());
+    put(0x518, &0x320Cu16.to_le_bytes());
+    put(0x51C, &0x2220u16.to_le_bytes());
+
+    // atom_integrated_system_info_v2_1
+    put(0x600, &header(32, 2, 1));
+    put(0x604, &0x1u32.to_le_bytes());
+    put(0x614, &25u16.to_le_bytes());
+    put(0x61A, &[5, 2, 5, 95]);
+
+    // atom_voltage_objects_info_v4_1
+    put(0x700, &header(4 + 12 + 24, 4, 1));
+    // VDDC, atom_svid2_voltage_object_v4
+    put(0x704, &[1, 7]);
+    put(0x706, &12u16.to_le_bytes());
+    put(0x708, &[1, 2, 3, 4, 5, 6]);
+    // MVDDC, atom_gpio_voltage_object_v4
+    put(0x710, &[2, 0]);
+    put(0x712, &24u16.to_le_bytes());
+    put(0x714, &[0x0A, 2, 10]);
+    put(0x720, &1350u16.to_le_bytes());
+    put(0x726, &1250u16.to_le_bytes());
+
+    v
+}
+
+#[test]
+fn test_atom_bios() {
+    let vbios = test_vbios();
+    let atom = AtomBios::parse(&vbios).unwrap();
+
+    assert_eq!(atom.rom_header, AtomTableHeader { size: 0x30, format_revision: 2, content_revision: 2 });
+    assert_eq!((atom.subsystem_vendor_id, atom.subsystem_id), (0x1002, 0x0E3A));
+    assert_eq!(
+        atom.data_tables.iter().map(|t| (t.name, t.offset)).collect::<Vec<_>>(),
+        [
+            ("firmwareinfo", 0x300),
+            ("displayobjectinfo", 0x500),
+            ("vram_info", 0x400),
+            ("integratedsysteminfo", 0x600),
+            ("voltageobject_info", 0x700),
+        ],
+    );
+
+    let fw = atom.firmware_info.unwrap();
+    assert_eq!(fw.firmware_revision, 0x0001_0203);
+    assert_eq!((fw.bootup_sclk_mhz, fw.bootup_mclk_mhz), (1300, 875));
+    assert_eq!(fw.capabilities(), ["FIRMWARE_POSTED", "SRAM_ECC"]);
+    assert_eq!(fw.bootup_vddc_mv, Some(900));
+    assert_eq!(fw.mem_module_id, Some(1));
+
+    let vram = atom.vram_info.unwrap();
+    let m = &vram.modules[0];
+    assert_eq!(vram.modules.len(), 1);
+    assert!(vram.gddr6_ac_timings.is_empty());
+    assert_eq!((m.memory_size_mib, m.max_mem_clk_mhz, m.mem_voltage_mv), (Some(8192), Some(2000), Some(1350)));
+    assert_eq!((m.memory_type_name(), m.channel_num, m.channel_width, m.density), ("GDDR5", 8, 32, 0x12));
+    assert_eq!((m.vendor_name(), m.revision_id), ("Hynix", 1));
+    assert_eq!(m.part_number, "H5GC8H24AJR");
+
+    let display = atom.display_object_info.unwrap();
+    assert_eq!(display.supported_devices, 0x0F);
+    assert_eq!(
+        display.paths.iter().map(|p| (p.connector_name(), p.encoder_name())).collect::<Vec<_>>(),
+        [
+            ("DISPLAYPORT-1".to_string(), "UNIPHY (link A)".to_string()),
+            ("HDMI_TYPE_A-2".to_string(), "UNIPHY1 (link B)".to_string()),
+        ],
+    );
+    assert_eq!(display.paths[0].device_tag, 0x0004);
+
+    let igp = atom.integrated_system_info.unwrap();
+    assert_eq!((igp.vbios_misc, igp.gpuclk_ss_percentage), (1, 25));
+    assert_eq!((igp.memory_type, igp.uma_channel_number), (5, 2));
+    assert_eq!((igp.htc_hyst_limit, igp.htc_tmp_limit), (Some(5), Some(95)));
+
+    let [vddc, mvddc] = &atom.voltage_objects[..] else { panic!() };
+    assert_eq!((vddc.voltage_type_name(), vddc.voltage_mode_name(), vddc.size), ("VDDC", "SVID2", 12));
+    assert_eq!(vddc.details[0], ("loadline_psi1", 1));
+    assert_eq!(vddc.details.len(), 6);
+    assert_eq!((mvddc.voltage_type_name(), mvddc.voltage_mode_name()), ("MVDDC", "GPIO_LUT"));
+    assert_eq!(mvddc.details, [("gpio_control_id", 0x0A), ("phase_delay_us", 10)]);
+    assert_eq!(mvddc.gpio_lut_mv, [1350, 1250]);
+}
+
+#[test]
+fn test_atom_bios_truncated() {
+    let vbios = test_vbios();
+
+    // no ROM header pointer, no ROM header, no "ATOM" signature
+    assert!(AtomBios::parse(&vbios[..0x40]).is_none());
+    assert!(AtomBios::parse(&vbios[..0x100]).is_none());
+    assert!(AtomBios::parse(&vbios[..0x106]).is_none());
+    assert!(AtomBios::parse(&[0x55]).is_none());
+
+    // truncated in the middle of the tables
+    let atom = AtomBios::parse(&vbios[..0x410]).unwrap();
+    assert!(atom.firmware_info.is_some());
+    assert!(atom.vram_info.is_none());
+    assert!(atom.display_object_info.is_none());
+    assert_eq!(atom.data_tables.len(), 2);
+}
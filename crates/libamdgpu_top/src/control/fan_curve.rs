@@ -0,0 +1,426 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::PCI;
+use super::{ControlError, OdClkVoltage, SysfsWrite, write_sysfs};
+
+// Older GPUs: write the fan speed to `pwm1` with `pwm1_enable` set to manual (1).
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#hwmon-interfaces
+//
+// RDNA 3 and later: the SMU firmware applies the curve written to `fan_curve`.
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#fan-curve
+
+const PWM1: &str = "pwm1";
+const PWM1_ENABLE: &str = "pwm1_enable";
+const PWM1_MAX: &str = "pwm1_max";
+const PWM_MANUAL: &str = "1";
+const PWM_AUTO: &str = "2";
+const OD_FAN_CURVE: &str = "gpu_od/fan_ctrl/fan_curve";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FanCurvePoint {
+    pub temp: i64, // C
+    pub speed: u32, // %
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FanCurve {
+    pub points: Vec<FanCurvePoint>, // sorted by temp
+}
+
+impl FanCurve {
+    pub fn new(mut points: Vec<FanCurvePoint>) -> Self {
+        points.sort_by_key(|p| p.temp);
+
+        Self { points }
+    }
+
+    /// Linear interpolation between the curve points.
+    pub fn speed_at(&self, temp: i64) -> u32 {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return 100;
+        };
+
+        if temp <= first.temp { return first.speed }
+        if temp >= last.temp { return last.speed }
+
+        for w in self.points.windows(2) {
+            let [a, b] = [w[0], w[1]];
+            if temp > b.temp { continue }
+
+            let (dt, ds) = (b.temp - a.temp, b.speed as i64 - a.speed as i64);
+            if dt == 0 { return b.speed }
+
+            return (a.speed as i64 + ds * (temp - a.temp) / dt) as u32;
+        }
+
+        last.speed
+    }
+
+    /// Resample the curve into `n` points for `fan_curve`.
+    pub fn resample(&self, n: usize) -> Vec<FanCurvePoint> {
+        if self.points.len() <= n {
+            let last = self.points.last().copied();
+
+            return self.points
+                .iter()
+                .copied()
+                .chain(std::iter::repeat_n(last, n - self.points.len()).flatten())
+                .collect();
+        }
+
+        let (first, last) = (self.points[0].temp, self.points[self.points.len()-1].temp);
+
+        (0..n).map(|i| {
+            let temp = first + (last - first) * i as i64 / (n as i64 - 1);
+            FanCurvePoint { temp, speed: self.speed_at(temp) }
+        }).collect()
+    }
+}
+
+impl std::str::FromStr for FanCurve {
+    type Err = String;
+
+    /// "40:20, 60:40, 80:100" (temp:speed)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let points = s.split(',').map(|p| {
+            let (temp, speed) = p.split_once(':').ok_or_else(|| format!("Invalid curve point: {p:?}"))?;
+            let temp = temp.trim().trim_end_matches('C').parse::<i64>()
+                .map_err(|_| format!("Invalid temperature: {temp:?}"))?;
+            let speed = speed.trim().trim_end_matches('%').parse::<u32>()
+                .map_err(|_| format!("Invalid fan speed: {speed:?}"))?;
+
+            if speed > 100 {
+                return Err(format!("Fan speed must be 0-100%: {speed}"));
+            }
+
+            Ok(FanCurvePoint { temp, speed })
+        }).collect::<Result<Vec<_>, String>>()?;
+
+        if points.is_empty() {
+            return Err("Empty fan curve".to_string());
+        }
+
+        Ok(Self::new(points))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanTempSensor {
+    Edge,
+    Junction,
+    Memory,
+}
+
+#[derive(Debug, Clone)]
+pub struct FanCurveEntry {
+    pub pci: Option<PCI::BUS_INFO>, // None: all devices
+    pub sensor: FanTempSensor,
+    pub hysteresis: i64, // C
+    pub curve: FanCurve,
+}
+
+#[derive(Debug, Clone)]
+pub struct FanCurveConfig {
+    pub interval: Duration,
+    pub entries: Vec<FanCurveEntry>,
+}
+
+impl FanCurveConfig {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let s = fs::read_to_string(path).map_err(|e| format!("{path:?}: {e}"))?;
+
+        Self::parse(&s)
+    }
+
+    /// ```text
+    /// interval = 1000       # ms
+    ///
+    /// [0000:03:00.0]        # PCI bus, or "*" for all devices
+    /// sensor = junction     # edge, junction, memory
+    /// hysteresis = 3        # C
+    /// curve = 40:20, 60:40, 75:70, 85:100
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut config = Self { interval: Duration::from_secs(1), entries: Vec::new() };
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let err = |msg: String| format!("line {}: {msg}", i+1);
+            if line.is_empty() { continue }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let pci = match section.trim() {
+                    "*" => None,
+                    s => Some(s.parse::<PCI::BUS_INFO>().map_err(|_| err(format!("Invalid PCI bus: {s:?}")))?),
+                };
+
+                config.entries.push(FanCurveEntry {
+                    pci,
+                    sensor: FanTempSensor::Junction,
+                    hysteresis: 2,
+                    curve: FanCurve::default(),
+                });
+
+                continue;
+            }
+
+            let (key, val) = line.split_once('=').ok_or_else(|| err(format!("Invalid line: {line:?}")))?;
+            let (key, val) = (key.trim(), val.trim());
+
+            if key == "interval" {
+                let ms = val.trim_end_matches("ms").parse::<u64>()
+                    .map_err(|_| err(format!("Invalid interval: {val:?}")))?;
+                config.interval = Duration::from_millis(ms.max(100));
+                continue;
+            }
+
+            let Some(entry) = config.entries.last_mut() else {
+                return Err(err(format!("{key:?} must be in a device section")));
+            };
+
+            match key {
+                "sensor" => entry.sensor = match val {
+                    "edge" => FanTempSensor::Edge,
+                    "junction" | "hotspot" => FanTempSensor::Junction,
+                    "memory" => FanTempSensor::Memory,
+                    _ => return Err(err(format!("Unknown sensor: {val:?}"))),
+                },
+                "hysteresis" => entry.hysteresis = val.trim_end_matches('C').parse()
+                    .map_err(|_| err(format!("Invalid hysteresis: {val:?}")))?,
+                "curve" => entry.curve = val.parse().map_err(err)?,
+                _ => return Err(err(format!("Unknown key: {key:?}"))),
+            }
+        }
+
+        if let Some(entry) = config.entries.iter().find(|e| e.curve.points.is_empty()) {
+            let name = entry.pci.map_or("*".to_string(), |pci| pci.to_string());
+            return Err(format!("[{name}]: missing \"curve\""));
+        }
+
+        Ok(config)
+    }
+
+    pub fn get_entry(&self, pci: &PCI::BUS_INFO) -> Option<&FanCurveEntry> {
+        self.entries.iter().find(|e| e.pci.as_ref() == Some(pci))
+            .or_else(|| self.entries.iter().find(|e| e.pci.is_none()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanControlMethod {
+    Pwm,
+    OdFanCurve,
+}
+
+#[derive(Debug)]
+pub struct FanCurveController {
+    hwmon_path: PathBuf,
+    fan_curve_path: PathBuf,
+    pub method: FanControlMethod,
+    pub curve: FanCurve,
+    pub hysteresis: i64,
+    pub dry_run: bool,
+    /// (temp, speed) of the last applied fan speed
+    last: Option<(i64, u32)>,
+    enabled: bool,
+    writes: Vec<SysfsWrite>,
+}
+
+impl FanCurveController {
+    pub fn new<P: Into<PathBuf>, Q: Into<PathBuf>>(
+        sysfs_path: P,
+        hwmon_path: Q,
+        curve: FanCurve,
+        hysteresis: i64,
+    ) -> Result<Self, ControlError> {
+        let hwmon_path = hwmon_path.into();
+        let fan_curve_path = sysfs_path.into().join(OD_FAN_CURVE);
+
+        let method = if fan_curve_path.exists() {
+            FanControlMethod::OdFanCurve
+        } else if hwmon_path.join(PWM1_ENABLE).exists() {
+            FanControlMethod::Pwm
+        } else {
+            return Err(ControlError::Unsupported("fan control".to_string()));
+        };
+
+        Ok(Self {
+            hwmon_path,
+            fan_curve_path,
+            method,
+            curve,
+            hysteresis: hysteresis.max(0),
+            dry_run: false,
+            last: None,
+            enabled: false,
+            writes: Vec::new(),
+        })
+    }
+
+    /// Returns the writes performed since the last call.
+    pub fn take_writes(&mut self) -> Vec<SysfsWrite> {
+        std::mem::take(&mut self.writes)
+    }
+
+    fn write(&mut self, path: PathBuf, value: String) -> Result<(), ControlError> {
+        write_sysfs(path, value, self.dry_run, &mut self.writes)
+    }
+
+    pub fn enable(&mut self) -> Result<(), ControlError> {
+        match self.method {
+            FanControlMethod::Pwm => {
+                self.enabled = true;
+                self.write(self.hwmon_path.join(PWM1_ENABLE), PWM_MANUAL.to_string())?;
+            },
+            FanControlMethod::OdFanCurve => {
+                let s = fs::read_to_string(&self.fan_curve_path)
+                    .map_err(|err| ControlError::Io { path: self.fan_curve_path.clone(), err })?;
+                let od = OdClkVoltage::parse(&s);
+                let n = od.sections
+                    .iter()
+                    .find(|s| s.name == "OD_FAN_CURVE")
+                    .map_or(0, |s| s.entries.len());
+
+                if n == 0 {
+                    return Err(ControlError::Unsupported("OD_FAN_CURVE".to_string()));
+                }
+
+                let points = self.curve.resample(n);
+
+                for p in &points {
+                    for (name, value) in [
+                        ("FAN_CURVE(hotspot temp)", p.temp),
+                        ("FAN_CURVE(fan speed)", p.speed as i64),
+                    ] {
+                        let Some(r) = od.get_range(name) else { continue };
+                        if !(r.min..=r.max).contains(&value) {
+                            return Err(ControlError::OutOfRange { name: name.to_string(), value, min: r.min, max: r.max });
+                        }
+                    }
+                }
+
+                self.enabled = true;
+
+                for (i, p) in points.iter().enumerate() {
+                    self.write(self.fan_curve_path.clone(), format!("{i} {} {}", p.temp, p.speed))?;
+                }
+
+                self.write(self.fan_curve_path.clone(), "c".to_string())?;
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Returns the fan speed (%) if it was changed.
+    /// The fan speed is lowered only after the temperature drops by `hysteresis`.
+    pub fn update(&mut self, temp: i64) -> Result<Option<u32>, ControlError> {
+        if self.method == FanControlMethod::OdFanCurve || !self.enabled {
+            return Ok(None);
+        }
+
+        let target = self.curve.speed_at(temp);
+        let apply = match self.last {
+            None => true,
+            Some((_, speed)) if target > speed => true,
+            Some((last_temp, speed)) if target < speed => temp <= last_temp - self.hysteresis,
+            _ => false,
+        };
+
+        if !apply { return Ok(None) }
+
+        let pwm_max = super::parse_hwmon::<u32, _>(self.hwmon_path.join(PWM1_MAX)).unwrap_or(255);
+        let pwm = (target * pwm_max + 50) / 100;
+
+        self.write(self.hwmon_path.join(PWM1), pwm.to_string())?;
+        self.last = Some((temp, target));
+
+        Ok(Some(target))
+    }
+
+    /// Failsafe: give the fan control back to the firmware.
+    pub fn restore_auto(&mut self) -> Result<(), ControlError> {
+        if !self.enabled { return Ok(()) }
+
+        self.enabled = false;
+        self.last = None;
+
+        match self.method {
+            FanControlMethod::Pwm => self.write(self.hwmon_path.join(PWM1_ENABLE), PWM_AUTO.to_string()),
+            FanControlMethod::OdFanCurve => {
+                self.write(self.fan_curve_path.clone(), "r".to_string())?;
+                self.write(self.fan_curve_path.clone(), "c".to_string())
+            },
+        }
+    }
+}
+
+impl Drop for FanCurveController {
+    fn drop(&mut self) {
+        let _ = self.restore_auto();
+    }
+}
+
+#[test]
+fn test_fan_curve_fake_hwmon() {
+    let sysfs = crate::test_util::FakeSysfs::new("fan_curve");
+    let dir = sysfs.path();
+    let hwmon = dir.join("hwmon/hwmon0");
+    fs::create_dir_all(&hwmon).unwrap();
+    fs::write(hwmon.join(PWM1), "80\n").unwrap();
+    fs::write(hwmon.join(PWM1_ENABLE), "2\n").unwrap();
+    fs::write(hwmon.join(PWM1_MAX), "255\n").unwrap();
+
+    let config = FanCurveConfig::parse(concat!(
+        "interval = 500\n",
+        "[*]\n",
+        "sensor = edge\n",
+        "hysteresis = 3 # C\n",
+        "curve = 40:20, 80:100, 60:40\n",
+    )).unwrap();
+    let entry = config.get_entry(&"0000:03:00.0".parse().unwrap()).unwrap();
+    assert_eq!(config.interval, Duration::from_millis(500));
+    assert_eq!(entry.sensor, FanTempSensor::Edge);
+    assert_eq!(entry.curve.speed_at(30), 20);
+    assert_eq!(entry.curve.speed_at(50), 30);
+    assert_eq!(entry.curve.speed_at(70), 70);
+    assert_eq!(entry.curve.speed_at(90), 100);
+    assert_eq!(entry.curve.resample(5).len(), 5);
+    assert!(FanCurveConfig::parse("[*]\nsensor = edge\n").is_err());
+
+    let read_pwm = || fs::read_to_string(hwmon.join(PWM1)).unwrap();
+    let read_enable = || fs::read_to_string(hwmon.join(PWM1_ENABLE)).unwrap();
+
+    {
+        let mut ctrl = FanCurveController::new(dir, &hwmon, entry.curve.clone(), entry.hysteresis).unwrap();
+        assert_eq!(ctrl.method, FanControlMethod::Pwm);
+
+        ctrl.enable().unwrap();
+        assert_eq!(read_enable(), PWM_MANUAL);
+
+        assert_eq!(ctrl.update(60).unwrap(), Some(40));
+        assert_eq!(read_pwm(), "102");
+        // within hysteresis
+        assert_eq!(ctrl.update(58).unwrap(), None);
+        assert_eq!(ctrl.update(57).unwrap(), Some(37));
+        assert_eq!(ctrl.update(70).unwrap(), Some(70));
+    }
+
+    // restored by Drop
+    assert_eq!(read_enable(), PWM_AUTO);
+
+    // even if the fan was in the manual mode (possibly at a low speed) before
+    fs::write(hwmon.join(PWM1_ENABLE), "1\n").unwrap();
+    fs::write(hwmon.join(PWM1), "30\n").unwrap();
+
+    {
+        let mut ctrl = FanCurveController::new(dir, &hwmon, entry.curve.clone(), entry.hysteresis).unwrap();
+
+        ctrl.enable().unwrap();
+        assert_eq!(ctrl.update(70).unwrap(), Some(70));
+        ctrl.restore_auto().unwrap();
+        assert_eq!(read_enable(), PWM_AUTO);
+    }
+}
@@ -0,0 +1,328 @@
+// Writing to sysfs requires root privileges.
+// Nothing is written unless the caller explicitly calls one of the `set_*` functions,
+// and all previous values are saved so that they can be restored with `DeviceControl::restore`.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::DevicePath;
+use crate::stat::parse_hwmon;
+
+mod perf_level;
+pub use perf_level::*;
+
+mod power_profile_mode;
+pub use power_profile_mode::*;
+
+mod od_clk_voltage;
+pub use od_clk_voltage::*;
+
+mod fan_curve;
+pub use fan_curve::*;
+
+const POWER1_CAP: &str = "power1_cap";
+const POWER1_CAP_MIN: &str = "power1_cap_min";
+const POWER1_CAP_MAX: &str = "power1_cap_max";
+const PERF_LEVEL: &str = "power_dpm_force_performance_level";
+const POWER_PROFILE_MODE: &str = "pp_power_profile_mode";
+const OD_CLK_VOLTAGE: &str = "pp_od_clk_voltage";
+
+#[derive(Debug)]
+pub enum ControlError {
+    Io { path: PathBuf, err: io::Error },
+    OutOfRange { name: String, value: i64, min: i64, max: i64 },
+    Unsupported(String),
+}
+
+impl ControlError {
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(self, Self::Io { err, .. } if err.kind() == io::ErrorKind::PermissionDenied)
+    }
+}
+
+impl fmt::Display for ControlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { path, err } if err.kind() == io::ErrorKind::PermissionDenied =>
+                write!(f, "Permission denied: {path:?} (root privileges are required)"),
+            Self::Io { path, err } => write!(f, "{path:?}: {err}"),
+            Self::OutOfRange { name, value, min, max } =>
+                write!(f, "{name}: {value} is out of range ({min}..={max})"),
+            Self::Unsupported(s) => write!(f, "Unsupported: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for ControlError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysfsWrite {
+    pub path: PathBuf,
+    pub value: String,
+}
+
+impl fmt::Display for SysfsWrite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "echo \"{}\" > {}", self.value, self.path.display())
+    }
+}
+
+pub(crate) fn write_sysfs(
+    path: PathBuf,
+    value: String,
+    dry_run: bool,
+    history: &mut Vec<SysfsWrite>,
+) -> Result<(), ControlError> {
+    if !dry_run {
+        fs::write(&path, &value).map_err(|err| ControlError::Io { path: path.clone(), err })?;
+    }
+
+    history.push(SysfsWrite { path, value });
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct DeviceControl {
+    sysfs_path: PathBuf,
+    hwmon_path: Option<PathBuf>,
+    pub dry_run: bool,
+    pub restore_on_drop: bool,
+    /// (target, write to restore the previous value)
+    saved: Vec<(String, SysfsWrite)>,
+    history: Vec<SysfsWrite>,
+}
+
+impl DeviceControl {
+    pub fn new<P: Into<PathBuf>>(sysfs_path: P, hwmon_path: Option<PathBuf>) -> Self {
+        Self {
+            sysfs_path: sysfs_path.into(),
+            hwmon_path,
+            dry_run: false,
+            restore_on_drop: false,
+            saved: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn from_device_path(device_path: &DevicePath) -> Self {
+        Self::new(&device_path.sysfs_path, device_path.pci.get_hwmon_path())
+    }
+
+    /// All writes performed (or printed in dry-run mode) so far, in order.
+    pub fn history(&self) -> &[SysfsWrite] {
+        &self.history
+    }
+
+    pub fn has_saved_values(&self) -> bool {
+        !self.saved.is_empty()
+    }
+
+    fn hwmon_path(&self) -> Result<&Path, ControlError> {
+        self.hwmon_path
+            .as_deref()
+            .ok_or_else(|| ControlError::Unsupported("hwmon".to_string()))
+    }
+
+    fn read(path: &Path) -> Result<String, ControlError> {
+        fs::read_to_string(path).map_err(|err| ControlError::Io { path: path.to_path_buf(), err })
+    }
+
+    fn write(&mut self, path: PathBuf, value: String) -> Result<(), ControlError> {
+        write_sysfs(path, value, self.dry_run, &mut self.history)
+    }
+
+    fn save(&mut self, target: String, path: PathBuf, value: String) {
+        if self.saved.iter().any(|(t, _)| *t == target) { return }
+
+        self.saved.push((target, SysfsWrite { path, value }));
+    }
+
+    /// (min, max) in W
+    pub fn power_cap_range(&self) -> Result<(u32, u32), ControlError> {
+        let hwmon_path = self.hwmon_path()?;
+        let [min, max] = [POWER1_CAP_MIN, POWER1_CAP_MAX].map(|name| {
+            parse_hwmon::<u64, _>(hwmon_path.join(name)).map(|v| (v / 1_000_000) as u32)
+        });
+
+        min.zip(max).ok_or_else(|| ControlError::Unsupported(POWER1_CAP.to_string()))
+    }
+
+    pub fn set_power_cap(&mut self, watts: u32) -> Result<(), ControlError> {
+        let (min, max) = self.power_cap_range()?;
+
+        if !(min..=max).contains(&watts) {
+            return Err(ControlError::OutOfRange {
+                name: POWER1_CAP.to_string(),
+                value: watts as i64,
+                min: min as i64,
+                max: max as i64,
+            });
+        }
+
+        let path = self.hwmon_path()?.join(POWER1_CAP);
+        let prev = Self::read(&path)?;
+        self.save(POWER1_CAP.to_string(), path.clone(), prev.trim_end().to_string());
+
+        self.write(path, (watts as u64 * 1_000_000).to_string())
+    }
+
+    pub fn perf_level(&self) -> Result<PerformanceLevel, ControlError> {
+        let s = Self::read(&self.sysfs_path.join(PERF_LEVEL))?;
+
+        s.parse().map_err(ControlError::Unsupported)
+    }
+
+    pub fn set_perf_level(&mut self, level: PerformanceLevel) -> Result<(), ControlError> {
+        let path = self.sysfs_path.join(PERF_LEVEL);
+        let prev = self.perf_level()?;
+        self.save(PERF_LEVEL.to_string(), path.clone(), prev.to_string());
+
+        self.write(path, level.to_string())
+    }
+
+    pub fn power_profile_modes(&self) -> Result<Vec<PowerProfileMode>, ControlError> {
+        let s = Self::read(&self.sysfs_path.join(POWER_PROFILE_MODE))?;
+
+        Ok(PowerProfileMode::parse_list(&s))
+    }
+
+    /// `profile` is a profile name (e.g. "COMPUTE", `PowerProfile::to_string()`) or an index.
+    pub fn set_power_profile(&mut self, profile: &str) -> Result<(), ControlError> {
+        let path = self.sysfs_path.join(POWER_PROFILE_MODE);
+        let modes = self.power_profile_modes()?;
+        let mode = modes
+            .iter()
+            .find(|m| m.name_matches(profile) || profile.trim().parse::<u32>() == Ok(m.index))
+            .ok_or_else(|| ControlError::Unsupported(format!("power profile {profile:?}")))?;
+        let index = mode.index;
+
+        if let Some(prev) = modes.iter().find(|m| m.is_active) {
+            self.save(POWER_PROFILE_MODE.to_string(), path.clone(), prev.index.to_string());
+        }
+
+        self.write(path, index.to_string())
+    }
+
+    pub fn od_clk_voltage(&self) -> Result<OdClkVoltage, ControlError> {
+        let s = Self::read(&self.sysfs_path.join(OD_CLK_VOLTAGE))?;
+
+        Ok(OdClkVoltage::parse(&s))
+    }
+
+    /// The new values take effect after `DeviceControl::commit_od`.
+    pub fn set_od(&mut self, cmd: OdCommand) -> Result<(), ControlError> {
+        let od = self.od_clk_voltage()?;
+
+        for (name, value) in OdClkVoltage::values_to_check(&cmd) {
+            let Some(range) = od.get_range(&name) else {
+                return Err(ControlError::Unsupported(format!("{name} in OD_RANGE")));
+            };
+
+            if !(range.min..=range.max).contains(&value) {
+                return Err(ControlError::OutOfRange { name, value, min: range.min, max: range.max });
+            }
+        }
+
+        let path = self.sysfs_path.join(OD_CLK_VOLTAGE);
+
+        if let Some(prev) = od.current_value_cmd(&cmd) {
+            self.save(cmd.target(), path.clone(), prev.to_string());
+        }
+
+        self.write(path, cmd.to_string())
+    }
+
+    pub fn commit_od(&mut self) -> Result<(), ControlError> {
+        self.write(self.sysfs_path.join(OD_CLK_VOLTAGE), "c".to_string())
+    }
+
+    /// Write back all saved values in reverse order.
+    /// Restoring continues even if one of the writes fails, and the first error is returned.
+    pub fn restore(&mut self) -> Result<(), ControlError> {
+        let saved = std::mem::take(&mut self.saved);
+        let mut first_err = None;
+        let mut iter = saved.into_iter().rev().peekable();
+
+        while let Some((_, w)) = iter.next() {
+            let is_od = w.path.ends_with(OD_CLK_VOLTAGE);
+            let mut r = self.write(w.path, w.value);
+
+            // commit at the end of a block of pp_od_clk_voltage writes
+            let next_is_od = iter.peek().is_some_and(|(_, w)| w.path.ends_with(OD_CLK_VOLTAGE));
+
+            if is_od && !next_is_od && r.is_ok() {
+                r = self.commit_od();
+            }
+
+            if let Err(e) = r {
+                first_err.get_or_insert(e);
+            }
+        }
+
+        first_err.map_or(Ok(()), Err)
+    }
+}
+
+impl Drop for DeviceControl {
+    fn drop(&mut self) {
+        if self.restore_on_drop {
+            let _ = self.restore();
+        }
+    }
+}
+
+#[test]
+fn test_device_control_fake_sysfs() {
+    let sysfs = crate::test_util::FakeSysfs::new("control");
+    let dir = sysfs.path();
+    let hwmon = dir.join("hwmon/hwmon0");
+    fs::create_dir_all(&hwmon).unwrap();
+
+    fs::write(hwmon.join(POWER1_CAP), "200000000\n").unwrap();
+    fs::write(hwmon.join(POWER1_CAP_MIN), "100000000\n").unwrap();
+    fs::write(hwmon.join(POWER1_CAP_MAX), "250000000\n").unwrap();
+    fs::write(dir.join(PERF_LEVEL), "auto\n").unwrap();
+    fs::copy("src/control/pp_power_profile_mode_sample.txt", dir.join(POWER_PROFILE_MODE)).unwrap();
+    fs::copy("src/control/pp_od_clk_voltage_sample.txt", dir.join(OD_CLK_VOLTAGE)).unwrap();
+
+    let mut ctrl = DeviceControl::new(dir, Some(hwmon.clone()));
+
+    assert_eq!(ctrl.power_cap_range().unwrap(), (100, 250));
+    assert!(matches!(ctrl.set_power_cap(300), Err(ControlError::OutOfRange { .. })));
+    ctrl.set_power_cap(220).unwrap();
+    assert_eq!(fs::read_to_string(hwmon.join(POWER1_CAP)).unwrap(), "220000000");
+
+    ctrl.set_perf_level(PerformanceLevel::Manual).unwrap();
+    assert_eq!(fs::read_to_string(dir.join(PERF_LEVEL)).unwrap(), "manual");
+
+    let modes = ctrl.power_profile_modes().unwrap();
+    assert_eq!(modes.len(), 8);
+    assert!(modes[0].is_active && modes[0].name == "BOOTUP_DEFAULT");
+    ctrl.set_power_profile("COMPUTE").unwrap();
+    assert_eq!(fs::read_to_string(dir.join(POWER_PROFILE_MODE)).unwrap(), "5");
+
+    assert!(matches!(
+        ctrl.set_od(OdCommand::Sclk { index: 1, mhz: 3100 }),
+        Err(ControlError::OutOfRange { .. }),
+    ));
+    ctrl.set_od("s 1 2600".parse().unwrap()).unwrap();
+    ctrl.commit_od().unwrap();
+
+    ctrl.restore().unwrap();
+
+    let restore: Vec<String> = ctrl.history()[5..].iter().map(|w| w.value.clone()).collect();
+    assert_eq!(restore, ["s 1 2500", "c", "0", "auto", "200000000"]);
+    assert_eq!(fs::read_to_string(hwmon.join(POWER1_CAP)).unwrap(), "200000000");
+    assert_eq!(fs::read_to_string(dir.join(PERF_LEVEL)).unwrap(), "auto");
+
+    let mut dry = DeviceControl::new(dir, Some(hwmon.clone()));
+    dry.dry_run = true;
+    dry.set_power_cap(150).unwrap();
+    assert_eq!(fs::read_to_string(hwmon.join(POWER1_CAP)).unwrap(), "200000000");
+    assert_eq!(
+        dry.history()[0].to_string(),
+        format!("echo \"150000000\" > {}", hwmon.join(POWER1_CAP).display()),
+    );
+}
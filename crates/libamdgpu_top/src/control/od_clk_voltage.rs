@@ -0,0 +1,185 @@
+use std::fmt;
+use std::str::FromStr;
+
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#pp-od-clk-voltage
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdEntry {
+    pub index: Option<u32>,
+    pub values: Vec<i64>, // MHz, mV, %
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdSection {
+    pub name: String, // OD_SCLK, OD_MCLK, OD_VDDC_CURVE, OD_VDDGFX_OFFSET, ...
+    pub entries: Vec<OdEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdRange {
+    pub name: String, // SCLK, MCLK, VDDC_CURVE_SCLK[0], VDDGFX_OFFSET, ...
+    pub min: i64,
+    pub max: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OdClkVoltage {
+    pub sections: Vec<OdSection>,
+    pub ranges: Vec<OdRange>,
+}
+
+impl OdClkVoltage {
+    pub fn parse(s: &str) -> Self {
+        let mut od = Self::default();
+        let mut in_range = false;
+
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() { continue }
+
+            if line.starts_with("OD_") && line.ends_with(':') {
+                let name = line.trim_end_matches(':');
+                in_range = name == "OD_RANGE";
+
+                if !in_range {
+                    od.sections.push(OdSection { name: name.to_string(), entries: Vec::new() });
+                }
+
+                continue;
+            }
+
+            if in_range {
+                let Some((name, values)) = line.split_once(':') else { continue };
+                let values: Vec<i64> = values.split_whitespace().filter_map(parse_with_unit).collect();
+
+                if let [min, max] = values[..] {
+                    od.ranges.push(OdRange { name: name.trim().to_string(), min, max });
+                }
+
+                continue;
+            }
+
+            let Some(section) = od.sections.last_mut() else { continue };
+
+            let (index, values) = match line.split_once(':') {
+                Some((index, values)) => (index.trim().parse::<u32>().ok(), values),
+                None => (None, line),
+            };
+            let values = values.split_whitespace().filter_map(parse_with_unit).collect();
+
+            section.entries.push(OdEntry { index, values });
+        }
+
+        od
+    }
+
+    pub fn get_range(&self, name: &str) -> Option<&OdRange> {
+        self.ranges.iter().find(|r| r.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn get_entry(&self, section: &str, index: Option<u32>) -> Option<&OdEntry> {
+        self.sections
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(section))?
+            .entries
+            .iter()
+            .find(|e| index.is_none() || e.index == index)
+    }
+
+    /// Returns (range name, value) pairs to be checked against `OD_RANGE`.
+    pub fn values_to_check(cmd: &OdCommand) -> Vec<(String, i64)> {
+        match *cmd {
+            OdCommand::Sclk { mhz, .. } => vec![("SCLK".to_string(), mhz)],
+            OdCommand::Mclk { mhz, .. } => vec![("MCLK".to_string(), mhz)],
+            OdCommand::VddcCurve { index, mhz, mv } => vec![
+                (format!("VDDC_CURVE_SCLK[{index}]"), mhz),
+                (format!("VDDC_CURVE_VOLT[{index}]"), mv),
+            ],
+            OdCommand::VddgfxOffset(mv) => vec![("VDDGFX_OFFSET".to_string(), mv)],
+        }
+    }
+
+    /// Build a command that writes back the current value of the entry modified by `cmd`.
+    pub fn current_value_cmd(&self, cmd: &OdCommand) -> Option<OdCommand> {
+        let prev = match *cmd {
+            OdCommand::Sclk { index, .. } => {
+                let e = self.get_entry("OD_SCLK", Some(index))?;
+                OdCommand::Sclk { index, mhz: *e.values.first()? }
+            },
+            OdCommand::Mclk { index, .. } => {
+                let e = self.get_entry("OD_MCLK", Some(index))?;
+                OdCommand::Mclk { index, mhz: *e.values.first()? }
+            },
+            OdCommand::VddcCurve { index, .. } => {
+                let e = self.get_entry("OD_VDDC_CURVE", Some(index))?;
+                OdCommand::VddcCurve { index, mhz: *e.values.first()?, mv: *e.values.get(1)? }
+            },
+            OdCommand::VddgfxOffset(_) => {
+                let e = self.get_entry("OD_VDDGFX_OFFSET", None)?;
+                OdCommand::VddgfxOffset(*e.values.first()?)
+            },
+        };
+
+        Some(prev)
+    }
+}
+
+fn parse_with_unit(s: &str) -> Option<i64> {
+    s.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '%').parse().ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OdCommand {
+    Sclk { index: u32, mhz: i64 },
+    Mclk { index: u32, mhz: i64 },
+    VddcCurve { index: u32, mhz: i64, mv: i64 },
+    VddgfxOffset(i64),
+}
+
+impl OdCommand {
+    /// Key to identify the entry modified by the command.
+    pub fn target(&self) -> String {
+        match self {
+            Self::Sclk { index, .. } => format!("s {index}"),
+            Self::Mclk { index, .. } => format!("m {index}"),
+            Self::VddcCurve { index, .. } => format!("vc {index}"),
+            Self::VddgfxOffset(_) => "vo".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for OdCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Sclk { index, mhz } => write!(f, "s {index} {mhz}"),
+            Self::Mclk { index, mhz } => write!(f, "m {index} {mhz}"),
+            Self::VddcCurve { index, mhz, mv } => write!(f, "vc {index} {mhz} {mv}"),
+            Self::VddgfxOffset(mv) => write!(f, "vo {mv}"),
+        }
+    }
+}
+
+impl FromStr for OdCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("Invalid pp_od_clk_voltage command: {s:?}");
+        let args: Vec<&str> = s.split_whitespace().collect();
+        let num = |i: usize| -> Result<i64, String> {
+            args.get(i).and_then(|v| parse_with_unit(v)).ok_or_else(err)
+        };
+        let idx = || -> Result<u32, String> {
+            u32::try_from(num(1)?).map_err(|_| err())
+        };
+
+        let cmd = match args.first().copied() {
+            Some("s") if args.len() == 3 => Self::Sclk { index: idx()?, mhz: num(2)? },
+            Some("m") if args.len() == 3 => Self::Mclk { index: idx()?, mhz: num(2)? },
+            Some("vc") if args.len() == 4 => Self::VddcCurve { index: idx()?, mhz: num(2)?, mv: num(3)? },
+            Some("vo") if args.len() == 2 => Self::VddgfxOffset(num(1)?),
+            _ => return Err(err()),
+        };
+
+        Ok(cmd)
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use super::PERF_LEVEL;
+
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#power-dpm-force-performance-level
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerformanceLevel {
+    Auto,
+    Low,
+    High,
+    Manual,
+    ProfileStandard,
+    ProfileMinSclk,
+    ProfileMinMclk,
+    ProfilePeak,
+    PerfDeterminism,
+}
+
+impl PerformanceLevel {
+    pub const ALL: [Self; 9] = [
+        Self::Auto,
+        Self::Low,
+        Self::High,
+        Self::Manual,
+        Self::ProfileStandard,
+        Self::ProfileMinSclk,
+        Self::ProfileMinMclk,
+        Self::ProfilePeak,
+        Self::PerfDeterminism,
+    ];
+
+    pub fn get_from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let s = std::fs::read_to_string(sysfs_path.into().join(PERF_LEVEL)).ok()?;
+
+        s.parse().ok()
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Low => "low",
+            Self::High => "high",
+            Self::Manual => "manual",
+            Self::ProfileStandard => "profile_standard",
+            Self::ProfileMinSclk => "profile_min_sclk",
+            Self::ProfileMinMclk => "profile_min_mclk",
+            Self::ProfilePeak => "profile_peak",
+            Self::PerfDeterminism => "perf_determinism",
+        }
+    }
+}
+
+impl FromStr for PerformanceLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        Self::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown performance level: {s:?}"))
+    }
+}
+
+impl fmt::Display for PerformanceLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
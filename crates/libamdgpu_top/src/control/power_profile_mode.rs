@@ -0,0 +1,72 @@
+// The layout of pp_power_profile_mode differs between SMU versions.
+// Some print one profile per line, SMU v13 prints all profiles in the header line,
+// so we look for the known profile names instead of parsing the table.
+// ref: drivers/gpu/drm/amd/pm/swsmu/smu*/
+
+const PROFILE_NAMES: &[&str] = &[
+    "BOOTUP_DEFAULT",
+    "3D_FULL_SCREEN",
+    "POWER_SAVING",
+    "VIDEO",
+    "VR",
+    "COMPUTE",
+    "CUSTOM",
+    "WINDOW_3D",
+    "CAPPED",
+    "UNCAPPED",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PowerProfileMode {
+    pub index: u32,
+    pub name: String,
+    pub is_active: bool,
+}
+
+impl PowerProfileMode {
+    pub fn parse_list(s: &str) -> Vec<Self> {
+        let mut list: Vec<Self> = Vec::new();
+
+        for line in s.lines() {
+            for name in PROFILE_NAMES {
+                if list.iter().any(|mode| mode.name == *name) { continue }
+                let Some(mode) = Self::find_in_line(line, name) else { continue };
+
+                list.push(mode);
+            }
+        }
+
+        list.sort_by_key(|mode| mode.index);
+
+        list
+    }
+
+    fn find_in_line(line: &str, name: &str) -> Option<Self> {
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+        for (pos, _) in line.match_indices(name) {
+            let (pre, post) = (&line[..pos], &line[pos+name.len()..]);
+
+            if post.starts_with(is_name_char) { continue }
+            if pre.ends_with(|c: char| c.is_ascii_alphabetic() || c == '_') { continue }
+
+            let pre = pre.trim_end();
+            let digits_len = pre.chars().rev().take_while(|c| c.is_ascii_digit()).count();
+            let Ok(index) = pre[pre.len()-digits_len..].parse::<u32>() else { continue };
+            let is_active = post.trim_start().starts_with('*');
+
+            return Some(Self { index, name: name.to_string(), is_active });
+        }
+
+        None
+    }
+
+    /// Compare with the name of `PowerProfile` or a user input, ignoring case and separators.
+    pub fn name_matches(&self, other: &str) -> bool {
+        let normalize = |s: &str| -> String {
+            s.chars().filter(|c| c.is_ascii_alphanumeric()).map(|c| c.to_ascii_uppercase()).collect()
+        };
+
+        normalize(&self.name) == normalize(other)
+    }
+}
@@ -72,6 +72,19 @@ impl DevicePath {
         }).collect()
     }
 
+    // for a hotplugged device
+    pub fn from_pci(pci: PCI::BUS_INFO) -> Option<Self> {
+        let driver = fs::read_link(pci.get_sysfs_path().join("driver")).ok()?;
+
+        if !driver.ends_with("amdgpu") { return None }
+
+        let mut device_path = Self::try_from(pci).ok()?;
+        device_path.libdrm_amdgpu = LibDrmAmdgpu::new().ok();
+        device_path.fill_amdgpu_device_name();
+
+        Some(device_path)
+    }
+
     pub fn fill_amdgpu_device_name(&mut self) {
         if let [Some(did), Some(rid)] = [self.device_id, self.revision_id] {
             self.device_name = AMDGPU::find_device_name(did, rid)
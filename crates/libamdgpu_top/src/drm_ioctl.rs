@@ -0,0 +1,452 @@
+// ref: https://github.com/torvalds/linux/blob/master/include/uapi/drm/drm.h
+// ref: https://github.com/torvalds/linux/blob/master/include/uapi/drm/drm_mode.h
+
+use std::os::fd::RawFd;
+
+pub(crate) const DRM_MODE_OBJECT_CRTC: u32 = 0xcccccccc;
+pub(crate) const DRM_MODE_OBJECT_CONNECTOR: u32 = 0xc0c0c0c0;
+pub(crate) const DRM_MODE_OBJECT_PLANE: u32 = 0xeeeeeeee;
+
+pub(crate) const DRM_CLIENT_CAP_UNIVERSAL_PLANES: u64 = 2;
+pub(crate) const DRM_MODE_FB_MODIFIERS: u32 = 1 << 1;
+pub(crate) const DRM_MODE_CONNECTED: u32 = 1;
+
+const DRM_MODE_FLAG_INTERLACE: u32 = 1 << 4;
+const DRM_MODE_FLAG_DBLSCAN: u32 = 1 << 5;
+
+const DRM_IOCTL_BASE: u64 = b'd' as u64;
+const DRM_PROP_NAME_LEN: usize = 32;
+
+const fn drm_iowr<T>(nr: u64) -> u64 {
+    (3 << 30) | ((std::mem::size_of::<T>() as u64) << 16) | (DRM_IOCTL_BASE << 8) | nr
+}
+
+const fn drm_iow<T>(nr: u64) -> u64 {
+    (1 << 30) | ((std::mem::size_of::<T>() as u64) << 16) | (DRM_IOCTL_BASE << 8) | nr
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_set_client_cap {
+    capability: u64,
+    value: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_gem_close {
+    handle: u32,
+    pad: u32,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_mode_card_res {
+    fb_id_ptr: u64,
+    crtc_id_ptr: u64,
+    connector_id_ptr: u64,
+    encoder_id_ptr: u64,
+    count_fbs: u32,
+    count_crtcs: u32,
+    count_connectors: u32,
+    count_encoders: u32,
+    min_width: u32,
+    max_width: u32,
+    min_height: u32,
+    max_height: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(non_camel_case_types, dead_code)]
+pub(crate) struct drm_mode_modeinfo {
+    pub clock: u32, // kHz
+    pub hdisplay: u16,
+    pub hsync_start: u16,
+    pub hsync_end: u16,
+    pub htotal: u16,
+    pub hskew: u16,
+    pub vdisplay: u16,
+    pub vsync_start: u16,
+    pub vsync_end: u16,
+    pub vtotal: u16,
+    pub vscan: u16,
+    pub vrefresh: u32,
+    pub flags: u32,
+    pub type_: u32,
+    pub name: [u8; 32],
+}
+
+impl drm_mode_modeinfo {
+    // same as drm_mode_vrefresh
+    pub fn refresh_rate(&self) -> f64 {
+        let total = self.htotal as f64 * self.vtotal as f64;
+
+        if total == 0.0 { return self.vrefresh as f64 }
+
+        let mut rate = self.clock as f64 * 1000.0 / total;
+
+        if self.flags & DRM_MODE_FLAG_INTERLACE != 0 { rate *= 2.0 }
+        if self.flags & DRM_MODE_FLAG_DBLSCAN != 0 { rate /= 2.0 }
+        if self.vscan > 1 { rate /= self.vscan as f64 }
+
+        rate
+    }
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+pub(crate) struct drm_mode_crtc {
+    set_connectors_ptr: u64,
+    count_connectors: u32,
+    pub crtc_id: u32,
+    pub fb_id: u32,
+    pub x: u32,
+    pub y: u32,
+    pub gamma_size: u32,
+    pub mode_valid: u32,
+    pub mode: drm_mode_modeinfo,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+pub(crate) struct drm_mode_get_encoder {
+    pub encoder_id: u32,
+    pub encoder_type: u32,
+    pub crtc_id: u32,
+    pub possible_crtcs: u32,
+    pub possible_clones: u32,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_mode_get_plane_res {
+    plane_id_ptr: u64,
+    count_planes: u32,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+pub(crate) struct drm_mode_get_plane {
+    pub plane_id: u32,
+    pub crtc_id: u32,
+    pub fb_id: u32,
+    pub possible_crtcs: u32,
+    pub gamma_size: u32,
+    count_format_types: u32,
+    format_type_ptr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+pub(crate) struct drm_mode_fb_cmd2 {
+    pub fb_id: u32,
+    pub width: u32,
+    pub height: u32,
+    pub pixel_format: u32, // fourcc
+    pub flags: u32,
+    handles: [u32; 4],
+    pub pitches: [u32; 4],
+    pub offsets: [u32; 4],
+    pub modifier: [u64; 4],
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+pub(crate) struct drm_mode_get_connector {
+    encoders_ptr: u64,
+    modes_ptr: u64,
+    props_ptr: u64,
+    prop_values_ptr: u64,
+    count_modes: u32,
+    count_props: u32,
+    count_encoders: u32,
+    pub encoder_id: u32,
+    pub connector_id: u32,
+    pub connector_type: u32,
+    pub connector_type_id: u32,
+    pub connection: u32,
+    pub mm_width: u32,
+    pub mm_height: u32,
+    pub subpixel: u32,
+    pad: u32,
+}
+
+pub(crate) struct CardResources {
+    pub crtcs: Vec<u32>,
+    pub connectors: Vec<u32>,
+    pub encoders: Vec<u32>,
+    pub min_size: (u32, u32),
+    pub max_size: (u32, u32),
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_mode_get_property {
+    values_ptr: u64,
+    enum_blob_ptr: u64,
+    prop_id: u32,
+    flags: u32,
+    name: [u8; DRM_PROP_NAME_LEN],
+    count_values: u32,
+    count_enum_blobs: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_mode_property_enum {
+    value: u64,
+    name: [u8; DRM_PROP_NAME_LEN],
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DrmProperty {
+    pub name: String,
+    pub enums: Vec<(u64, String)>, // (value, name)
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_mode_obj_get_properties {
+    props_ptr: u64,
+    prop_values_ptr: u64,
+    count_props: u32,
+    obj_id: u32,
+    obj_type: u32,
+}
+
+#[repr(C)]
+#[derive(Default)]
+#[allow(non_camel_case_types, dead_code)]
+struct drm_mode_get_blob {
+    blob_id: u32,
+    length: u32,
+    data: u64,
+}
+
+const DRM_IOCTL_SET_CLIENT_CAP: u64 = drm_iow::<drm_set_client_cap>(0x0D);
+const DRM_IOCTL_GEM_CLOSE: u64 = drm_iow::<drm_gem_close>(0x09);
+const DRM_IOCTL_MODE_GETRESOURCES: u64 = drm_iowr::<drm_mode_card_res>(0xA0);
+const DRM_IOCTL_MODE_GETCRTC: u64 = drm_iowr::<drm_mode_crtc>(0xA1);
+const DRM_IOCTL_MODE_GETENCODER: u64 = drm_iowr::<drm_mode_get_encoder>(0xA6);
+const DRM_IOCTL_MODE_GETCONNECTOR: u64 = drm_iowr::<drm_mode_get_connector>(0xA7);
+const DRM_IOCTL_MODE_GETPLANERESOURCES: u64 = drm_iowr::<drm_mode_get_plane_res>(0xB5);
+const DRM_IOCTL_MODE_GETPLANE: u64 = drm_iowr::<drm_mode_get_plane>(0xB6);
+const DRM_IOCTL_MODE_GETFB2: u64 = drm_iowr::<drm_mode_fb_cmd2>(0xCE);
+const DRM_IOCTL_MODE_GETPROPERTY: u64 = drm_iowr::<drm_mode_get_property>(0xAA);
+const DRM_IOCTL_MODE_GETPROPBLOB: u64 = drm_iowr::<drm_mode_get_blob>(0xAC);
+const DRM_IOCTL_MODE_OBJ_GETPROPERTIES: u64 = drm_iowr::<drm_mode_obj_get_properties>(0xB9);
+
+// same as drmIoctl
+pub(crate) fn drm_ioctl<T>(fd: RawFd, request: u64, arg: &mut T) -> bool {
+    loop {
+        let r = unsafe { libc::ioctl(fd, request as _, arg as *mut T) };
+
+        if r == 0 { return true }
+
+        let errno = std::io::Error::last_os_error().raw_os_error();
+
+        if errno != Some(libc::EINTR) && errno != Some(libc::EAGAIN) { return false }
+    }
+}
+
+fn name_from_bytes(name: &[u8]) -> String {
+    let len = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+
+    String::from_utf8_lossy(&name[..len]).to_string()
+}
+
+pub(crate) fn get_property_name(fd: RawFd, prop_id: u32) -> Option<String> {
+    let mut prop = drm_mode_get_property { prop_id, ..Default::default() };
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPROPERTY, &mut prop) { return None }
+
+    Some(name_from_bytes(&prop.name))
+}
+
+// the name and the enum names of the property
+pub(crate) fn get_property(fd: RawFd, prop_id: u32) -> Option<DrmProperty> {
+    let mut prop = drm_mode_get_property { prop_id, ..Default::default() };
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPROPERTY, &mut prop) { return None }
+
+    let mut enums = vec![
+        drm_mode_property_enum { value: 0, name: [0; DRM_PROP_NAME_LEN] };
+        prop.count_enum_blobs as usize
+    ];
+
+    if !enums.is_empty() {
+        // the values are not needed
+        prop.count_values = 0;
+        prop.enum_blob_ptr = enums.as_mut_ptr() as u64;
+
+        if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPROPERTY, &mut prop) { return None }
+
+        enums.truncate(prop.count_enum_blobs as usize);
+    }
+
+    Some(DrmProperty {
+        name: name_from_bytes(&prop.name),
+        enums: enums.iter().map(|e| (e.value, name_from_bytes(&e.name))).collect(),
+    })
+}
+
+pub(crate) fn get_property_blob(fd: RawFd, blob_id: u32) -> Option<Vec<u8>> {
+    let mut arg = drm_mode_get_blob { blob_id, ..Default::default() };
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPROPBLOB, &mut arg) || arg.length == 0 { return None }
+
+    let mut buf = vec![0u8; arg.length as usize];
+    arg.data = buf.as_mut_ptr() as u64;
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPROPBLOB, &mut arg) { return None }
+
+    buf.truncate(arg.length as usize);
+
+    Some(buf)
+}
+
+// Vec<(prop_id, value)>
+pub(crate) fn get_object_properties(fd: RawFd, obj_id: u32, obj_type: u32) -> Vec<(u32, u64)> {
+    let mut arg = drm_mode_obj_get_properties { obj_id, obj_type, ..Default::default() };
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_OBJ_GETPROPERTIES, &mut arg) || arg.count_props == 0 {
+        return Vec::new();
+    }
+
+    let mut props = vec![0u32; arg.count_props as usize];
+    let mut values = vec![0u64; arg.count_props as usize];
+
+    arg.props_ptr = props.as_mut_ptr() as u64;
+    arg.prop_values_ptr = values.as_mut_ptr() as u64;
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_OBJ_GETPROPERTIES, &mut arg) {
+        return Vec::new();
+    }
+
+    // the number of properties may have decreased
+    let len = (arg.count_props as usize).min(props.len());
+
+    props.into_iter().zip(values).take(len).collect()
+}
+
+pub(crate) fn set_client_cap(fd: RawFd, capability: u64, value: u64) -> bool {
+    drm_ioctl(fd, DRM_IOCTL_SET_CLIENT_CAP, &mut drm_set_client_cap { capability, value })
+}
+
+pub(crate) fn get_resources(fd: RawFd) -> Option<CardResources> {
+    let mut res = drm_mode_card_res::default();
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETRESOURCES, &mut res) { return None }
+
+    // the framebuffers are not fetched
+    let mut crtcs = vec![0u32; res.count_crtcs as usize];
+    let mut connectors = vec![0u32; res.count_connectors as usize];
+    let mut encoders = vec![0u32; res.count_encoders as usize];
+    let mut res = drm_mode_card_res {
+        crtc_id_ptr: crtcs.as_mut_ptr() as u64,
+        connector_id_ptr: connectors.as_mut_ptr() as u64,
+        encoder_id_ptr: encoders.as_mut_ptr() as u64,
+        count_crtcs: res.count_crtcs,
+        count_connectors: res.count_connectors,
+        count_encoders: res.count_encoders,
+        ..Default::default()
+    };
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETRESOURCES, &mut res) { return None }
+
+    crtcs.truncate(res.count_crtcs as usize);
+    connectors.truncate(res.count_connectors as usize);
+    encoders.truncate(res.count_encoders as usize);
+
+    Some(CardResources {
+        crtcs,
+        connectors,
+        encoders,
+        min_size: (res.min_width, res.min_height),
+        max_size: (res.max_width, res.max_height),
+    })
+}
+
+pub(crate) fn get_crtc(fd: RawFd, crtc_id: u32) -> Option<drm_mode_crtc> {
+    let mut crtc = drm_mode_crtc { crtc_id, ..Default::default() };
+
+    drm_ioctl(fd, DRM_IOCTL_MODE_GETCRTC, &mut crtc).then_some(crtc)
+}
+
+// count_modes > 0 not to probe the connector (and read EDID), same as drmModeGetConnectorCurrent
+pub(crate) fn get_connector_current(fd: RawFd, connector_id: u32) -> Option<drm_mode_get_connector> {
+    let mut mode = drm_mode_modeinfo::default();
+    let mut conn = drm_mode_get_connector {
+        connector_id,
+        modes_ptr: &mut mode as *mut drm_mode_modeinfo as u64,
+        count_modes: 1,
+        ..Default::default()
+    };
+
+    drm_ioctl(fd, DRM_IOCTL_MODE_GETCONNECTOR, &mut conn).then_some(conn)
+}
+
+pub(crate) fn get_encoder(fd: RawFd, encoder_id: u32) -> Option<drm_mode_get_encoder> {
+    let mut encoder = drm_mode_get_encoder { encoder_id, ..Default::default() };
+
+    drm_ioctl(fd, DRM_IOCTL_MODE_GETENCODER, &mut encoder).then_some(encoder)
+}
+
+pub(crate) fn get_plane_resources(fd: RawFd) -> Vec<u32> {
+    let mut res = drm_mode_get_plane_res::default();
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPLANERESOURCES, &mut res) || res.count_planes == 0 {
+        return Vec::new();
+    }
+
+    let mut planes = vec![0u32; res.count_planes as usize];
+    res.plane_id_ptr = planes.as_mut_ptr() as u64;
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPLANERESOURCES, &mut res) { return Vec::new() }
+
+    planes.truncate(res.count_planes as usize);
+
+    planes
+}
+
+// (plane, formats)
+pub(crate) fn get_plane(fd: RawFd, plane_id: u32) -> Option<(drm_mode_get_plane, Vec<u32>)> {
+    let mut plane = drm_mode_get_plane { plane_id, ..Default::default() };
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPLANE, &mut plane) { return None }
+
+    let mut formats = vec![0u32; plane.count_format_types as usize];
+    plane.format_type_ptr = formats.as_mut_ptr() as u64;
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETPLANE, &mut plane) { return None }
+
+    formats.truncate(plane.count_format_types as usize);
+
+    Some((plane, formats))
+}
+
+pub(crate) fn get_fb2(fd: RawFd, fb_id: u32) -> Option<drm_mode_fb_cmd2> {
+    let mut fb = drm_mode_fb_cmd2 { fb_id, ..Default::default() };
+
+    if !drm_ioctl(fd, DRM_IOCTL_MODE_GETFB2, &mut fb) { return None }
+
+    // GEM handles are only returned to DRM master or CAP_SYS_ADMIN
+    for handle in fb.handles.iter().filter(|h| **h != 0) {
+        drm_ioctl(fd, DRM_IOCTL_GEM_CLOSE, &mut drm_gem_close { handle: *handle, pad: 0 });
+    }
+
+    Some(fb)
+}
@@ -1,5 +1,6 @@
 use std::fmt::Write;
 use std::fs::File;
+use std::os::fd::{AsRawFd, RawFd};
 use crate::{
     LibDrm,
     drmModePropType,
@@ -10,6 +11,8 @@ use crate::{
     drm_mode_property_enum,
 };
 use crate::DevicePath;
+use crate::edid::Edid;
+use crate::drm_ioctl;
 
 #[derive(Debug, Clone)]
 pub struct ConnectorInfo {
@@ -20,6 +23,7 @@ pub struct ConnectorInfo {
     pub mode_info: Vec<drmModeModeInfo>,
     pub mode_props: Vec<(ModeProp, u64)>,
     pub crtc: Option<drmModeCrtc>,
+    pub edid: Option<Edid>,
 }
 
 impl ConnectorInfo {
@@ -61,14 +65,12 @@ pub fn connector_info(device_path: &DevicePath) -> Vec<ConnectorInfo> {
     let Some(libdrm) = device_path.libdrm_amdgpu.clone().map(|l| LibDrm::from(l)) else {
         return Vec::new();
     };
-    let fd = {
-        use std::os::fd::IntoRawFd;
+    let Some(f) = File::open(&device_path.card).ok() else { return Vec::new() };
 
-        let Some(f) = File::open(&device_path.card).ok() else { return Vec::new() };
-
-        f.into_raw_fd()
-    };
+    connector_info_from_fd(&libdrm, f.as_raw_fd())
+}
 
+pub(crate) fn connector_info_from_fd(libdrm: &LibDrm, fd: RawFd) -> Vec<ConnectorInfo> {
     libdrm.set_all_client_caps(fd);
     let Some(drm_mode_res) = libdrm.get_drm_mode_resources(fd) else { return Vec::new() };
     let current_connectors = drm_mode_res.get_drm_mode_all_connector_current(fd);
@@ -118,6 +120,12 @@ pub fn connector_info(device_path: &DevicePath) -> Vec<ConnectorInfo> {
             None
         };
 
+        let edid = mode_props
+            .iter()
+            .find(|prop| prop.0.name == "EDID" && prop.1 != 0)
+            .and_then(|prop| drm_ioctl::get_property_blob(fd, prop.1 as u32))
+            .and_then(|blob| Edid::parse(&blob));
+
         Some(ConnectorInfo {
             connector_id,
             connector_type,
@@ -126,6 +134,7 @@ pub fn connector_info(device_path: &DevicePath) -> Vec<ConnectorInfo> {
             mode_info,
             mode_props,
             crtc,
+            edid,
         })
     }).collect();
 
@@ -0,0 +1,391 @@
+// ref: https://github.com/torvalds/linux/blob/master/include/uapi/drm/drm_mode.h
+// ref: https://github.com/torvalds/linux/blob/master/include/uapi/drm/drm_fourcc.h
+// ref: https://gitlab.freedesktop.org/emersion/drm_info
+
+use std::fs::File;
+use std::os::fd::{AsRawFd, RawFd};
+use crate::DevicePath;
+use crate::drm_ioctl::{
+    self,
+    DRM_CLIENT_CAP_UNIVERSAL_PLANES,
+    DRM_MODE_FB_MODIFIERS,
+    DRM_MODE_OBJECT_PLANE,
+};
+
+const DRM_FORMAT_MOD_INVALID: u64 = 0x00FF_FFFF_FFFF_FFFF;
+const DRM_FORMAT_MOD_LINEAR: u64 = 0;
+const DRM_FORMAT_MOD_VENDOR_AMD: u64 = 0x02;
+
+pub fn fourcc_string(fourcc: u32) -> String {
+    fourcc
+        .to_le_bytes()
+        .iter()
+        .map(|b| if b.is_ascii_graphic() || *b == b' ' { *b as char } else { '?' })
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+pub fn modifier_string(modifier: u64) -> String {
+    match modifier {
+        DRM_FORMAT_MOD_INVALID => return "INVALID".to_string(),
+        DRM_FORMAT_MOD_LINEAR => return "LINEAR".to_string(),
+        _ => {},
+    }
+
+    let vendor = modifier >> 56;
+
+    if vendor != DRM_FORMAT_MOD_VENDOR_AMD {
+        let vendor_name = match vendor {
+            0x01 => "INTEL",
+            0x03 => "NVIDIA",
+            0x04 => "SAMSUNG",
+            0x05 => "QCOM",
+            0x06 => "VIVANTE",
+            0x07 => "BROADCOM",
+            0x08 => "ARM",
+            0x09 => "ALLWINNER",
+            0x0A => "AMLOGIC",
+            0x0B => "MTK",
+            0x0C => "APPLE",
+            _ => "UNKNOWN",
+        };
+
+        return format!("{vendor_name}({modifier:#018X})");
+    }
+
+    // AMD_FMT_MOD_*
+    let field = |shift: u64, mask: u64| (modifier >> shift) & mask;
+    let tile_version = match field(0, 0xFF) {
+        1 => "GFX9",
+        2 => "GFX10",
+        3 => "GFX10_RBPLUS",
+        4 => "GFX11",
+        5 => "GFX12",
+        _ => "UNKNOWN",
+    };
+    let tile = field(8, 0x1F);
+    let tile_name = if tile_version == "GFX12" {
+        match tile {
+            1 => "256B_2D",
+            2 => "4K_2D",
+            3 => "64K_2D",
+            4 => "256K_2D",
+            _ => "UNKNOWN",
+        }
+    } else {
+        match tile {
+            9 => "64K_S",
+            10 => "64K_D",
+            25 => "64K_S_X",
+            26 => "64K_D_X",
+            27 => "64K_R_X",
+            31 => "256K_R_X",
+            _ => "UNKNOWN",
+        }
+    };
+    let mut s = format!("AMD({tile_version}, {tile_name}");
+
+    for (name, bit) in [
+        ("DCC", 13),
+        ("DCC_RETILE", 14),
+        ("DCC_PIPE_ALIGN", 15),
+        ("DCC_INDEPENDENT_64B", 16),
+        ("DCC_INDEPENDENT_128B", 17),
+        ("DCC_CONSTANT_ENCODE", 20),
+    ] {
+        if field(bit, 0b1) == 1 {
+            s.push_str(", ");
+            s.push_str(name);
+        }
+    }
+
+    s.push(')');
+
+    s
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrmPlaneType {
+    Overlay,
+    Primary,
+    Cursor,
+}
+
+impl std::fmt::Display for DrmPlaneType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DrmCrtcMode {
+    pub name: String,
+    pub hdisplay: u16,
+    pub vdisplay: u16,
+    pub refresh_rate: f64, // Hz
+}
+
+#[derive(Debug, Clone)]
+pub struct DrmCrtc {
+    pub index: usize,
+    pub crtc_id: u32,
+    pub fb_id: u32,
+    pub position: (u32, u32),
+    pub gamma_size: u32,
+    pub mode: Option<DrmCrtcMode>,
+}
+
+impl DrmCrtc {
+    pub fn is_active(&self) -> bool {
+        self.mode.is_some()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DrmEncoder {
+    pub encoder_id: u32,
+    pub encoder_type: u32,
+    pub crtc_id: u32,
+    pub possible_crtcs: u32, // bitmask of the CRTC index
+}
+
+impl DrmEncoder {
+    pub fn type_name(&self) -> &'static str {
+        match self.encoder_type {
+            0 => "NONE",
+            1 => "DAC",
+            2 => "TMDS",
+            3 => "LVDS",
+            4 => "TVDAC",
+            5 => "VIRTUAL",
+            6 => "DSI",
+            7 => "DPMST",
+            8 => "DPI",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DrmFramebuffer {
+    pub fb_id: u32,
+    pub width: u32,
+    pub height: u32,
+    pub pixel_format: u32, // fourcc
+    pub modifier: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DrmPlane {
+    pub plane_id: u32,
+    pub plane_type: Option<DrmPlaneType>,
+    pub crtc_id: u32,
+    pub possible_crtcs: u32, // bitmask of the CRTC index
+    pub formats: Vec<u32>, // fourcc
+    pub in_formats: Vec<(u64, Vec<u32>)>, // (modifier, formats)
+    pub fb: Option<DrmFramebuffer>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DrmModeObjects {
+    pub min_size: (u32, u32),
+    pub max_size: (u32, u32),
+    pub crtcs: Vec<DrmCrtc>,
+    pub encoders: Vec<DrmEncoder>,
+    pub planes: Vec<DrmPlane>,
+}
+
+// struct drm_format_modifier_blob
+fn parse_in_formats(blob: &[u8]) -> Vec<(u64, Vec<u32>)> {
+    let u32_at = |pos: usize| -> Option<u32> {
+        Some(u32::from_ne_bytes(blob.get(pos..pos+4)?.try_into().ok()?))
+    };
+    let u64_at = |pos: usize| -> Option<u64> {
+        Some(u64::from_ne_bytes(blob.get(pos..pos+8)?.try_into().ok()?))
+    };
+    let [Some(count_formats), Some(formats_offset), Some(count_modifiers), Some(modifiers_offset)] =
+        [8, 12, 16, 20].map(u32_at) else { return Vec::new() };
+
+    let formats: Vec<u32> = (0..count_formats as usize)
+        .filter_map(|i| u32_at(formats_offset as usize + i * 4))
+        .collect();
+
+    // struct drm_format_modifier { __u64 formats; __u32 offset; __u32 pad; __u64 modifier; }
+    (0..count_modifiers as usize).filter_map(|i| {
+        let pos = modifiers_offset as usize + i * 24;
+        let mask = u64_at(pos)?;
+        let offset = u32_at(pos + 8)? as usize;
+        let modifier = u64_at(pos + 16)?;
+        let formats = (0..64)
+            .filter(|bit| (mask >> bit) & 0b1 == 1)
+            .filter_map(|bit| formats.get(offset + bit).copied())
+            .collect();
+
+        Some((modifier, formats))
+    }).collect()
+}
+
+fn get_framebuffer(fd: RawFd, fb_id: u32) -> Option<DrmFramebuffer> {
+    if fb_id == 0 { return None }
+
+    let fb = drm_ioctl::get_fb2(fd, fb_id)?;
+
+    Some(DrmFramebuffer {
+        fb_id,
+        width: fb.width,
+        height: fb.height,
+        pixel_format: fb.pixel_format,
+        modifier: (fb.flags & DRM_MODE_FB_MODIFIERS != 0).then_some(fb.modifier[0]),
+    })
+}
+
+fn get_plane(fd: RawFd, plane_id: u32) -> Option<DrmPlane> {
+    let (plane, formats) = drm_ioctl::get_plane(fd, plane_id)?;
+    let mut plane_type = None;
+    let mut in_formats = Vec::new();
+
+    for (prop_id, value) in drm_ioctl::get_object_properties(fd, plane_id, DRM_MODE_OBJECT_PLANE) {
+        match drm_ioctl::get_property_name(fd, prop_id).as_deref() {
+            Some("type") => plane_type = match value {
+                0 => Some(DrmPlaneType::Overlay),
+                1 => Some(DrmPlaneType::Primary),
+                2 => Some(DrmPlaneType::Cursor),
+                _ => None,
+            },
+            Some("IN_FORMATS") => if let Some(blob) = drm_ioctl::get_property_blob(fd, value as u32) {
+                in_formats = parse_in_formats(&blob);
+            },
+            _ => {},
+        }
+    }
+
+    Some(DrmPlane {
+        plane_id,
+        plane_type,
+        crtc_id: plane.crtc_id,
+        possible_crtcs: plane.possible_crtcs,
+        formats,
+        in_formats,
+        fb: get_framebuffer(fd, plane.fb_id),
+    })
+}
+
+pub fn drm_mode_objects(device_path: &DevicePath) -> Option<DrmModeObjects> {
+    let f = File::open(&device_path.card).ok()?;
+    let fd = f.as_raw_fd();
+
+    // to get the primary and cursor planes
+    drm_ioctl::set_client_cap(fd, DRM_CLIENT_CAP_UNIVERSAL_PLANES, 1);
+
+    let res = drm_ioctl::get_resources(fd)?;
+    let crtcs = res.crtcs.iter().enumerate().filter_map(|(index, crtc_id)| {
+        let crtc = drm_ioctl::get_crtc(fd, *crtc_id)?;
+        let m = &crtc.mode;
+        let mode = (crtc.mode_valid != 0).then(|| {
+            let len = m.name.iter().position(|b| *b == 0).unwrap_or(m.name.len());
+
+            DrmCrtcMode {
+                name: String::from_utf8_lossy(&m.name[..len]).to_string(),
+                hdisplay: m.hdisplay,
+                vdisplay: m.vdisplay,
+                refresh_rate: m.refresh_rate(),
+            }
+        });
+
+        Some(DrmCrtc {
+            index,
+            crtc_id: crtc.crtc_id,
+            fb_id: crtc.fb_id,
+            position: (crtc.x, crtc.y),
+            gamma_size: crtc.gamma_size,
+            mode,
+        })
+    }).collect();
+    let encoders = res.encoders.iter().filter_map(|encoder_id| {
+        let encoder = drm_ioctl::get_encoder(fd, *encoder_id)?;
+
+        Some(DrmEncoder {
+            encoder_id: encoder.encoder_id,
+            encoder_type: encoder.encoder_type,
+            crtc_id: encoder.crtc_id,
+            possible_crtcs: encoder.possible_crtcs,
+        })
+    }).collect();
+    let planes = drm_ioctl::get_plane_resources(fd)
+        .into_iter()
+        .filter_map(|plane_id| get_plane(fd, plane_id))
+        .collect();
+
+    Some(DrmModeObjects {
+        min_size: res.min_size,
+        max_size: res.max_size,
+        crtcs,
+        encoders,
+        planes,
+    })
+}
+
+#[test]
+fn test_modifier_string() {
+    const AMD: u64 = DRM_FORMAT_MOD_VENDOR_AMD << 56;
+    const DCC: u64 = 1 << 13;
+    const DCC_INDEPENDENT_64B: u64 = 1 << 16;
+    const DCC_INDEPENDENT_128B: u64 = 1 << 17;
+    const PIPE_XOR_BITS_3: u64 = 3 << 21;
+
+    assert_eq!(modifier_string(DRM_FORMAT_MOD_LINEAR), "LINEAR");
+    assert_eq!(modifier_string(DRM_FORMAT_MOD_INVALID), "INVALID");
+    assert_eq!(modifier_string(0x0100_0000_0000_0001), "INTEL(0x0100000000000001)");
+    assert_eq!(
+        modifier_string(AMD | (25 << 8) | 1 | DCC | DCC_INDEPENDENT_64B | PIPE_XOR_BITS_3),
+        "AMD(GFX9, 64K_S_X, DCC, DCC_INDEPENDENT_64B)",
+    );
+    assert_eq!(
+        modifier_string(AMD | (27 << 8) | 3 | DCC | DCC_INDEPENDENT_64B | DCC_INDEPENDENT_128B | (1 << 18)),
+        "AMD(GFX10_RBPLUS, 64K_R_X, DCC, DCC_INDEPENDENT_64B, DCC_INDEPENDENT_128B)",
+    );
+    assert_eq!(modifier_string(AMD | (10 << 8) | 2), "AMD(GFX10, 64K_D)");
+    assert_eq!(modifier_string(AMD | (4 << 8) | 5 | DCC), "AMD(GFX12, 256K_2D, DCC)");
+    assert_eq!(modifier_string(AMD | (31 << 8) | 5), "AMD(GFX12, UNKNOWN)");
+
+    assert_eq!(fourcc_string(u32::from_le_bytes(*b"XR24")), "XR24");
+    assert_eq!(fourcc_string(u32::from_le_bytes(*b"R8  ")), "R8");
+}
+
+#[test]
+fn test_parse_in_formats() {
+    let formats = [*b"XR24", *b"AR24", *b"XB24", *b"NV12"].map(u32::from_le_bytes);
+    let gfx9_dcc = (DRM_FORMAT_MOD_VENDOR_AMD << 56) | (25 << 8) | 1 | (1 << 13);
+    // (formats bitmask, offset, modifier)
+    let modifiers = [
+        (0b1111, 0, DRM_FORMAT_MOD_LINEAR),
+        (0b0011, 0, gfx9_dcc),
+        (0b0001, 3, DRM_FORMAT_MOD_INVALID),
+    ];
+
+    // struct drm_format_modifier_blob, the modifiers are aligned to 8 bytes
+    let mut blob: Vec<u8> = Vec::new();
+    for v in [1, 0, formats.len() as u32, 24, modifiers.len() as u32, 40] {
+        blob.extend(v.to_ne_bytes());
+    }
+    for f in formats {
+        blob.extend(f.to_ne_bytes());
+    }
+    for (mask, offset, modifier) in modifiers {
+        blob.extend(u64::to_ne_bytes(mask));
+        blob.extend(u32::to_ne_bytes(offset));
+        blob.extend(u32::to_ne_bytes(0));
+        blob.extend(u64::to_ne_bytes(modifier));
+    }
+
+    assert_eq!(parse_in_formats(&blob), [
+        (DRM_FORMAT_MOD_LINEAR, formats.to_vec()),
+        (gfx9_dcc, formats[..2].to_vec()),
+        (DRM_FORMAT_MOD_INVALID, formats[3..].to_vec()),
+    ]);
+
+    // truncated
+    assert_eq!(parse_in_formats(&blob[..blob.len()-1]).len(), 2);
+    assert!(parse_in_formats(&blob[..20]).is_empty());
+}
@@ -0,0 +1,556 @@
+// ref: VESA Enhanced Extended Display Identification Data (E-EDID) Standard, Release A, Revision 2
+// ref: CTA-861-H
+// ref: https://git.linuxtv.org/v4l-utils.git/tree/utils/edid-decode
+
+use std::fmt;
+
+const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+const EDID_BLOCK_SIZE: usize = 128;
+const CTA_EXTENSION_TAG: u8 = 0x02;
+const AMD_OUI: u32 = 0x00001A;
+
+// (VIC, hactive, vactive, refresh rate, interlaced)
+const CTA_VIC_TABLE: &[(u8, u16, u16, f32, bool)] = &[
+    (1, 640, 480, 59.94, false),
+    (2, 720, 480, 59.94, false),
+    (3, 720, 480, 59.94, false),
+    (4, 1280, 720, 60.0, false),
+    (5, 1920, 1080, 60.0, true),
+    (16, 1920, 1080, 60.0, false),
+    (17, 720, 576, 50.0, false),
+    (18, 720, 576, 50.0, false),
+    (19, 1280, 720, 50.0, false),
+    (20, 1920, 1080, 50.0, true),
+    (31, 1920, 1080, 50.0, false),
+    (32, 1920, 1080, 24.0, false),
+    (33, 1920, 1080, 25.0, false),
+    (34, 1920, 1080, 30.0, false),
+    (63, 1920, 1080, 120.0, false),
+    (64, 1920, 1080, 100.0, false),
+    (93, 3840, 2160, 24.0, false),
+    (94, 3840, 2160, 25.0, false),
+    (95, 3840, 2160, 30.0, false),
+    (96, 3840, 2160, 50.0, false),
+    (97, 3840, 2160, 60.0, false),
+    (117, 3840, 2160, 100.0, false),
+    (118, 3840, 2160, 120.0, false),
+];
+
+// (byte, bit, hactive, vactive, refresh rate)
+const ESTABLISHED_TIMINGS: &[(usize, u8, u16, u16, f32)] = &[
+    (0, 7, 720, 400, 70.0),
+    (0, 6, 720, 400, 88.0),
+    (0, 5, 640, 480, 60.0),
+    (0, 4, 640, 480, 67.0),
+    (0, 3, 640, 480, 72.0),
+    (0, 2, 640, 480, 75.0),
+    (0, 1, 800, 600, 56.0),
+    (0, 0, 800, 600, 60.0),
+    (1, 7, 800, 600, 72.0),
+    (1, 6, 800, 600, 75.0),
+    (1, 5, 832, 624, 75.0),
+    (1, 3, 1024, 768, 60.0),
+    (1, 2, 1024, 768, 70.0),
+    (1, 1, 1024, 768, 75.0),
+    (1, 0, 1280, 1024, 75.0),
+    (2, 7, 1152, 870, 75.0),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdidTimingSource {
+    Established,
+    Standard,
+    Detailed,
+    Cta(u8), // VIC
+}
+
+impl fmt::Display for EdidTimingSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Established => write!(f, "Established"),
+            Self::Standard => write!(f, "Standard"),
+            Self::Detailed => write!(f, "Detailed"),
+            Self::Cta(vic) => write!(f, "CTA VIC {vic}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdidTiming {
+    pub hactive: u16,
+    pub vactive: u16,
+    pub refresh_rate: f32, // Hz
+    pub interlaced: bool,
+    pub source: EdidTimingSource,
+}
+
+impl fmt::Display for EdidTiming {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}x{}{}@{:.2} ({})",
+            self.hactive,
+            self.vactive,
+            if self.interlaced { "i" } else { "" },
+            self.refresh_rate,
+            self.source,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdidRangeLimits {
+    pub min_v_rate: u16, // Hz
+    pub max_v_rate: u16, // Hz
+    pub min_h_rate: u16, // kHz
+    pub max_h_rate: u16, // kHz
+    pub max_pixel_clock: u16, // MHz
+    pub timing_support: u8, // 0x01: range limits only
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HdrStaticMetadata {
+    pub eotf: u8,
+    pub static_metadata_type: u8,
+    pub max_luminance: Option<f32>, // cd/m^2
+    pub max_frame_avg_luminance: Option<f32>, // cd/m^2
+    pub min_luminance: Option<f32>, // cd/m^2
+}
+
+impl HdrStaticMetadata {
+    pub fn eotf_list(&self) -> Vec<&'static str> {
+        [
+            "Traditional SDR",
+            "Traditional HDR",
+            "SMPTE ST2084",
+            "HLG",
+        ]
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| (self.eotf >> i) & 0b1 == 1)
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    fn parse(data: &[u8]) -> Option<Self> {
+        let eotf = *data.first()?;
+        let static_metadata_type = *data.get(1)?;
+        // 50 * 2^(cv/32)
+        let max_luminance = data.get(2).filter(|v| **v != 0).map(|v| 50.0 * 2f32.powf(*v as f32 / 32.0));
+        let max_frame_avg_luminance = data.get(3).filter(|v| **v != 0).map(|v| 50.0 * 2f32.powf(*v as f32 / 32.0));
+        let min_luminance = data.get(4).and_then(|v| {
+            let max = max_luminance?;
+
+            Some(max * (*v as f32 / 255.0).powi(2) / 100.0)
+        });
+
+        Some(Self {
+            eotf,
+            static_metadata_type,
+            max_luminance,
+            max_frame_avg_luminance,
+            min_luminance,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreeSyncRange {
+    pub version: (u8, u8),
+    pub min_refresh_rate: u8, // Hz
+    pub max_refresh_rate: u8, // Hz
+}
+
+impl FreeSyncRange {
+    // AMD Vendor-Specific Data Block, the payload after the OUI
+    fn parse(data: &[u8]) -> Option<Self> {
+        let [major, minor, min, max, ..] = data else { return None };
+
+        Some(Self {
+            version: (*major, *minor),
+            min_refresh_rate: *min,
+            max_refresh_rate: *max,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CtaExtension {
+    pub revision: u8,
+    pub underscan: bool,
+    pub basic_audio: bool,
+    pub ycbcr444: bool,
+    pub ycbcr422: bool,
+    pub timings: Vec<EdidTiming>,
+    pub hdr_static_metadata: Option<HdrStaticMetadata>,
+    pub freesync: Option<FreeSyncRange>,
+}
+
+impl CtaExtension {
+    fn parse(block: &[u8]) -> Option<Self> {
+        if block.len() < EDID_BLOCK_SIZE || block[0] != CTA_EXTENSION_TAG { return None }
+
+        let revision = block[1];
+        let dtd_offset = (block[2] as usize).min(EDID_BLOCK_SIZE - 1);
+        let flags = block[3];
+        let mut cta = Self {
+            revision,
+            underscan: (flags >> 7) & 0b1 == 1,
+            basic_audio: (flags >> 6) & 0b1 == 1,
+            ycbcr444: (flags >> 5) & 0b1 == 1,
+            ycbcr422: (flags >> 4) & 0b1 == 1,
+            ..Default::default()
+        };
+
+        // Data Block Collection
+        if dtd_offset > 4 {
+            let mut pos = 4;
+
+            while pos < dtd_offset {
+                let tag = block[pos] >> 5;
+                let len = (block[pos] & 0x1F) as usize;
+                let Some(data) = block.get(pos+1..pos+1+len) else { break };
+
+                cta.parse_data_block(tag, data);
+
+                pos += 1 + len;
+            }
+        }
+
+        if dtd_offset >= 4 {
+            for dtd in block[dtd_offset..EDID_BLOCK_SIZE-1].chunks_exact(18) {
+                let Some(timing) = parse_detailed_timing(dtd) else { break };
+
+                cta.timings.push(timing);
+            }
+        }
+
+        Some(cta)
+    }
+
+    fn parse_data_block(&mut self, tag: u8, data: &[u8]) {
+        match tag {
+            // Video Data Block
+            2 => {
+                for svd in data {
+                    // VIC 1-64 have a native flag in bit 7
+                    let vic = if (129..=192).contains(svd) { svd & 0x7F } else { *svd };
+                    let Some((_, hactive, vactive, refresh_rate, interlaced)) = CTA_VIC_TABLE
+                        .iter()
+                        .find(|v| v.0 == vic)
+                        .copied()
+                    else { continue };
+
+                    self.timings.push(EdidTiming {
+                        hactive,
+                        vactive,
+                        refresh_rate,
+                        interlaced,
+                        source: EdidTimingSource::Cta(vic),
+                    });
+                }
+            },
+            // Vendor-Specific Data Block
+            3 => {
+                let [o0, o1, o2, payload @ ..] = data else { return };
+                let oui = u32::from_le_bytes([*o0, *o1, *o2, 0]);
+
+                if oui == AMD_OUI {
+                    self.freesync = FreeSyncRange::parse(payload);
+                }
+            },
+            // Extended Tag
+            7 => {
+                let [ext_tag, payload @ ..] = data else { return };
+
+                // HDR Static Metadata Data Block
+                if *ext_tag == 6 {
+                    self.hdr_static_metadata = HdrStaticMetadata::parse(payload);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edid {
+    pub manufacturer: String, // PNP ID
+    pub product_code: u16,
+    pub serial_number: u32,
+    pub serial_string: Option<String>,
+    pub monitor_name: Option<String>,
+    pub week: u8,
+    pub year: u16,
+    pub version: (u8, u8),
+    pub digital: bool,
+    pub physical_size: Option<(u8, u8)>, // cm
+    pub continuous_frequency: bool,
+    pub timings: Vec<EdidTiming>,
+    pub range_limits: Option<EdidRangeLimits>,
+    pub extension_count: u8,
+    pub cta: Vec<CtaExtension>,
+    pub checksum_ok: bool,
+}
+
+fn checksum_ok(block: &[u8]) -> bool {
+    block.iter().fold(0u8, |sum, v| sum.wrapping_add(*v)) == 0
+}
+
+fn parse_manufacturer(v: u16) -> String {
+    [10, 5, 0]
+        .iter()
+        .map(|shift| (((v >> shift) & 0x1F) as u8 + b'@') as char)
+        .collect()
+}
+
+fn parse_descriptor_string(data: &[u8]) -> String {
+    let len = data.iter().position(|b| *b == b'\n').unwrap_or(data.len());
+
+    String::from_utf8_lossy(&data[..len]).trim_end().to_string()
+}
+
+fn parse_detailed_timing(dtd: &[u8]) -> Option<EdidTiming> {
+    let pixel_clock = u16::from_le_bytes([dtd[0], dtd[1]]) as f32 * 10_000.0; // Hz
+
+    if pixel_clock == 0.0 { return None }
+
+    let hactive = dtd[2] as u16 | ((dtd[4] as u16 >> 4) << 8);
+    let hblank = dtd[3] as u16 | ((dtd[4] as u16 & 0xF) << 8);
+    let vactive = dtd[5] as u16 | ((dtd[7] as u16 >> 4) << 8);
+    let vblank = dtd[6] as u16 | ((dtd[7] as u16 & 0xF) << 8);
+    let interlaced = (dtd[17] >> 7) & 0b1 == 1;
+    let total = (hactive + hblank) as f32 * (vactive + vblank) as f32;
+
+    if total == 0.0 { return None }
+
+    Some(EdidTiming {
+        hactive,
+        vactive: if interlaced { vactive * 2 } else { vactive },
+        refresh_rate: pixel_clock / total,
+        interlaced,
+        source: EdidTimingSource::Detailed,
+    })
+}
+
+impl Edid {
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        if buf.len() < EDID_BLOCK_SIZE || buf[..8] != EDID_HEADER { return None }
+
+        let base = &buf[..EDID_BLOCK_SIZE];
+        let version = (base[18], base[19]);
+        let digital = (base[20] >> 7) & 0b1 == 1;
+        let physical_size = if base[21] != 0 && base[22] != 0 {
+            Some((base[21], base[22]))
+        } else {
+            None
+        };
+        let mut edid = Self {
+            manufacturer: parse_manufacturer(u16::from_be_bytes([base[8], base[9]])),
+            product_code: u16::from_le_bytes([base[10], base[11]]),
+            serial_number: u32::from_le_bytes([base[12], base[13], base[14], base[15]]),
+            serial_string: None,
+            monitor_name: None,
+            week: base[16],
+            year: base[17] as u16 + 1990,
+            version,
+            digital,
+            physical_size,
+            continuous_frequency: base[24] & 0b1 == 1,
+            timings: Vec::new(),
+            range_limits: None,
+            extension_count: base[126],
+            cta: Vec::new(),
+            checksum_ok: checksum_ok(base),
+        };
+
+        for (byte, bit, hactive, vactive, refresh_rate) in ESTABLISHED_TIMINGS {
+            if (base[35 + byte] >> bit) & 0b1 == 1 {
+                edid.timings.push(EdidTiming {
+                    hactive: *hactive,
+                    vactive: *vactive,
+                    refresh_rate: *refresh_rate,
+                    interlaced: false,
+                    source: EdidTimingSource::Established,
+                });
+            }
+        }
+
+        for st in base[38..54].chunks_exact(2) {
+            if st == [0x01, 0x01] || st[0] == 0 { continue }
+
+            let hactive = (st[0] as u16 + 31) * 8;
+            let vactive = match st[1] >> 6 {
+                // 1:1 before EDID 1.3
+                0 if version < (1, 3) => hactive,
+                0 => hactive * 10 / 16,
+                1 => hactive * 3 / 4,
+                2 => hactive * 4 / 5,
+                _ => hactive * 9 / 16,
+            };
+
+            edid.timings.push(EdidTiming {
+                hactive,
+                vactive,
+                refresh_rate: (st[1] & 0x3F) as f32 + 60.0,
+                interlaced: false,
+                source: EdidTimingSource::Standard,
+            });
+        }
+
+        for desc in base[54..126].chunks_exact(18) {
+            if let Some(timing) = parse_detailed_timing(desc) {
+                edid.timings.push(timing);
+                continue;
+            }
+
+            edid.parse_display_descriptor(desc);
+        }
+
+        for block in buf[EDID_BLOCK_SIZE..].chunks_exact(EDID_BLOCK_SIZE) {
+            if let Some(cta) = CtaExtension::parse(block) {
+                edid.cta.push(cta);
+            }
+        }
+
+        Some(edid)
+    }
+
+    fn parse_display_descriptor(&mut self, desc: &[u8]) {
+        let data = &desc[5..18];
+
+        match desc[3] {
+            0xFF => self.serial_string = Some(parse_descriptor_string(data)),
+            0xFC => self.monitor_name = Some(parse_descriptor_string(data)),
+            0xFD => {
+                let offsets = desc[4];
+                let offset = |bit: u8| if (offsets >> bit) & 0b1 == 1 { 255 } else { 0 };
+
+                self.range_limits = Some(EdidRangeLimits {
+                    min_v_rate: data[0] as u16 + if offsets & 0b11 == 0b11 { 255 } else { 0 },
+                    max_v_rate: data[1] as u16 + offset(1),
+                    min_h_rate: data[2] as u16 + if offsets & 0b1100 == 0b1100 { 255 } else { 0 },
+                    max_h_rate: data[3] as u16 + offset(3),
+                    max_pixel_clock: data[4] as u16 * 10,
+                    timing_support: data[5],
+                });
+            },
+            _ => {},
+        }
+    }
+
+    pub fn hdr_static_metadata(&self) -> Option<&HdrStaticMetadata> {
+        self.cta.iter().find_map(|cta| cta.hdr_static_metadata.as_ref())
+    }
+
+    pub fn freesync(&self) -> Option<&FreeSyncRange> {
+        self.cta.iter().find_map(|cta| cta.freesync.as_ref())
+    }
+
+    // the AMD VSDB, or the range limits for DisplayPort Adaptive-Sync
+    // ref: amdgpu_dm_update_freesync_caps
+    pub fn vrr_range(&self) -> Option<(u16, u16)> {
+        if let Some(fs) = self.freesync() {
+            return Some((fs.min_refresh_rate as u16, fs.max_refresh_rate as u16));
+        }
+
+        if !self.continuous_frequency { return None }
+
+        self.range_limits
+            .filter(|r| r.timing_support == 0x01 && r.min_v_rate + 10 < r.max_v_rate)
+            .map(|r| (r.min_v_rate, r.max_v_rate))
+    }
+
+    pub fn all_timings(&self) -> impl Iterator<Item = &EdidTiming> {
+        self.timings.iter().chain(self.cta.iter().flat_map(|cta| &cta.timings))
+    }
+
+    pub fn name(&self) -> String {
+        match &self.monitor_name {
+            Some(name) => format!("{name} ({} {:#06X})", self.manufacturer, self.product_code),
+            None => format!("{} {:#06X}", self.manufacturer, self.product_code),
+        }
+    }
+}
+
+#[test]
+fn test_edid_parse_dp_hdr_freesync() {
+    let buf = std::fs::read("src/edid/edid_sample_dp.bin").unwrap();
+    let edid = Edid::parse(&buf).unwrap();
+
+    assert!(edid.checksum_ok);
+    assert_eq!(edid.manufacturer, "AMD");
+    assert_eq!(edid.product_code, 0x1234);
+    assert_eq!(edid.serial_number, 0x01020304);
+    assert_eq!(edid.serial_string.as_deref(), Some("SN0001"));
+    assert_eq!(edid.monitor_name.as_deref(), Some("AGT Monitor"));
+    assert_eq!((edid.week, edid.year), (10, 2023));
+    assert_eq!(edid.version, (1, 4));
+    assert!(edid.digital);
+    assert_eq!(edid.physical_size, Some((60, 34)));
+    assert_eq!(edid.extension_count, 1);
+
+    let timings: Vec<(u16, u16, EdidTimingSource)> = edid.all_timings()
+        .map(|t| (t.hactive, t.vactive, t.source))
+        .collect();
+
+    assert!(timings.contains(&(640, 480, EdidTimingSource::Established)));
+    assert!(timings.contains(&(1920, 1080, EdidTimingSource::Standard)));
+    assert!(timings.contains(&(2560, 1440, EdidTimingSource::Detailed)));
+    assert!(timings.contains(&(3840, 2160, EdidTimingSource::Cta(97))));
+
+    let preferred = edid.timings.iter().find(|t| t.source == EdidTimingSource::Detailed).unwrap();
+    assert!((preferred.refresh_rate - 143.97).abs() < 0.01);
+
+    assert_eq!(
+        edid.range_limits,
+        Some(EdidRangeLimits {
+            min_v_rate: 48,
+            max_v_rate: 144,
+            min_h_rate: 30,
+            max_h_rate: 230,
+            max_pixel_clock: 600,
+            timing_support: 0x01,
+        }),
+    );
+
+    let hdr = edid.hdr_static_metadata().unwrap();
+    assert_eq!(hdr.eotf_list(), ["Traditional SDR", "SMPTE ST2084"]);
+    assert!((hdr.max_luminance.unwrap() - 603.67).abs() < 0.01);
+    assert!((hdr.min_luminance.unwrap() - 0.0059).abs() < 0.0001);
+
+    let fs = edid.freesync().unwrap();
+    assert_eq!(fs.version, (2, 0));
+    assert_eq!((fs.min_refresh_rate, fs.max_refresh_rate), (48, 144));
+    assert_eq!(edid.vrr_range(), Some((48, 144)));
+}
+
+#[test]
+fn test_edid_parse_no_extension() {
+    let buf = std::fs::read("src/edid/edid_sample_legacy.bin").unwrap();
+    let edid = Edid::parse(&buf).unwrap();
+
+    assert!(edid.checksum_ok);
+    assert_eq!(edid.manufacturer, "SAM");
+    assert_eq!(edid.version, (1, 3));
+    assert_eq!(edid.physical_size, Some((47, 30)));
+    assert_eq!(edid.monitor_name.as_deref(), Some("Legacy LCD"));
+    assert!(edid.serial_string.is_none());
+    assert!(edid.cta.is_empty());
+    assert!(edid.hdr_static_metadata().is_none());
+    assert!(edid.freesync().is_none());
+
+    let preferred = edid.timings.iter().find(|t| t.source == EdidTimingSource::Detailed).unwrap();
+    assert_eq!((preferred.hactive, preferred.vactive), (1680, 1050));
+    assert!((preferred.refresh_rate - 59.88).abs() < 0.01);
+
+    // 56-75 Hz, GTF
+    assert_eq!(edid.range_limits.map(|r| (r.min_v_rate, r.max_v_rate)), Some((56, 75)));
+    assert_eq!(edid.vrr_range(), None);
+}
+
+#[test]
+fn test_edid_parse_invalid() {
+    assert!(Edid::parse(&[0u8; 128]).is_none());
+    assert!(Edid::parse(&EDID_HEADER).is_none());
+}
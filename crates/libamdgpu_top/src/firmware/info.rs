@@ -0,0 +1,108 @@
+use crate::AMDGPU::{
+    DeviceHandle,
+    FW_VERSION::FW_TYPE,
+};
+use super::FirmwareKind;
+
+const FW_LIST: &[FW_TYPE] = &[
+    FW_TYPE::VCE,
+    FW_TYPE::UVD,
+    FW_TYPE::GMC,
+    FW_TYPE::GFX_ME,
+    FW_TYPE::GFX_PFP,
+    FW_TYPE::GFX_CE,
+    FW_TYPE::GFX_RLC,
+    FW_TYPE::GFX_MEC,
+    FW_TYPE::SMC,
+    FW_TYPE::SDMA,
+    FW_TYPE::SOS,
+    FW_TYPE::ASD,
+    FW_TYPE::VCN,
+    FW_TYPE::GFX_RLC_RESTORE_LIST_CNTL,
+    FW_TYPE::GFX_RLC_RESTORE_LIST_GPM_MEM,
+    FW_TYPE::GFX_RLC_RESTORE_LIST_SRM_MEM,
+    FW_TYPE::DMCU,
+    FW_TYPE::TA,
+    FW_TYPE::DMCUB,
+    FW_TYPE::TOC,
+];
+
+#[derive(Debug, Clone)]
+pub struct FirmwareVersion {
+    pub name: String,
+    pub fw_type: FW_TYPE,
+    pub index: u32,
+    pub version: u32,
+    pub feature: u32,
+}
+
+impl FirmwareVersion {
+    pub fn kind(&self) -> Option<FirmwareKind> {
+        let kind = match self.fw_type {
+            FW_TYPE::VCE => FirmwareKind::Vce,
+            FW_TYPE::UVD => FirmwareKind::Uvd,
+            FW_TYPE::GFX_ME => FirmwareKind::Me,
+            FW_TYPE::GFX_PFP => FirmwareKind::Pfp,
+            FW_TYPE::GFX_CE => FirmwareKind::Ce,
+            FW_TYPE::GFX_RLC => FirmwareKind::Rlc,
+            FW_TYPE::GFX_MEC if self.index == 1 => FirmwareKind::Mec2,
+            FW_TYPE::GFX_MEC => FirmwareKind::Mec,
+            FW_TYPE::SMC => FirmwareKind::Smc,
+            FW_TYPE::SDMA => FirmwareKind::Sdma,
+            FW_TYPE::SOS => FirmwareKind::Sos,
+            FW_TYPE::ASD => FirmwareKind::Asd,
+            FW_TYPE::VCN => FirmwareKind::Vcn,
+            FW_TYPE::DMCU => FirmwareKind::Dmcu,
+            FW_TYPE::TA => FirmwareKind::Ta,
+            FW_TYPE::DMCUB => FirmwareKind::Dmcub,
+            FW_TYPE::TOC => FirmwareKind::Toc,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FirmwareInfo {
+    pub list: Vec<FirmwareVersion>,
+}
+
+impl FirmwareInfo {
+    pub fn get(amdgpu_dev: &DeviceHandle) -> Self {
+        let mut list = Vec::new();
+
+        for fw_type in FW_LIST {
+            // MEC2 is queried with index 1, SDMA with the instance number as index
+            let max_index = match fw_type {
+                FW_TYPE::GFX_MEC => 2,
+                FW_TYPE::SDMA => 16,
+                _ => 1,
+            };
+
+            // AMDGPU_INFO_FW_VERSION returns -EINVAL for ip_instance != 0,
+            // the kernel only reports the first instance (e.g. VCN0)
+            for index in 0..max_index {
+                let Ok(fw) = amdgpu_dev.query_firmware_version(*fw_type, 0, index) else { break };
+
+                if fw.version == 0 { continue }
+
+                let name = match (fw_type, index) {
+                    (FW_TYPE::GFX_MEC, 1) => "GFX_MEC2".to_string(),
+                    (FW_TYPE::SDMA, i) if i != 0 => format!("SDMA{i}"),
+                    _ => fw_type.to_string(),
+                };
+
+                list.push(FirmwareVersion {
+                    name,
+                    fw_type: *fw_type,
+                    index,
+                    version: fw.version,
+                    feature: fw.feature,
+                });
+            }
+        }
+
+        Self { list }
+    }
+}
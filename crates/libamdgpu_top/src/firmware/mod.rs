@@ -0,0 +1,375 @@
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/amdgpu/amdgpu_ucode.h
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/amdgpu/amdgpu_ucode.c
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+mod info;
+pub use info::*;
+
+pub const LINUX_FIRMWARE_DIR: &str = "/lib/firmware/amdgpu";
+
+// struct common_firmware_header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FirmwareHeader {
+    pub size_bytes: u32,
+    pub header_version: (u16, u16),
+    pub ip_version: (u16, u16),
+    pub ucode_version: u32,
+    pub ucode_size_bytes: u32,
+}
+
+impl FirmwareHeader {
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let u16_at = |pos: usize| Some(u16::from_le_bytes(bytes.get(pos..pos+2)?.try_into().ok()?));
+        let u32_at = |pos: usize| Some(u32::from_le_bytes(bytes.get(pos..pos+4)?.try_into().ok()?));
+
+        let header = Self {
+            size_bytes: u32_at(0)?,
+            header_version: (u16_at(8)?, u16_at(10)?),
+            ip_version: (u16_at(12)?, u16_at(14)?),
+            ucode_version: u32_at(16)?,
+            ucode_size_bytes: u32_at(20)?,
+        };
+        let header_size = u32_at(4)?;
+
+        // not an amdgpu firmware blob
+        if header_size < 32 || header.size_bytes as usize != bytes.len() {
+            return None;
+        }
+
+        Some(header)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirmwareKind {
+    Me,
+    Pfp,
+    Ce,
+    Rlc,
+    Mec,
+    Mec2,
+    Smc,
+    Sdma,
+    Sos,
+    Asd,
+    Ta,
+    Toc,
+    Uvd,
+    Vce,
+    Vcn,
+    Dmcu,
+    Dmcub,
+}
+
+impl std::fmt::Display for FirmwareKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Self::Me => "GFX_ME",
+            Self::Pfp => "GFX_PFP",
+            Self::Ce => "GFX_CE",
+            Self::Rlc => "GFX_RLC",
+            Self::Mec => "GFX_MEC",
+            Self::Mec2 => "GFX_MEC2",
+            Self::Smc => "SMC",
+            Self::Sdma => "SDMA",
+            Self::Sos => "SOS",
+            Self::Asd => "ASD",
+            Self::Ta => "TA",
+            Self::Toc => "TOC",
+            Self::Uvd => "UVD",
+            Self::Vce => "VCE",
+            Self::Vcn => "VCN",
+            Self::Dmcu => "DMCU",
+            Self::Dmcub => "DMCUB",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+impl FirmwareKind {
+    // the version in the header of TA/ASD/TOC blobs is not the one reported by the kernel
+    pub fn comparable(&self) -> bool {
+        !matches!(self, Self::Asd | Self::Ta | Self::Toc)
+    }
+}
+
+type IpVersion = (u32, u32, u32);
+
+#[derive(Debug, Clone, Default)]
+pub struct FirmwareIpVersions {
+    pub gc: Option<IpVersion>,
+    pub sdma: Option<IpVersion>,
+    pub mp0: Option<IpVersion>,
+    pub mp1: Option<IpVersion>,
+    pub vcn: Option<IpVersion>,
+    pub dcn: Option<IpVersion>,
+    /// lowercase chip name (e.g. "polaris10"), for GPUs without the IP discovery table
+    pub chip: Option<String>,
+}
+
+impl FirmwareIpVersions {
+    /// from the pairs of the HW IP name in the IP discovery table and its version
+    pub fn from_ip_list<'a, I: IntoIterator<Item = (&'a str, IpVersion)>>(list: I) -> Self {
+        let mut s = Self::default();
+
+        for (name, ver) in list {
+            let dst = match name {
+                "GC" => &mut s.gc,
+                "SDMA0" => &mut s.sdma,
+                "MP0" => &mut s.mp0,
+                "MP1" => &mut s.mp1,
+                "UVD" | "VCN" => &mut s.vcn,
+                "DMU" => &mut s.dcn,
+                _ => continue,
+            };
+
+            dst.get_or_insert(ver);
+        }
+
+        s
+    }
+
+    /// `CHIP_VEGA10` -> `vega10`
+    pub fn with_chip_name(mut self, asic_name: &str) -> Self {
+        let name = asic_name.strip_prefix("CHIP_").unwrap_or(asic_name).to_lowercase();
+
+        self.chip = Some(name).filter(|s| !s.is_empty());
+        self
+    }
+
+    // the chip name used for firmware before the IP-versioned names
+    // ref: amdgpu_ucode_ip_version_decode
+    fn legacy_chip_names(&self) -> Vec<&str> {
+        let names: &[&str] = match self.gc {
+            Some((9, 0, 1)) => &["vega10"],
+            Some((9, 2, 1)) => &["vega12"],
+            Some((9, 4, 0)) => &["vega20"],
+            Some((9, 1, 0)) => &["raven", "picasso"],
+            Some((9, 2, 2)) => &["raven2"],
+            Some((9, 4, 1)) => &["arcturus"],
+            Some((9, 3, 0)) => &["renoir", "green_sardine"],
+            Some((9, 4, 2)) => &["aldebaran"],
+            Some((10, 1, 10)) => &["navi10"],
+            Some((10, 1, 1)) => &["navi14"],
+            Some((10, 1, 2)) => &["navi12"],
+            Some((10, 1, 3)) => &["cyan_skillfish2"],
+            Some((10, 3, 0)) => &["sienna_cichlid"],
+            Some((10, 3, 1)) => &["vangogh"],
+            Some((10, 3, 2)) => &["navy_flounder"],
+            Some((10, 3, 3)) => &["yellow_carp"],
+            Some((10, 3, 4)) => &["dimgrey_cavefish"],
+            Some((10, 3, 5)) => &["beige_goby"],
+            _ => &[],
+        };
+
+        if !names.is_empty() || self.gc.is_some() {
+            return names.to_vec();
+        }
+
+        // pre-IP discovery GPUs (Polaris, Vega, Raven, ...)
+        match self.chip.as_deref() {
+            Some("raven") => vec!["raven", "picasso"],
+            Some("renoir") => vec!["renoir", "green_sardine"],
+            chip => chip.into_iter().collect(),
+        }
+    }
+
+    /// Candidate file names (without the extension) of the firmware blob
+    pub fn blob_names(&self, kind: FirmwareKind) -> Vec<String> {
+        let versioned = |prefix: &str, ver: Option<IpVersion>, suffix: &str| {
+            ver.map(|(major, minor, rev)| format!("{prefix}_{major}_{minor}_{rev}{suffix}"))
+        };
+        let (legacy_suffix, new) = match kind {
+            FirmwareKind::Me => ("_me", versioned("gc", self.gc, "_me")),
+            FirmwareKind::Pfp => ("_pfp", versioned("gc", self.gc, "_pfp")),
+            FirmwareKind::Ce => ("_ce", versioned("gc", self.gc, "_ce")),
+            FirmwareKind::Rlc => ("_rlc", versioned("gc", self.gc, "_rlc")),
+            FirmwareKind::Mec => ("_mec", versioned("gc", self.gc, "_mec")),
+            FirmwareKind::Mec2 => ("_mec2", versioned("gc", self.gc, "_mec2")),
+            FirmwareKind::Smc => ("_smc", versioned("smu", self.mp1, "")),
+            FirmwareKind::Sdma => ("_sdma", versioned("sdma", self.sdma, "")),
+            FirmwareKind::Sos => ("_sos", versioned("psp", self.mp0, "_sos")),
+            FirmwareKind::Asd => ("_asd", versioned("psp", self.mp0, "_asd")),
+            FirmwareKind::Ta => ("_ta", versioned("psp", self.mp0, "_ta")),
+            FirmwareKind::Toc => ("_toc", versioned("psp", self.mp0, "_toc")),
+            FirmwareKind::Uvd => ("_uvd", None),
+            FirmwareKind::Vce => ("_vce", None),
+            FirmwareKind::Vcn => ("_vcn", versioned("vcn", self.vcn, "")),
+            FirmwareKind::Dmcu => ("_dmcu", None),
+            FirmwareKind::Dmcub => ("_dmcub", versioned("dcn", self.dcn, "_dmcub")),
+        };
+
+        self.legacy_chip_names()
+            .iter()
+            .map(|chip| format!("{chip}{legacy_suffix}"))
+            .chain(new)
+            .collect()
+    }
+}
+
+pub fn find_blob(dir: &Path, names: &[String]) -> Option<PathBuf> {
+    names.iter().find_map(|name| {
+        ["bin", "bin.zst", "bin.xz"]
+            .iter()
+            .map(|ext| dir.join(format!("{name}.{ext}")))
+            .find(|path| path.is_file())
+    })
+}
+
+/// Read a firmware blob, `.zst` and `.xz` are decompressed with the `zstd`/`xz` command
+pub fn read_blob(path: &Path) -> io::Result<Vec<u8>> {
+    let cmd = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("zst") => "zstd",
+        Some("xz") => "xz",
+        _ => return std::fs::read(path),
+    };
+    let mut child = Command::new(cmd)
+        .args(["-dc", "--"])
+        .arg(path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => io::Error::new(err.kind(), format!("decompressor not found: `{cmd}` is required to read {path:?}")),
+            _ => io::Error::new(err.kind(), format!("{cmd}: {err}")),
+        })?;
+    let mut buf = Vec::new();
+
+    child.stdout.take().unwrap().read_to_end(&mut buf)?;
+
+    if !child.wait()?.success() {
+        return Err(io::Error::other(format!("{cmd}: failed to decompress {path:?}")));
+    }
+
+    Ok(buf)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FirmwareStatus {
+    UpToDate,
+    /// The installed blob is newer, a reboot (and an initramfs rebuild) is needed to load it
+    Outdated,
+    /// The loaded firmware is newer than the installed blob
+    Newer,
+    Missing,
+    /// No candidate blob name, the IP versions and the chip name are unknown
+    Unknown,
+    Unreadable(String),
+    NotComparable,
+}
+
+impl std::fmt::Display for FirmwareStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UpToDate => write!(f, "up to date"),
+            Self::Outdated => write!(f, "outdated, reboot or rebuild the initramfs"),
+            Self::Newer => write!(f, "loaded firmware is newer than the installed blob"),
+            Self::Missing => write!(f, "missing blob"),
+            Self::Unknown => write!(f, "unknown blob name"),
+            Self::Unreadable(err) => write!(f, "unreadable blob ({err})"),
+            Self::NotComparable => write!(f, "installed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FirmwareCheck {
+    pub kind: FirmwareKind,
+    pub loaded_version: u32,
+    pub blob: Option<PathBuf>,
+    pub installed: Option<FirmwareHeader>,
+    pub status: FirmwareStatus,
+}
+
+impl FirmwareCheck {
+    pub fn check(dir: &Path, ip: &FirmwareIpVersions, kind: FirmwareKind, loaded_version: u32) -> Self {
+        let names = ip.blob_names(kind);
+        let blob = find_blob(dir, &names);
+        let (installed, status) = match &blob {
+            None if names.is_empty() => (None, FirmwareStatus::Unknown),
+            None => (None, FirmwareStatus::Missing),
+            Some(path) => match read_blob(path) {
+                Err(err) => (None, FirmwareStatus::Unreadable(err.to_string())),
+                Ok(bytes) => match FirmwareHeader::parse(&bytes) {
+                    None => (None, FirmwareStatus::Unreadable("invalid header".to_string())),
+                    Some(h) => {
+                        let status = if !kind.comparable() {
+                            FirmwareStatus::NotComparable
+                        } else {
+                            match h.ucode_version.cmp(&loaded_version) {
+                                std::cmp::Ordering::Equal => FirmwareStatus::UpToDate,
+                                std::cmp::Ordering::Greater => FirmwareStatus::Outdated,
+                                std::cmp::Ordering::Less => FirmwareStatus::Newer,
+                            }
+                        };
+
+                        (Some(h), status)
+                    },
+                },
+            },
+        };
+
+        Self { kind, loaded_version, blob, installed, status }
+    }
+}
+
+#[test]
+fn test_parse_header() {
+    let bytes = std::fs::read("src/firmware/test_fw/gc_11_0_0_me.bin").unwrap();
+    let h = FirmwareHeader::parse(&bytes).unwrap();
+
+    assert_eq!(h.header_version, (2, 0));
+    assert_eq!(h.ip_version, (11, 0));
+    assert_eq!(h.ucode_version, 0x5B0);
+    assert_eq!(FirmwareHeader::parse(&bytes[..40]), None);
+}
+
+#[test]
+fn test_blob_names() {
+    let navi10 = FirmwareIpVersions::from_ip_list([("GC", (10, 1, 10)), ("MP1", (11, 0, 0))]);
+    let navi31 = FirmwareIpVersions::from_ip_list([("GC", (11, 0, 0)), ("MP0", (13, 0, 0)), ("VCN", (4, 0, 0))]);
+
+    assert_eq!(navi10.blob_names(FirmwareKind::Smc), ["navi10_smc", "smu_11_0_0"]);
+    assert_eq!(navi31.blob_names(FirmwareKind::Sos), ["psp_13_0_0_sos"]);
+    assert_eq!(navi31.blob_names(FirmwareKind::Vcn), ["vcn_4_0_0"]);
+    assert!(navi31.blob_names(FirmwareKind::Dmcub).is_empty());
+
+    // without the IP discovery table
+    let vega10 = FirmwareIpVersions::default().with_chip_name("CHIP_VEGA10");
+    let raven = FirmwareIpVersions::default().with_chip_name("CHIP_RAVEN");
+    let polaris10 = FirmwareIpVersions::default().with_chip_name("CHIP_POLARIS10");
+
+    assert_eq!(vega10.blob_names(FirmwareKind::Sos), ["vega10_sos"]);
+    assert_eq!(raven.blob_names(FirmwareKind::Me), ["raven_me", "picasso_me"]);
+    assert_eq!(polaris10.blob_names(FirmwareKind::Uvd), ["polaris10_uvd"]);
+    assert!(FirmwareIpVersions::default().blob_names(FirmwareKind::Me).is_empty());
+}
+
+#[test]
+fn test_check_firmware() {
+    let dir = Path::new("src/firmware/test_fw");
+    let navi10 = FirmwareIpVersions::from_ip_list([("GC", (10, 1, 10)), ("SDMA0", (5, 0, 0))]);
+    let navi31 = FirmwareIpVersions::from_ip_list([("GC", (11, 0, 0)), ("VCN", (4, 0, 0))]);
+
+    let me = FirmwareCheck::check(dir, &navi31, FirmwareKind::Me, 0x5A4);
+    assert_eq!(me.status, FirmwareStatus::Outdated);
+    assert_eq!(me.installed.map(|h| h.ucode_version), Some(0x5B0));
+
+    assert_eq!(FirmwareCheck::check(dir, &navi31, FirmwareKind::Me, 0x5B0).status, FirmwareStatus::UpToDate);
+    assert_eq!(FirmwareCheck::check(dir, &navi31, FirmwareKind::Me, 0x600).status, FirmwareStatus::Newer);
+    assert_eq!(FirmwareCheck::check(dir, &navi31, FirmwareKind::Pfp, 0x5A4).status, FirmwareStatus::Missing);
+    assert_eq!(FirmwareCheck::check(dir, &navi31, FirmwareKind::Dmcub, 0).status, FirmwareStatus::Unknown);
+
+    // compressed blob
+    let vcn = FirmwareCheck::check(dir, &navi31, FirmwareKind::Vcn, 0);
+    assert_eq!(vcn.blob, Some(dir.join("vcn_4_0_0.bin.zst")));
+
+    // legacy chip name
+    let sdma = FirmwareCheck::check(dir, &navi10, FirmwareKind::Sdma, 0x23);
+    assert_eq!(sdma.blob, Some(dir.join("navi10_sdma.bin")));
+    assert_eq!(sdma.status, FirmwareStatus::UpToDate);
+}
@@ -0,0 +1,307 @@
+use std::mem::{MaybeUninit, size_of};
+use crate::AMDGPU::{
+    gpu_metrics_v1_0,
+    gpu_metrics_v1_1,
+    gpu_metrics_v1_2,
+    gpu_metrics_v1_3,
+    gpu_metrics_v1_4,
+    gpu_metrics_v1_5,
+    gpu_metrics_v2_0,
+    gpu_metrics_v2_1,
+    gpu_metrics_v2_2,
+    gpu_metrics_v2_3,
+    gpu_metrics_v2_4,
+    gpu_metrics_v3_0,
+};
+use super::MetricsTableHeader;
+
+/// Field values of a synthetic gpu_metrics blob, `None` is encoded as N/A (all bits set).
+/// Fields that do not exist in the target revision are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct GpuMetricsFields {
+    pub temperature_edge: Option<u16>,
+    pub temperature_hotspot: Option<u16>,
+    pub temperature_mem: Option<u16>,
+    pub temperature_vrgfx: Option<u16>,
+    pub temperature_vrsoc: Option<u16>,
+    pub temperature_vrmem: Option<u16>,
+    pub temperature_gfx: Option<u16>,
+    pub temperature_soc: Option<u16>,
+    pub temperature_core: Option<Vec<u16>>,
+    pub temperature_l3: Option<Vec<u16>>,
+    pub temperature_hbm: Option<Vec<u16>>,
+    pub average_gfx_activity: Option<u16>,
+    pub average_umc_activity: Option<u16>,
+    pub average_mm_activity: Option<u16>,
+    pub vcn_activity: Option<Vec<u16>>,
+    pub average_socket_power: Option<u16>,
+    pub current_socket_power: Option<u16>,
+    pub average_cpu_power: Option<u16>,
+    pub average_soc_power: Option<u16>,
+    pub average_gfx_power: Option<u16>,
+    pub average_core_power: Option<Vec<u16>>,
+    pub energy_accumulator: Option<u64>,
+    pub system_clock_counter: Option<u64>,
+    pub firmware_timestamp: Option<u64>,
+    pub average_gfxclk_frequency: Option<u16>,
+    pub average_socclk_frequency: Option<u16>,
+    pub average_uclk_frequency: Option<u16>,
+    pub average_fclk_frequency: Option<u16>,
+    pub average_vclk_frequency: Option<u16>,
+    pub average_dclk_frequency: Option<u16>,
+    pub average_vclk1_frequency: Option<u16>,
+    pub average_dclk1_frequency: Option<u16>,
+    pub current_gfxclk: Option<u16>,
+    pub current_socclk: Option<u16>,
+    pub current_uclk: Option<u16>,
+    pub current_fclk: Option<u16>,
+    pub current_vclk: Option<u16>,
+    pub current_dclk: Option<u16>,
+    pub current_vclk1: Option<u16>,
+    pub current_dclk1: Option<u16>,
+    pub current_coreclk: Option<Vec<u16>>,
+    pub current_l3clk: Option<Vec<u16>>,
+    pub throttle_status: Option<u32>,
+    pub indep_throttle_status: Option<u64>,
+    pub current_fan_speed: Option<u16>,
+    pub fan_pwm: Option<u16>,
+    pub pcie_link_width: Option<u16>,
+    pub pcie_link_speed: Option<u16>,
+    pub voltage_soc: Option<u16>,
+    pub voltage_gfx: Option<u16>,
+    pub voltage_mem: Option<u16>,
+}
+
+// `set!(m, f; dst = src, ...)`, or `set!(m, f; name, ...)` when the names are the same
+macro_rules! set {
+    ($m:ident, $f:ident; $($dst:ident $(= $src:ident)?),* $(,)?) => {
+        $(set!(@one $m, $f, $dst $(, $src)?);)*
+    };
+    (@one $m:ident, $f:ident, $dst:ident) => { set!(@one $m, $f, $dst, $dst) };
+    (@one $m:ident, $f:ident, $dst:ident, $src:ident) => {
+        if let Some(v) = $f.$src { $m.$dst = v as _; }
+    };
+}
+
+macro_rules! set_array {
+    ($m:ident, $f:ident; $($dst:ident $(= $src:ident)?),* $(,)?) => {
+        $(set_array!(@one $m, $f, $dst $(, $src)?);)*
+    };
+    (@one $m:ident, $f:ident, $dst:ident) => { set_array!(@one $m, $f, $dst, $dst) };
+    (@one $m:ident, $f:ident, $dst:ident, $src:ident) => {
+        if let Some(vec) = &$f.$src {
+            for (d, s) in $m.$dst.iter_mut().zip(vec) { *d = *s as _; }
+        }
+    };
+}
+
+// gpu_metrics structs only have integer fields, so any bit pattern is valid
+fn encode_struct<T>(format_revision: u8, content_revision: u8, set_fields: impl FnOnce(&mut T)) -> Vec<u8> {
+    let size = size_of::<T>();
+    let mut m = MaybeUninit::<T>::uninit();
+
+    let mut bytes = unsafe {
+        m.as_mut_ptr().write_bytes(0xFF, 1);
+        set_fields(&mut *m.as_mut_ptr());
+
+        std::slice::from_raw_parts(m.as_ptr().cast::<u8>(), size).to_vec()
+    };
+
+    bytes[..2].copy_from_slice(&(size as u16).to_le_bytes());
+    bytes[2] = format_revision;
+    bytes[3] = content_revision;
+
+    bytes
+}
+
+impl GpuMetricsFields {
+    /// Build a gpu_metrics blob, the inverse of `GpuMetrics` decoding
+    pub fn encode(&self, format_revision: u8, content_revision: u8) -> Option<Vec<u8>> {
+        let f = self;
+
+        macro_rules! v1_0_3 {
+            ($m:ident) => {
+                set!($m, f;
+                    temperature_edge, temperature_hotspot, temperature_mem,
+                    temperature_vrgfx, temperature_vrsoc, temperature_vrmem,
+                    average_gfx_activity, average_umc_activity, average_mm_activity,
+                    average_socket_power, energy_accumulator, system_clock_counter,
+                    average_gfxclk_frequency, average_socclk_frequency, average_uclk_frequency,
+                    average_vclk0_frequency = average_vclk_frequency,
+                    average_dclk0_frequency = average_dclk_frequency,
+                    average_vclk1_frequency, average_dclk1_frequency,
+                    current_gfxclk, current_socclk, current_uclk,
+                    current_vclk0 = current_vclk, current_dclk0 = current_dclk,
+                    current_vclk1, current_dclk1,
+                    throttle_status, current_fan_speed, pcie_link_width, pcie_link_speed,
+                );
+            };
+        }
+        macro_rules! v1_4_5 {
+            ($m:ident) => {
+                set!($m, f;
+                    temperature_hotspot, temperature_mem, temperature_vrsoc,
+                    curr_socket_power = current_socket_power,
+                    average_gfx_activity, average_umc_activity,
+                    energy_accumulator, system_clock_counter, firmware_timestamp,
+                    throttle_status, pcie_link_width, pcie_link_speed, current_uclk,
+                );
+                set_array!($m, f; vcn_activity);
+                for (dst, src) in [
+                    (&mut $m.current_gfxclk[0], f.current_gfxclk),
+                    (&mut $m.current_socclk[0], f.current_socclk),
+                    (&mut $m.current_vclk0[0], f.current_vclk),
+                    (&mut $m.current_dclk0[0], f.current_dclk),
+                ] {
+                    if let Some(v) = src { *dst = v; }
+                }
+            };
+        }
+        macro_rules! v2_x {
+            ($m:ident) => {
+                set!($m, f;
+                    temperature_gfx, temperature_soc,
+                    average_gfx_activity, average_mm_activity, system_clock_counter,
+                    average_socket_power, average_cpu_power, average_soc_power, average_gfx_power,
+                    average_gfxclk_frequency, average_socclk_frequency, average_uclk_frequency,
+                    average_fclk_frequency, average_vclk_frequency, average_dclk_frequency,
+                    current_gfxclk, current_socclk, current_uclk,
+                    current_fclk, current_vclk, current_dclk,
+                    throttle_status, fan_pwm,
+                );
+                set_array!($m, f;
+                    temperature_core, temperature_l3, average_core_power,
+                    current_coreclk, current_l3clk,
+                );
+            };
+        }
+
+        let bytes = match (format_revision, content_revision) {
+            (1, 0) => encode_struct(1, 0, |m: &mut gpu_metrics_v1_0| { v1_0_3!(m); }),
+            (1, 1) => encode_struct(1, 1, |m: &mut gpu_metrics_v1_1| {
+                v1_0_3!(m);
+                set_array!(m, f; temperature_hbm);
+            }),
+            (1, 2) => encode_struct(1, 2, |m: &mut gpu_metrics_v1_2| {
+                v1_0_3!(m);
+                set_array!(m, f; temperature_hbm);
+                set!(m, f; firmware_timestamp);
+            }),
+            (1, 3) => encode_struct(1, 3, |m: &mut gpu_metrics_v1_3| {
+                v1_0_3!(m);
+                set_array!(m, f; temperature_hbm);
+                set!(m, f; firmware_timestamp, voltage_soc, voltage_gfx, voltage_mem, indep_throttle_status);
+            }),
+            (1, 4) => encode_struct(1, 4, |m: &mut gpu_metrics_v1_4| { v1_4_5!(m); }),
+            (1, 5) => encode_struct(1, 5, |m: &mut gpu_metrics_v1_5| { v1_4_5!(m); }),
+            (2, 0) => encode_struct(2, 0, |m: &mut gpu_metrics_v2_0| { v2_x!(m); }),
+            (2, 1) => encode_struct(2, 1, |m: &mut gpu_metrics_v2_1| { v2_x!(m); }),
+            (2, 2) => encode_struct(2, 2, |m: &mut gpu_metrics_v2_2| {
+                v2_x!(m);
+                set!(m, f; indep_throttle_status);
+            }),
+            (2, 3) => encode_struct(2, 3, |m: &mut gpu_metrics_v2_3| {
+                v2_x!(m);
+                set!(m, f; indep_throttle_status);
+            }),
+            (2, 4) => encode_struct(2, 4, |m: &mut gpu_metrics_v2_4| {
+                v2_x!(m);
+                set!(m, f; indep_throttle_status);
+            }),
+            (3, 0) => encode_struct(3, 0, |m: &mut gpu_metrics_v3_0| {
+                set!(m, f;
+                    temperature_gfx, temperature_soc, average_gfx_activity, system_clock_counter,
+                    average_socket_power, average_gfx_power,
+                    average_gfxclk_frequency, average_socclk_frequency, average_uclk_frequency,
+                    average_fclk_frequency, average_vclk_frequency,
+                );
+                set_array!(m, f; temperature_core, average_core_power, current_coreclk);
+            }),
+            _ => return None,
+        };
+
+        debug_assert!(MetricsTableHeader::parse(&bytes).is_some_and(|h| h.is_supported()));
+
+        Some(bytes)
+    }
+}
+
+#[cfg(test)]
+fn test_fields() -> GpuMetricsFields {
+    GpuMetricsFields {
+        temperature_edge: Some(45),
+        temperature_hotspot: Some(52),
+        temperature_mem: Some(48),
+        temperature_gfx: Some(4_100),
+        temperature_soc: Some(4_200),
+        temperature_core: Some(vec![4_300, 4_400]),
+        average_gfx_activity: Some(37),
+        average_umc_activity: Some(12),
+        average_socket_power: Some(95),
+        system_clock_counter: Some(0x1234_5678_9ABC),
+        average_gfxclk_frequency: Some(1_800),
+        current_gfxclk: Some(2_100),
+        current_uclk: Some(1_000),
+        current_coreclk: Some(vec![3_400, 3_500]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_gpu_metrics_round_trip() {
+    use crate::AMDGPU::MetricsInfo;
+    use super::{GpuMetricsDecode, SUPPORTED_REVISIONS};
+
+    let f = test_fields();
+
+    for (format_revision, content_revision) in SUPPORTED_REVISIONS {
+        let rev = format!("v{format_revision}.{content_revision}");
+        let bytes = f.encode(*format_revision, *content_revision).unwrap();
+        let m = GpuMetricsDecode::decode(&rev, bytes).metrics.unwrap();
+        let h = m.get_header().unwrap();
+
+        assert_eq!((h.format_revision, h.content_revision), (*format_revision, *content_revision));
+        assert_eq!(m.get_average_gfx_activity(), f.average_gfx_activity, "{rev}");
+        assert_eq!(m.get_system_clock_counter(), f.system_clock_counter, "{rev}");
+
+        match format_revision {
+            1 if *content_revision < 4 => {
+                assert_eq!(m.get_temperature_edge(), f.temperature_edge, "{rev}");
+                assert_eq!(m.get_current_gfxclk(), f.current_gfxclk, "{rev}");
+                assert_eq!(m.get_current_uclk(), f.current_uclk, "{rev}");
+                assert_eq!(m.get_average_gfxclk_frequency(), f.average_gfxclk_frequency, "{rev}");
+            },
+            1 => {
+                assert_eq!(m.get_temperature_hotspot(), f.temperature_hotspot, "{rev}");
+                assert_eq!(m.get_current_uclk(), f.current_uclk, "{rev}");
+            },
+            2 => {
+                assert_eq!(m.get_temperature_gfx(), f.temperature_gfx, "{rev}");
+                assert_eq!(m.get_current_gfxclk(), f.current_gfxclk, "{rev}");
+                assert_eq!(m.get_average_gfxclk_frequency(), f.average_gfxclk_frequency, "{rev}");
+                assert_eq!(m.get_current_coreclk().unwrap()[..2], f.current_coreclk.as_ref().unwrap()[..], "{rev}");
+            },
+            _ => {
+                assert_eq!(m.get_temperature_gfx(), f.temperature_gfx, "{rev}");
+                assert_eq!(m.get_average_gfxclk_frequency(), f.average_gfxclk_frequency, "{rev}");
+            },
+        }
+    }
+}
+
+#[test]
+fn test_gpu_metrics_unset_fields() {
+    use crate::AMDGPU::MetricsInfo;
+
+    let bytes = GpuMetricsFields::default().encode(1, 3).unwrap();
+    let m = super::GpuMetricsDecode::decode("v1.3", bytes).metrics.unwrap();
+
+    assert_eq!(m.get_temperature_edge(), Some(u16::MAX));
+    assert_eq!(m.get_current_gfxclk(), Some(u16::MAX));
+}
+
+#[test]
+fn test_gpu_metrics_encode_unsupported_revision() {
+    assert!(GpuMetricsFields::default().encode(1, 99).is_none());
+    assert!(GpuMetricsFields::default().encode(4, 0).is_none());
+}
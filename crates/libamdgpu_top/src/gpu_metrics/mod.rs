@@ -0,0 +1,182 @@
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/include/kgd_pp_interface.h
+
+use std::io::{self, Read};
+use std::path::Path;
+use crate::AMDGPU::GpuMetrics;
+
+mod text_dump;
+pub use text_dump::*;
+
+mod encode;
+pub use encode::*;
+
+/// `(format_revision, content_revision)` decodable by `GpuMetrics`
+pub const SUPPORTED_REVISIONS: &[(u8, u8)] = &[
+    (1, 0), (1, 1), (1, 2), (1, 3), (1, 4), (1, 5),
+    (2, 0), (2, 1), (2, 2), (2, 3), (2, 4),
+    (3, 0),
+];
+
+// struct metrics_table_header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricsTableHeader {
+    pub structure_size: u16,
+    pub format_revision: u8,
+    pub content_revision: u8,
+}
+
+impl MetricsTableHeader {
+    pub const SIZE: usize = 4;
+
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let [s0, s1, format_revision, content_revision] = *bytes.get(..Self::SIZE)? else { return None };
+
+        Some(Self {
+            structure_size: u16::from_le_bytes([s0, s1]),
+            format_revision,
+            content_revision,
+        })
+    }
+
+    /// Whether `GpuMetrics` has a variant for the revision
+    pub fn is_supported(&self) -> bool {
+        SUPPORTED_REVISIONS.contains(&(self.format_revision, self.content_revision))
+    }
+}
+
+impl std::fmt::Display for MetricsTableHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "v{}.{}", self.format_revision, self.content_revision)
+    }
+}
+
+#[derive(Debug)]
+pub struct GpuMetricsDecode {
+    pub source: String,
+    pub header: Option<MetricsTableHeader>,
+    pub metrics: Result<GpuMetrics, String>,
+    pub bytes: Vec<u8>,
+}
+
+impl GpuMetricsDecode {
+    /// `bytes` is either a raw gpu_metrics blob or a hex/base64 text dump of it
+    pub fn decode(source: &str, bytes: Vec<u8>) -> Self {
+        let bytes = if is_text_dump(&bytes) {
+            match parse_text_dump(&String::from_utf8_lossy(&bytes)) {
+                Ok(b) => b,
+                Err(err) => return Self::error(source, None, bytes, err),
+            }
+        } else {
+            bytes
+        };
+
+        let Some(header) = MetricsTableHeader::parse(&bytes) else {
+            return Self::error(source, None, bytes, "too short for gpu_metrics header".to_string());
+        };
+
+        if !header.is_supported() {
+            return Self::error(
+                source,
+                Some(header),
+                bytes,
+                format!("unsupported gpu_metrics revision: {header}"),
+            );
+        }
+
+        let metrics = decode_with_temp_file(&bytes)
+            .map_err(|err| format!("failed to decode gpu_metrics {header}: {err}"));
+
+        Self { source: source.to_string(), header: Some(header), metrics, bytes }
+    }
+
+    fn error(source: &str, header: Option<MetricsTableHeader>, bytes: Vec<u8>, err: String) -> Self {
+        Self { source: source.to_string(), header, metrics: Err(err), bytes }
+    }
+
+    /// Fields after the header as little-endian u32 words, `(offset, value)`
+    pub fn raw_fields(&self) -> Vec<(usize, u32)> {
+        let end = self.header
+            .map_or(self.bytes.len(), |h| usize::from(h.structure_size).min(self.bytes.len()));
+
+        self.bytes
+            .get(MetricsTableHeader::SIZE..end)
+            .unwrap_or_default()
+            .chunks_exact(4)
+            .enumerate()
+            .map(|(i, w)| (MetricsTableHeader::SIZE + i * 4, u32::from_le_bytes(w.try_into().unwrap())))
+            .collect()
+    }
+}
+
+// `GpuMetrics` can only be read from a file.
+// The file is created in a new private (0700) directory with `create_new` (O_EXCL),
+// so an existing file or symlink in the shared temp directory is never followed.
+fn decode_with_temp_file(bytes: &[u8]) -> io::Result<GpuMetrics> {
+    use std::io::Write;
+    use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = std::env::temp_dir().join(format!(
+        "amdgpu_top-gpu_metrics-{}-{}",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed),
+    ));
+    std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+
+    let path = dir.join("gpu_metrics");
+    let r = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)
+        .and_then(|mut f| f.write_all(bytes))
+        .and_then(|_| {
+            let mut buf = Vec::with_capacity(bytes.len());
+
+            GpuMetrics::read_file_with_buffer(&mut buf, path.to_string_lossy().as_ref())
+        });
+
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_dir(&dir);
+
+    r
+}
+
+/// Decode `-` (stdin), a file, or every file in a directory
+pub fn decode_gpu_metrics_inputs(arg: &str) -> io::Result<Vec<GpuMetricsDecode>> {
+    if arg == "-" {
+        let mut buf = Vec::new();
+        io::stdin().read_to_end(&mut buf)?;
+
+        return Ok(vec![GpuMetricsDecode::decode("<stdin>", buf)]);
+    }
+
+    let path = Path::new(arg);
+
+    if !path.is_dir() {
+        let buf = std::fs::read(path)?;
+
+        return Ok(vec![GpuMetricsDecode::decode(arg, buf)]);
+    }
+
+    let mut files: Vec<_> = std::fs::read_dir(path)?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    let list = files
+        .iter()
+        .map(|path| {
+            let source = path.to_string_lossy();
+
+            match std::fs::read(path) {
+                Ok(buf) => GpuMetricsDecode::decode(&source, buf),
+                Err(err) => GpuMetricsDecode::error(&source, None, Vec::new(), err.to_string()),
+            }
+        })
+        .collect();
+
+    Ok(list)
+}
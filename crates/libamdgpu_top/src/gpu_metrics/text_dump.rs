@@ -0,0 +1,177 @@
+// hex (plain, `xxd`, `hexdump -C`) or base64 text of a gpu_metrics blob, as pasted into issues
+
+/// gpu_metrics blobs always contain NUL bytes, so a printable ASCII file is a text dump
+pub fn is_text_dump(bytes: &[u8]) -> bool {
+    !bytes.is_empty() && bytes.iter().all(|b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+}
+
+pub fn parse_text_dump(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim();
+
+    if s.lines().any(|line| offset_prefix(line).is_some()) {
+        parse_offset_hex_dump(s)
+    } else if let Some(bytes) = parse_plain_hex(s) {
+        Ok(bytes)
+    } else {
+        parse_base64(s).ok_or_else(|| "neither a hex nor a base64 dump".to_string())
+    }
+}
+
+// "00000010: 7800 0103 ..." (xxd) or "00000010  78 00 01 03 ..." (hexdump -C)
+fn offset_prefix(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_start();
+    let end = line.find(|c: char| !c.is_ascii_hexdigit())?;
+    let (offset, rest) = line.split_at(end);
+
+    if offset.len() < 7 {
+        return None;
+    }
+
+    let offset = usize::from_str_radix(offset, 16).ok()?;
+    let data = if let Some(rest) = rest.strip_prefix(':') {
+        // xxd: the ASCII column follows two spaces
+        rest.trim_start().split("  ").next().unwrap_or_default()
+    } else if rest.starts_with("  ") {
+        // hexdump -C: the ASCII column is enclosed in '|'
+        rest.split('|').next().unwrap_or_default()
+    } else {
+        return None;
+    };
+
+    Some((offset, data))
+}
+
+// hexdump collapses repeated lines into '*', so lines are placed at their offsets
+fn parse_offset_hex_dump(s: &str) -> Result<Vec<u8>, String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut prev_line: Vec<u8> = Vec::new();
+
+    for line in s.lines().map(str::trim).filter(|l| !l.is_empty() && *l != "*") {
+        let (offset, data) = match offset_prefix(line) {
+            Some(v) => v,
+            // the last line of hexdump is only the total size
+            None if line.chars().all(|c| c.is_ascii_hexdigit()) => {
+                (usize::from_str_radix(line, 16).map_err(|e| e.to_string())?, "")
+            },
+            None => return Err(format!("invalid hex dump line: {line:?}")),
+        };
+
+        if offset < bytes.len() {
+            return Err(format!("invalid hex dump offset: {offset:#x}"));
+        }
+
+        while bytes.len() < offset && !prev_line.is_empty() {
+            let n = (offset - bytes.len()).min(prev_line.len());
+            bytes.extend_from_slice(&prev_line[..n]);
+        }
+        bytes.resize(offset, 0);
+
+        let data = parse_plain_hex(data).ok_or_else(|| format!("invalid hex dump line: {line:?}"))?;
+
+        if !data.is_empty() {
+            bytes.extend_from_slice(&data);
+            prev_line = data;
+        }
+    }
+
+    Ok(bytes)
+}
+
+// "78 00 01 03", "0x78, 0x00" or "78000103", odd-length groups are rejected
+fn parse_plain_hex(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    for token in s.split(|c: char| c.is_ascii_whitespace() || c == ',').filter(|t| !t.is_empty()) {
+        let token = token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")).unwrap_or(token);
+
+        if token.len() % 2 != 0 || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        for i in (0..token.len()).step_by(2) {
+            bytes.push(u8::from_str_radix(&token[i..i+2], 16).ok()?);
+        }
+    }
+
+    Some(bytes)
+}
+
+fn parse_base64(s: &str) -> Option<Vec<u8>> {
+    let sextet = |c: u8| -> Option<u32> {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+
+        Some(u32::from(v))
+    };
+    let chars: Vec<u8> = s.bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let chars = {
+        let end = chars.iter().rposition(|c| *c != b'=').map_or(0, |i| i + 1);
+        &chars[..end]
+    };
+
+    if chars.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(chars.len() * 3 / 4);
+
+    for chunk in chars.chunks(4) {
+        let mut v = 0u32;
+
+        for (i, c) in chunk.iter().enumerate() {
+            v |= sextet(*c)? << (18 - i * 6);
+        }
+
+        let n = chunk.len() - 1;
+        bytes.extend_from_slice(&v.to_be_bytes()[1..1+n]);
+    }
+
+    Some(bytes)
+}
+
+#[test]
+fn test_parse_text_dump() {
+    let expected: Vec<u8> = vec![0x78, 0x00, 0x01, 0x03, 0x2a, 0x00, 0x00, 0x00];
+
+    for s in [
+        "78 00 01 03 2a 00 00 00\n",
+        "0x78, 0x00, 0x01, 0x03, 0x2a, 0x00, 0x00, 0x00",
+        "7800 0103 2a00 0000",
+        "00000000: 7800 0103 2a00 0000                      x...*...\n",
+        "00000000  78 00 01 03 2a 00 00 00                           |x...*...|\n00000008\n",
+        "eAABAyoAAAA=\n",
+    ] {
+        assert_eq!(parse_text_dump(s), Ok(expected.clone()), "{s:?}");
+    }
+}
+
+#[test]
+fn test_parse_hexdump_repeat() {
+    let s = concat!(
+        "00000000  01 02 03 04 05 06 07 08  09 0a 0b 0c 0d 0e 0f 10  |................|\n",
+        "00000010  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|\n",
+        "*\n",
+        "00000040  ff ff                                             |..|\n",
+        "00000042\n",
+    );
+    let bytes = parse_text_dump(s).unwrap();
+
+    assert_eq!(bytes.len(), 0x42);
+    assert_eq!(&bytes[..4], &[1, 2, 3, 4]);
+    assert!(bytes[0x10..0x40].iter().all(|b| *b == 0));
+    assert_eq!(&bytes[0x40..], &[0xff, 0xff]);
+}
+
+#[test]
+fn test_is_text_dump() {
+    assert!(is_text_dump(b"eAABAyoAAAA=\n"));
+    assert!(!is_text_dump(&[0x78, 0x00, 0x01, 0x03]));
+}
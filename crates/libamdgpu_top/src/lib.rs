@@ -14,6 +14,14 @@ pub use app_device_info::*;
 pub mod stat;
 pub mod app;
 pub mod xdna;
+pub mod control;
+pub mod uevent;
+pub mod summary;
+pub mod edid;
+pub mod pp_table;
+pub mod atom;
+pub mod firmware;
+pub mod gpu_metrics;
 
 mod device_path;
 pub use device_path::DevicePath;
@@ -21,9 +29,17 @@ pub use device_path::DevicePath;
 mod drm_mode;
 pub use drm_mode::*;
 
+mod drm_ioctl;
+
+mod drm_objects;
+pub use drm_objects::*;
+
 mod ppfeaturemask;
 pub use ppfeaturemask::*;
 
+#[cfg(test)]
+mod test_util;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum GuiWgpuBackend {
     Gl,
@@ -39,6 +55,10 @@ pub struct UiArgs {
     pub is_dark_mode: Option<bool>, // TUI, GUI
     pub hide_fdinfo: bool, // TUI
     pub gui_wgpu_backend: GuiWgpuBackend,
+    pub power_attribution: stat::PowerAttributionModel,
+    pub summary: bool,
+    pub pc_config: stat::PCConfig,
+    pub pc_sampler: stat::PCSamplerConfig,
 }
 
 pub struct Sampling {
@@ -169,3 +189,23 @@ pub fn get_rocm_version() -> Option<String> {
 
     s.split_once('-').map(|(ver, _)| ver.to_string())
 }
+
+// "YYYY-MM-DD HH:MM:SS" (local time)
+pub fn get_local_time_string() -> String {
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+
+    unsafe {
+        let t = libc::time(std::ptr::null_mut());
+        libc::localtime_r(&t, &mut tm);
+    }
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec,
+    )
+}
@@ -0,0 +1,427 @@
+// `PPTable` (libdrm_amdgpu_sys) only implements `Debug`, so the pretty-printed (`{:#?}`) form is parsed
+// into a tree to be serialized or compared field by field.
+// Scalars are typed once here, so JSON and diff do not depend on their textual form.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scalar {
+    Null, // `None`
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Str(String), // unescaped string or char
+    Ident(String), // unit struct or enum variant
+}
+
+impl Scalar {
+    fn from_token(s: &str) -> Self {
+        match s {
+            "None" => Self::Null,
+            "true" => Self::Bool(true),
+            "false" => Self::Bool(false),
+            _ => if let Ok(n) = s.parse::<i64>() {
+                Self::Int(n)
+            } else if let Ok(n) = s.parse::<u64>() {
+                Self::UInt(n)
+            } else if let Some(n) = s.parse::<f64>().ok().filter(|_| s.starts_with(|c: char| c.is_ascii_digit() || c == '-')) {
+                Self::Float(n)
+            } else {
+                Self::Ident(s.to_string())
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Scalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "None"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Int(n) => write!(f, "{n}"),
+            Self::UInt(n) => write!(f, "{n}"),
+            Self::Float(n) => write!(f, "{n:?}"),
+            Self::Str(s) => write!(f, "{s:?}"),
+            Self::Ident(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugValue {
+    Struct { name: String, fields: Vec<(String, DebugValue)> },
+    Tuple { name: String, items: Vec<DebugValue> },
+    List(Vec<DebugValue>),
+    Map(Vec<(DebugValue, DebugValue)>),
+    Scalar(Scalar),
+}
+
+impl DebugValue {
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parser = Parser { s: s.as_bytes(), pos: 0 };
+        let v = parser.value()?;
+
+        parser.skip_ws();
+
+        (parser.pos == parser.s.len()).then_some(v)
+    }
+
+    /// Flatten into `(path, value)` pairs, e.g. `("SmcPptable.SocketPowerLimitAc[0]", Scalar::Int(300))`
+    pub fn flatten(&self) -> Vec<(String, Scalar)> {
+        let mut vec = Vec::new();
+
+        self.flatten_into(String::new(), &mut vec);
+
+        vec
+    }
+
+    fn flatten_into(&self, path: String, vec: &mut Vec<(String, Scalar)>) {
+        let join = |name: &str| if path.is_empty() { name.to_string() } else { format!("{path}.{name}") };
+
+        match self {
+            Self::Struct { fields, .. } => for (name, v) in fields {
+                v.flatten_into(join(name), vec);
+            },
+            Self::Tuple { name, items } => {
+                let path = if name.is_empty() || name == "Some" { path.clone() } else { join(name) };
+
+                if let [item] = &items[..] {
+                    item.flatten_into(path, vec);
+                } else {
+                    for (i, v) in items.iter().enumerate() {
+                        v.flatten_into(format!("{path}.{i}"), vec);
+                    }
+                }
+            },
+            Self::List(items) => for (i, v) in items.iter().enumerate() {
+                v.flatten_into(format!("{path}[{i}]"), vec);
+            },
+            Self::Map(entries) => for (k, v) in entries {
+                let key = match k {
+                    Self::Scalar(Scalar::Str(s)) => s.clone(),
+                    Self::Scalar(s) => s.to_string(),
+                    _ => format!("{k:?}"),
+                };
+
+                v.flatten_into(format!("{path}[{key}]"), vec);
+            },
+            Self::Scalar(s) => vec.push((path, s.clone())),
+        }
+    }
+}
+
+fn unescape_debug_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('0') => out.push('\0'),
+            Some(c) => out.push(c),
+            None => {},
+        }
+    }
+
+    out
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        self.skip_ws();
+
+        let matched = self.peek() == Some(c);
+
+        if matched {
+            self.pos += 1;
+        }
+
+        matched
+    }
+
+    // `,`-separated elements until `close`, the trailing comma is optional
+    fn seq<T>(&mut self, close: u8, mut elem: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let mut vec = Vec::new();
+
+        loop {
+            if self.eat(close) { return Some(vec) }
+
+            vec.push(elem(self)?);
+
+            if !self.eat(b',') {
+                return self.eat(close).then_some(vec);
+            }
+        }
+    }
+
+    // without the quotes, unescaped
+    fn quoted(&mut self, quote: u8) -> Option<String> {
+        self.pos += 1;
+
+        let start = self.pos;
+
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                c if c == quote => break,
+                _ => self.pos += 1,
+            }
+        }
+
+        let s = String::from_utf8_lossy(&self.s[start..self.pos]).to_string();
+
+        self.pos += 1;
+
+        Some(unescape_debug_str(&s))
+    }
+
+    fn token(&mut self) -> String {
+        let start = self.pos;
+
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || b"_.-+#".contains(&c)) {
+            self.pos += 1;
+        }
+
+        String::from_utf8_lossy(&self.s[start..self.pos]).to_string()
+    }
+
+    fn value(&mut self) -> Option<DebugValue> {
+        self.skip_ws();
+
+        match self.peek()? {
+            b'[' => {
+                self.pos += 1;
+                self.seq(b']', Self::value).map(DebugValue::List)
+            },
+            b'{' => {
+                self.pos += 1;
+                self.seq(b'}', |p| {
+                    let k = p.value()?;
+                    p.eat(b':').then_some(())?;
+                    Some((k, p.value()?))
+                }).map(DebugValue::Map)
+            },
+            b'(' => {
+                self.pos += 1;
+                let items = self.seq(b')', Self::value)?;
+                Some(DebugValue::Tuple { name: String::new(), items })
+            },
+            q @ (b'"' | b'\'') => self.quoted(q).map(|s| DebugValue::Scalar(Scalar::Str(s))),
+            _ => {
+                let name = self.token();
+
+                if name.is_empty() { return None }
+
+                // `Name(` or `Name {`, a struct field is handled by `field`
+                if self.peek() == Some(b'(') {
+                    self.pos += 1;
+                    let items = self.seq(b')', Self::value)?;
+                    return Some(DebugValue::Tuple { name, items });
+                }
+
+                let save = self.pos;
+
+                if self.eat(b'{') {
+                    let fields = self.seq(b'}', Self::field)?;
+                    let fields = fields.into_iter().flatten().collect();
+                    return Some(DebugValue::Struct { name, fields });
+                }
+
+                self.pos = save;
+
+                Some(DebugValue::Scalar(Scalar::from_token(&name)))
+            },
+        }
+    }
+
+    // `None` for `..` of `finish_non_exhaustive`
+    fn field(&mut self) -> Option<Option<(String, DebugValue)>> {
+        self.skip_ws();
+
+        let start = self.pos;
+
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'#') {
+            self.pos += 1;
+        }
+
+        let name = String::from_utf8_lossy(&self.s[start..self.pos]).to_string();
+
+        if name.is_empty() {
+            return (self.s[self.pos..].starts_with(b"..")).then(|| { self.pos += 2; None });
+        }
+
+        self.eat(b':').then_some(())?;
+
+        let name = name.trim_start_matches("r#").to_string();
+
+        Some(Some((name, self.value()?)))
+    }
+}
+
+// shaped like the `{:#?}` output of `PPTable::{Smu11, Smu13}`
+#[cfg(test)]
+mod sample {
+    // only read through `Debug`
+    #![allow(dead_code, non_snake_case)]
+
+    #[derive(Debug)]
+    pub struct Header {
+        pub structure_size: u16,
+        pub format_revision: u8,
+        pub content_revision: u8,
+    }
+
+    #[derive(Debug)]
+    pub struct SmcPptable {
+        pub Version: u32,
+        pub SocketPowerLimitAc: [u16; 4],
+        pub FanStartTempMin: u16,
+        pub MinVoltageGfx: u16,
+        pub GfxclkFgfxoffEntry: u16,
+        pub DcBtcGb: [i16; 2],
+        pub dBtcGbGfxDfllModelSelect: f32,
+        pub FanPwmMin: Option<u16>,
+    }
+
+    #[derive(Debug)]
+    pub struct OverDrive(pub u8, pub i32);
+
+    #[derive(Debug)]
+    pub enum FanMode {
+        Auto,
+        Manual { pwm: u8 },
+    }
+
+    #[derive(Debug)]
+    pub struct PowerPlayTable {
+        pub header: Header,
+        pub golden_pp_id: u8,
+        pub name: String,
+        pub overdrive: Option<OverDrive>,
+        pub fan_mode: FanMode,
+        pub smc_pptable: SmcPptable,
+    }
+
+    #[derive(Debug)]
+    pub enum PPTable {
+        Smu11(PowerPlayTable),
+        Smu13(PowerPlayTable),
+    }
+
+    pub fn table(smu13: bool) -> PPTable {
+        let t = PowerPlayTable {
+            header: Header { structure_size: 2470, format_revision: 15, content_revision: 0 },
+            golden_pp_id: 0,
+            name: "Navi \"3x\"\tA0\\".to_string(),
+            overdrive: Some(OverDrive(1, -50)),
+            fan_mode: FanMode::Manual { pwm: 128 },
+            smc_pptable: SmcPptable {
+                Version: 0x3d,
+                SocketPowerLimitAc: [303, 0, 0, 0],
+                FanStartTempMin: 50,
+                MinVoltageGfx: 2240,
+                GfxclkFgfxoffEntry: 2500,
+                DcBtcGb: [-100, 0],
+                dBtcGbGfxDfllModelSelect: -0.25,
+                FanPwmMin: None,
+            },
+        };
+
+        if smu13 { PPTable::Smu13(t) } else { PPTable::Smu11(t) }
+    }
+}
+
+#[test]
+fn test_debug_tree_parse_pp_table() {
+    use Scalar::*;
+
+    for smu13 in [false, true] {
+        let table = sample::table(smu13);
+        let tree = DebugValue::parse(&format!("{table:#?}")).unwrap();
+        let DebugValue::Tuple { name, items } = &tree else { panic!("{tree:?}") };
+        let prefix = if smu13 { "Smu13" } else { "Smu11" };
+
+        assert_eq!(name, prefix);
+        assert!(matches!(&items[..], [DebugValue::Struct { name, .. }] if name == "PowerPlayTable"));
+
+        let flat = tree.flatten();
+        let get = |path: &str| flat.iter().find(|(p, _)| p == path).map(|(_, v)| v.clone());
+        let expected = [
+            ("header.structure_size", Int(2470)),
+            ("name", Str("Navi \"3x\"\tA0\\".to_string())),
+            ("overdrive.OverDrive.0", Int(1)),
+            ("overdrive.OverDrive.1", Int(-50)),
+            ("fan_mode.pwm", Int(128)),
+            ("smc_pptable.Version", Int(61)),
+            ("smc_pptable.SocketPowerLimitAc[0]", Int(303)),
+            ("smc_pptable.SocketPowerLimitAc[3]", Int(0)),
+            ("smc_pptable.DcBtcGb[0]", Int(-100)),
+            ("smc_pptable.dBtcGbGfxDfllModelSelect", Float(-0.25)),
+            ("smc_pptable.FanPwmMin", Null),
+        ];
+
+        for (path, value) in expected {
+            assert_eq!(get(&format!("{prefix}.{path}")), Some(value), "{path}");
+        }
+
+        assert_eq!(flat.len(), 20);
+    }
+}
+
+#[test]
+fn test_debug_tree_parse_edge_cases() {
+    #[derive(Debug)]
+    struct FanModeAuto;
+
+    let s = format!("{:#?}", (Some(Some(-1i8)), [1.5f64, -2e-7, f64::MAX], 'a', '\'', u64::MAX, true, FanModeAuto));
+    let tree = DebugValue::parse(&s).unwrap();
+    let DebugValue::Tuple { name, items } = tree else { panic!() };
+    let scalar = |s: Scalar| DebugValue::Scalar(s);
+
+    assert!(name.is_empty());
+    assert_eq!(items[0], DebugValue::Tuple {
+        name: "Some".to_string(),
+        items: vec![DebugValue::Tuple { name: "Some".to_string(), items: vec![scalar(Scalar::Int(-1))] }],
+    });
+    assert_eq!(items[1], DebugValue::List(
+        [1.5f64, -2e-7, f64::MAX].into_iter().map(|v| scalar(Scalar::Float(v))).collect()
+    ));
+    assert_eq!(items[2], scalar(Scalar::Str("a".to_string())));
+    assert_eq!(items[3], scalar(Scalar::Str("'".to_string())));
+    assert_eq!(items[4], scalar(Scalar::UInt(u64::MAX)));
+    assert_eq!(items[5], scalar(Scalar::Bool(true)));
+    assert_eq!(items[6], scalar(Scalar::Ident("FanModeAuto".to_string())));
+
+    let map: std::collections::BTreeMap<&str, Vec<u8>> = [("a\"b", vec![]), ("c", vec![1])].into();
+    let tree = DebugValue::parse(&format!("{map:#?}")).unwrap();
+
+    assert_eq!(tree.flatten(), vec![("[c][0]".to_string(), Scalar::Int(1))]);
+
+    assert_eq!(DebugValue::parse("Foo { a: 1, .. }"), Some(DebugValue::Struct {
+        name: "Foo".to_string(),
+        fields: vec![("a".to_string(), scalar(Scalar::Int(1)))],
+    }));
+    assert_eq!(DebugValue::parse("Foo { a: 1 } trailing"), None);
+    assert_eq!(DebugValue::parse("Foo { a: \"unterminated }"), None);
+}
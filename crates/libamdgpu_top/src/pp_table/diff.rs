@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use super::{DebugValue, Scalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PPTableFieldCategory {
+    PowerLimit,
+    Clock,
+    Fan,
+    Voltage,
+    Other,
+}
+
+impl PPTableFieldCategory {
+    // PowerPlay/SMU field names do not follow a common schema across generations,
+    // so guess from the words of the path (e.g. `SocketPowerLimitAc`, `FanMaximumRpm`, `VcBtcFixedVminAgingOffset`).
+    // Whole words are compared, so `smc_pptable` is not `PPT` and `OverDriveLimits` is not a power limit.
+    pub fn from_path(path: &str) -> Self {
+        let words = path_words(path);
+        let has = |f: &dyn Fn(&str) -> bool| words.iter().any(|w| f(w));
+
+        if has(&|w| matches!(w, "fan" | "acoustic" | "pwm")) {
+            Self::Fan
+        } else if has(&|w| w.starts_with("volt") || w.starts_with("vdd") || matches!(w, "vid" | "vmin" | "vmax" | "droop" | "curve")) {
+            Self::Voltage
+        } else if has(&|w| w.starts_with("temp") || matches!(w, "hotspot" | "thermal")) {
+            Self::Other
+        } else if has(&|w| w.starts_with("watt") || matches!(w, "ppt" | "tdc" | "edc" | "tdp"))
+            || (has(&|w| w == "power") && has(&|w| w.starts_with("limit")))
+        {
+            Self::PowerLimit
+        } else if has(&|w| w.ends_with("clk") || w.starts_with("freq") || matches!(w, "clock" | "dpm")) {
+            Self::Clock
+        } else {
+            Self::Other
+        }
+    }
+}
+
+// lowercase words split at `_`, `.`, digits and camel case boundaries, without `[..]` indices/keys
+// e.g. `smc_pptable.SkuTable.PPTLimit[0]` -> `smc`, `pptable`, `sku`, `table`, `ppt`, `limit`
+fn path_words(path: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut cur = String::new();
+    let mut depth = 0;
+    let chars: Vec<char> = path.chars().collect();
+
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => {},
+        }
+
+        if depth > 0 || !c.is_ascii_alphanumeric() {
+            if !cur.is_empty() { words.push(std::mem::take(&mut cur)) }
+            continue;
+        }
+
+        if !cur.is_empty() {
+            let prev = chars[i-1];
+            let next_lower = chars.get(i+1).is_some_and(|n| n.is_ascii_lowercase());
+            let boundary = (prev.is_ascii_digit() != c.is_ascii_digit())
+                || (prev.is_ascii_lowercase() && c.is_ascii_uppercase())
+                || (prev.is_ascii_uppercase() && c.is_ascii_uppercase() && next_lower);
+
+            if boundary { words.push(std::mem::take(&mut cur)) }
+        }
+
+        cur.push(c.to_ascii_lowercase());
+    }
+
+    if !cur.is_empty() { words.push(cur) }
+
+    words
+}
+
+impl std::fmt::Display for PPTableFieldCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Self::PowerLimit => "Power Limit",
+            Self::Clock => "Clock",
+            Self::Fan => "Fan",
+            Self::Voltage => "Voltage",
+            Self::Other => "Other",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PPTableFieldDiff {
+    pub path: String,
+    pub category: PPTableFieldCategory,
+    pub a: Option<Scalar>,
+    pub b: Option<Scalar>,
+}
+
+/// Changed fields between two decoded tables, grouped by category
+pub fn diff_pp_table(a: &DebugValue, b: &DebugValue) -> Vec<PPTableFieldDiff> {
+    let a = a.flatten();
+    let mut b: Vec<_> = b.flatten().into_iter().map(Some).collect();
+    let b_index: HashMap<String, usize> = b
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| Some((v.as_ref()?.0.clone(), i)))
+        .collect();
+    let mut vec = Vec::new();
+
+    for (path, va) in a {
+        let vb = b_index.get(&path).and_then(|i| b[*i].take()).map(|(_, v)| v);
+
+        if vb.as_ref() != Some(&va) {
+            vec.push(PPTableFieldDiff {
+                category: PPTableFieldCategory::from_path(&path),
+                path,
+                a: Some(va),
+                b: vb,
+            });
+        }
+    }
+
+    for (path, vb) in b.into_iter().flatten() {
+        vec.push(PPTableFieldDiff {
+            category: PPTableFieldCategory::from_path(&path),
+            path,
+            a: None,
+            b: Some(vb),
+        });
+    }
+
+    vec.sort_by_key(|d| d.category);
+
+    vec
+}
+
+#[test]
+fn test_pp_table_field_category() {
+    use PPTableFieldCategory::*;
+
+    for (path, category) in [
+        // SMU11 (Navi1x)
+        ("Smu11.smc_pptable.SocketPowerLimitAc[0]", PowerLimit),
+        ("Smu11.smc_pptable.TdcLimitGfx", PowerLimit),
+        ("Smu11.smc_pptable.FanMaximumRpm", Fan),
+        ("Smu11.smc_pptable.FanStopTemp", Fan),
+        ("Smu11.smc_pptable.MinVoltageGfx", Voltage),
+        ("Smu11.smc_pptable.VcBtcFixedVminAgingOffset", Voltage),
+        ("Smu11.smc_pptable.FreqTableGfx[8]", Clock),
+        ("Smu11.smc_pptable.Mp0clkFreq[1]", Clock),
+        ("Smu11.smc_pptable.TemperatureLimit[0]", Other),
+        ("Smu11.smc_pptable.ThrottlerControlMask", Other),
+        ("Smu11.power_saving_clock_max[0]", Clock),
+        // SMU13 (Navi3x)
+        ("Smu13.smc_pptable.SkuTable.MsgLimits.Power[0][1]", PowerLimit),
+        ("Smu13.smc_pptable.SkuTable.MsgLimits.Tdc[0]", PowerLimit),
+        ("Smu13.smc_pptable.SkuTable.MsgLimits.Temperature[1]", Other),
+        ("Smu13.smc_pptable.SkuTable.MsgLimits.PwmLimit", Fan),
+        ("Smu13.smc_pptable.SkuTable.OverDriveLimitsBasicMin.GfxclkFmax", Clock),
+        ("Smu13.smc_pptable.SkuTable.OverDriveLimitsBasicMin.VddGfxVmax", Voltage),
+        ("Smu13.smc_pptable.SkuTable.VideoFeatureMask", Other),
+        ("Smu13.smc_pptable.SkuTable.DcModeMaxFreq[0]", Clock),
+        ("Smu13.smc_pptable.BoardTable.VddciUlvPhaseSheddingMask", Voltage),
+        ("Smu13.smc_pptable.SkuTable.PPTLimitGfx", PowerLimit),
+    ] {
+        assert_eq!(PPTableFieldCategory::from_path(path), category, "{path}");
+    }
+}
+
+#[test]
+fn test_diff_pp_table() {
+    let a = DebugValue::parse("Smu13(PowerPlayTable { FanStopTemp: 50, MinVoltageGfx: 2240, SocketPowerLimitAc: [303, 0], Removed: 1, Ratio: 1.0 })").unwrap();
+    let b = DebugValue::parse("Smu13(PowerPlayTable { FanStopTemp: 50, MinVoltageGfx: 2300, SocketPowerLimitAc: [330, 0], Added: Some(2), Ratio: 1.0 })").unwrap();
+    let diff: Vec<_> = diff_pp_table(&a, &b)
+        .into_iter()
+        .map(|d| (d.path, d.category, d.a, d.b))
+        .collect();
+    let n = |v: i64| Some(Scalar::Int(v));
+
+    assert_eq!(diff, vec![
+        ("Smu13.SocketPowerLimitAc[0]".to_string(), PPTableFieldCategory::PowerLimit, n(303), n(330)),
+        ("Smu13.MinVoltageGfx".to_string(), PPTableFieldCategory::Voltage, n(2240), n(2300)),
+        ("Smu13.Removed".to_string(), PPTableFieldCategory::Other, n(1), None),
+        ("Smu13.Added".to_string(), PPTableFieldCategory::Other, None, n(2)),
+    ]);
+
+    assert!(diff_pp_table(&a, &a).is_empty());
+}
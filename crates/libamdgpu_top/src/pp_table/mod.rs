@@ -0,0 +1,98 @@
+use std::path::Path;
+use crate::AMDGPU::{self, DeviceHandle, HwId, IpHwId, PPTable};
+use crate::DevicePath;
+
+mod debug_tree;
+pub use debug_tree::*;
+
+mod diff;
+pub use diff::*;
+
+fn smu_ip(sysfs_path: &Path) -> Option<IpHwId> {
+    IpHwId::get_from_die_id_sysfs(HwId::MP1, sysfs_path.join("ip_discovery/die/0/")).ok()
+}
+
+/// Decode with the SMU version of the device, if available
+pub fn decode_pp_table(bytes: &[u8], sysfs_path: &Path) -> Option<PPTable> {
+    let smu = smu_ip(sysfs_path);
+
+    if let Some(smu) = smu.as_ref().and_then(|smu| smu.instances.first()) {
+        PPTable::decode_with_smu_version(bytes, smu.version()).ok()
+    } else {
+        PPTable::decode(bytes).ok()
+    }
+}
+
+pub fn pp_table_tree(table: &PPTable) -> Option<DebugValue> {
+    DebugValue::parse(&format!("{table:#?}"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PPTableSource {
+    Sysfs,
+    Vbios,
+}
+
+impl std::fmt::Display for PPTableSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let s = match self {
+            Self::Sysfs => "sysfs",
+            Self::Vbios => "VBIOS",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug)]
+pub struct PPTableDump {
+    pub source: PPTableSource,
+    pub bytes: Option<Vec<u8>>,
+    pub table: Option<PPTable>,
+}
+
+impl PPTableDump {
+    pub fn tree(&self) -> Option<DebugValue> {
+        pp_table_tree(self.table.as_ref()?)
+    }
+}
+
+#[derive(Debug)]
+pub struct PPTables {
+    pub smu_version: Option<(u32, u32, u32)>,
+    pub tables: Vec<PPTableDump>,
+}
+
+impl PPTables {
+    pub fn get(device_path: &DevicePath, amdgpu_dev: &DeviceHandle) -> Self {
+        let sysfs = &device_path.sysfs_path;
+        let smu = smu_ip(sysfs);
+        let smu_version = smu.as_ref().and_then(|smu| smu.instances.first()).map(|smu| (
+            u32::from(smu.major),
+            u32::from(smu.minor),
+            u32::from(smu.revision),
+        ));
+
+        let bytes_sysfs = std::fs::read(sysfs.join("pp_table")).ok();
+        let bytes_vbios = amdgpu_dev.get_vbios_image().ok().and_then(|vbios_image| {
+            use AMDGPU::VBIOS::VbiosParser;
+
+            let vbios_parser = VbiosParser::new(vbios_image);
+            let rom_header = vbios_parser.get_atom_rom_header()?;
+            let data_table = vbios_parser.get_atom_data_table(&rom_header)?;
+
+            Some(vbios_parser.get_powerplay_table_bytes(&data_table)?.to_vec())
+        });
+
+        let tables = [
+            (bytes_sysfs, PPTableSource::Sysfs),
+            (bytes_vbios, PPTableSource::Vbios),
+        ].into_iter().map(|(bytes, source)| {
+            let table = bytes.as_ref().and_then(|bytes| decode_pp_table(bytes, sysfs));
+
+            PPTableDump { source, bytes, table }
+        }).collect();
+
+        Self { smu_version, tables }
+    }
+}
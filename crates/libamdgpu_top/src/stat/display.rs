@@ -0,0 +1,291 @@
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::os::fd::{AsRawFd, RawFd};
+use std::time::{Duration, Instant};
+use crate::{get_local_time_string, DevicePath};
+use crate::drm_ioctl::{
+    self,
+    DrmProperty,
+    DRM_MODE_CONNECTED,
+    DRM_MODE_OBJECT_CONNECTOR,
+    DRM_MODE_OBJECT_CRTC,
+};
+
+// ref: drm_connector_enum_list in drivers/gpu/drm/drm_connector.c
+fn connector_type_name(connector_type: u32) -> &'static str {
+    match connector_type {
+        1 => "VGA",
+        2 => "DVI-I",
+        3 => "DVI-D",
+        4 => "DVI-A",
+        5 => "Composite",
+        6 => "SVIDEO",
+        7 => "LVDS",
+        8 => "Component",
+        9 => "DIN",
+        10 => "DP",
+        11 => "HDMI-A",
+        12 => "HDMI-B",
+        13 => "TV",
+        14 => "eDP",
+        15 => "Virtual",
+        16 => "DSI",
+        17 => "DPI",
+        18 => "Writeback",
+        19 => "SPI",
+        20 => "USB",
+        _ => "Unknown",
+    }
+}
+
+// The property IDs do not change while the device exists,
+// the names are fetched only once instead of one GETPROPERTY per property per update.
+#[derive(Debug, Clone, Default)]
+struct PropertyCache(HashMap<u32, DrmProperty>);
+
+impl PropertyCache {
+    // Vec<(prop_id, value)>
+    fn get_object_properties(&mut self, fd: RawFd, obj_id: u32, obj_type: u32) -> Vec<(u32, u64)> {
+        let values = drm_ioctl::get_object_properties(fd, obj_id, obj_type);
+
+        for (prop_id, _) in &values {
+            if let Entry::Vacant(e) = self.0.entry(*prop_id) {
+                if let Some(prop) = drm_ioctl::get_property(fd, *prop_id) {
+                    e.insert(prop);
+                }
+            }
+        }
+
+        values
+    }
+
+    fn find(&self, values: &[(u32, u64)], name: &str) -> Option<(&DrmProperty, u64)> {
+        values.iter().find_map(|(prop_id, value)| {
+            self.0.get(prop_id).filter(|prop| prop.name == name).map(|prop| (prop, *value))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayMode {
+    pub crtc_id: u32,
+    pub hdisplay: u16,
+    pub vdisplay: u16,
+    pub refresh_rate: f64, // Hz
+}
+
+impl fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}@{:.2}", self.hdisplay, self.vdisplay, self.refresh_rate)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayState {
+    pub connector_id: u32,
+    pub name: String,
+    pub connected: bool,
+    pub mode: Option<DisplayMode>,
+    pub vrr_capable: Option<bool>,
+    pub vrr_enabled: Option<bool>, // CRTC property
+    pub max_bpc: Option<u64>,
+    pub colorspace: Option<String>,
+    pub hdr_output_metadata: Option<bool>,
+}
+
+impl DisplayState {
+    fn get(fd: RawFd, props: &mut PropertyCache, connector_id: u32) -> Option<Self> {
+        let conn = drm_ioctl::get_connector_current(fd, connector_id)?;
+        let values = props.get_object_properties(fd, connector_id, DRM_MODE_OBJECT_CONNECTOR);
+        let value = |name: &str| props.find(&values, name).map(|(_, v)| v);
+        let mode = Some(conn.encoder_id)
+            .filter(|id| *id != 0)
+            .and_then(|id| drm_ioctl::get_encoder(fd, id))
+            .filter(|encoder| encoder.crtc_id != 0)
+            .and_then(|encoder| drm_ioctl::get_crtc(fd, encoder.crtc_id))
+            .filter(|crtc| crtc.mode_valid != 0)
+            .map(|crtc| DisplayMode {
+                crtc_id: crtc.crtc_id,
+                hdisplay: crtc.mode.hdisplay,
+                vdisplay: crtc.mode.vdisplay,
+                refresh_rate: crtc.mode.refresh_rate(),
+            });
+        let vrr_capable = value("vrr_capable").map(|v| v != 0);
+        let max_bpc = value("max bpc");
+        // blob id
+        let hdr_output_metadata = value("HDR_OUTPUT_METADATA").map(|v| v != 0);
+        let colorspace = props.find(&values, "Colorspace").and_then(|(prop, value)| {
+            prop.enums
+                .iter()
+                .find(|(v, _)| *v == value)
+                .map(|(_, name)| name.clone())
+        });
+        let vrr_enabled = mode.and_then(|mode| {
+            let crtc_values = props.get_object_properties(fd, mode.crtc_id, DRM_MODE_OBJECT_CRTC);
+
+            props.find(&crtc_values, "VRR_ENABLED").map(|(_, v)| v != 0)
+        });
+
+        Some(Self {
+            connector_id: conn.connector_id,
+            name: format!("{}-{}", connector_type_name(conn.connector_type), conn.connector_type_id),
+            connected: conn.connection == DRM_MODE_CONNECTED,
+            mode,
+            vrr_capable,
+            vrr_enabled,
+            max_bpc,
+            colorspace,
+            hdr_output_metadata,
+        })
+    }
+
+    fn diff(&self, pre: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if self.connected != pre.connected {
+            changes.push((if self.connected { "Connected" } else { "Disconnected" }).to_string());
+        }
+
+        if self.mode != pre.mode {
+            changes.push(format!(
+                "Mode: {}",
+                self.mode.map_or("Off".to_string(), |mode| mode.to_string()),
+            ));
+        }
+
+        if self.vrr_enabled != pre.vrr_enabled {
+            changes.push(format!("VRR: {}", on_off(self.vrr_enabled)));
+        }
+
+        if self.max_bpc != pre.max_bpc {
+            changes.push(format!("max bpc: {}", self.max_bpc.map_or("_".to_string(), |v| v.to_string())));
+        }
+
+        if self.colorspace != pre.colorspace {
+            changes.push(format!("Colorspace: {}", self.colorspace.as_deref().unwrap_or("_")));
+        }
+
+        if self.hdr_output_metadata != pre.hdr_output_metadata {
+            changes.push(format!("HDR: {}", on_off(self.hdr_output_metadata)));
+        }
+
+        changes
+    }
+}
+
+pub fn on_off(v: Option<bool>) -> &'static str {
+    match v {
+        Some(true) => "On",
+        Some(false) => "Off",
+        None => "_",
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DisplayEvent {
+    pub time: String, // local time
+    pub name: String,
+    pub change: String,
+}
+
+impl fmt::Display for DisplayEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.time, self.name, self.change)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DisplayMonitor {
+    pub displays: Vec<DisplayState>,
+    pub events: VecDeque<DisplayEvent>,
+    props: PropertyCache,
+    hotplug: bool,
+    last_update: Option<Instant>,
+}
+
+impl DisplayMonitor {
+    const MAX_LEN: usize = 32;
+    // mode, VRR and colorspace changes do not send a hotplug uevent
+    const FALLBACK_INTERVAL: Duration = Duration::from_secs(5);
+
+    // only the current state of the connectors, without probing them or reading EDID
+    fn get_displays(&mut self, device_path: &DevicePath) -> Option<Vec<DisplayState>> {
+        // the card node is opened every time, not to keep the device awake
+        let f = File::open(&device_path.card).ok()?;
+        let fd = f.as_raw_fd();
+        let res = drm_ioctl::get_resources(fd)?;
+
+        let displays = res.connectors
+            .iter()
+            .filter_map(|connector_id| DisplayState::get(fd, &mut self.props, *connector_id))
+            .collect();
+
+        self.last_update = Some(Instant::now());
+
+        Some(displays)
+    }
+
+    pub fn new(device_path: &DevicePath) -> Option<Self> {
+        let mut monitor = Self::default();
+
+        monitor.displays = monitor.get_displays(device_path)?;
+
+        if monitor.displays.is_empty() { return None }
+
+        Some(monitor)
+    }
+
+    /// Re-enumerate the connectors at the next update, on a DRM hotplug uevent
+    pub fn on_hotplug(&mut self) {
+        self.hotplug = true;
+    }
+
+    pub fn update(&mut self, device_path: &DevicePath) {
+        if !self.hotplug
+            && self.last_update.is_some_and(|t| t.elapsed() < Self::FALLBACK_INTERVAL)
+        {
+            return;
+        }
+
+        self.hotplug = false;
+
+        let Some(displays) = self.get_displays(device_path) else { return };
+
+        for cur in &displays {
+            let changes = match self.displays.iter().find(|pre| pre.connector_id == cur.connector_id) {
+                Some(pre) => cur.diff(pre),
+                // MST
+                None => vec!["Added".to_string()],
+            };
+
+            for change in changes {
+                self.push_event(&cur.name, change);
+            }
+        }
+
+        let removed: Vec<String> = self.displays
+            .iter()
+            .filter(|pre| !displays.iter().any(|cur| cur.connector_id == pre.connector_id))
+            .map(|pre| pre.name.clone())
+            .collect();
+
+        for name in removed {
+            self.push_event(&name, "Removed".to_string());
+        }
+
+        while self.events.len() > Self::MAX_LEN {
+            self.events.pop_front();
+        }
+
+        self.displays = displays;
+    }
+
+    fn push_event(&mut self, name: &str, change: String) {
+        self.events.push_back(DisplayEvent {
+            time: get_local_time_string(),
+            name: name.to_string(),
+            change,
+        });
+    }
+}
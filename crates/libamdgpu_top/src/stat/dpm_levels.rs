@@ -0,0 +1,253 @@
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#pp-dpm-sclk-pp-dpm-mclk-pp-dpm-socclk-pp-dpm-fclk-pp-dpm-dcefclk-pp-dpm-pcie
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DpmClockType {
+    SCLK,
+    MCLK,
+    FCLK,
+    SOCCLK,
+    DCEFCLK,
+    VCLK,
+    DCLK,
+}
+
+impl DpmClockType {
+    pub const ALL: [Self; 7] = [
+        Self::SCLK,
+        Self::MCLK,
+        Self::FCLK,
+        Self::SOCCLK,
+        Self::DCEFCLK,
+        Self::VCLK,
+        Self::DCLK,
+    ];
+
+    pub const fn file_name(&self) -> &'static str {
+        match self {
+            Self::SCLK => "pp_dpm_sclk",
+            Self::MCLK => "pp_dpm_mclk",
+            Self::FCLK => "pp_dpm_fclk",
+            Self::SOCCLK => "pp_dpm_socclk",
+            Self::DCEFCLK => "pp_dpm_dcefclk",
+            Self::VCLK => "pp_dpm_vclk",
+            Self::DCLK => "pp_dpm_dclk",
+        }
+    }
+}
+
+impl fmt::Display for DpmClockType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DpmLevel {
+    pub index: Option<u32>, // None: "S:" (deep sleep level on APUs)
+    pub mhz: u32,
+    pub is_current: bool,
+}
+
+impl fmt::Display for DpmLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.index {
+            Some(i) => write!(f, "{i}")?,
+            None => write!(f, "S")?,
+        }
+
+        write!(f, ": {}MHz", self.mhz)?;
+
+        if self.is_current {
+            write!(f, " *")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DpmClock {
+    pub type_: DpmClockType,
+    pub levels: Vec<DpmLevel>,
+}
+
+impl DpmClock {
+    pub fn get_from_sysfs<P: AsRef<Path>>(sysfs_path: P, type_: DpmClockType) -> Option<Self> {
+        let s = std::fs::read_to_string(sysfs_path.as_ref().join(type_.file_name())).ok()?;
+
+        Self::parse(type_, &s)
+    }
+
+    // "0: 500Mhz \n1: 2500Mhz *\n"
+    pub fn parse(type_: DpmClockType, s: &str) -> Option<Self> {
+        let levels: Vec<DpmLevel> = s.lines().filter_map(|line| {
+            let (index, val) = line.split_once(':')?;
+            let index = match index.trim() {
+                "S" => None,
+                i => Some(i.parse::<u32>().ok()?),
+            };
+            let val = val.trim();
+            let is_current = val.ends_with('*');
+            let mhz = val
+                .trim_end_matches('*')
+                .trim_end()
+                .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+                .parse::<u32>()
+                .ok()?;
+
+            Some(DpmLevel { index, mhz, is_current })
+        }).collect();
+
+        if levels.is_empty() {
+            return None;
+        }
+
+        Some(Self { type_, levels })
+    }
+
+    pub fn current_level(&self) -> Option<&DpmLevel> {
+        self.levels.iter().find(|level| level.is_current)
+    }
+}
+
+// time spent in each DPM level
+#[derive(Debug, Clone)]
+pub struct DpmResidency {
+    pub type_: DpmClockType,
+    pub levels: Vec<DpmLevel>,
+    pub time: Vec<Duration>,
+    pub total: Duration,
+}
+
+impl DpmResidency {
+    pub fn new(clk: &DpmClock) -> Self {
+        Self {
+            type_: clk.type_,
+            levels: clk.levels.clone(),
+            time: vec![Duration::ZERO; clk.levels.len()],
+            total: Duration::ZERO,
+        }
+    }
+
+    fn add(&mut self, clk: &DpmClock, interval: Duration) {
+        // the DPM table has been changed (e.g. OverDrive)
+        if self.levels.len() != clk.levels.len() {
+            *self = Self::new(clk);
+        }
+
+        // with fine-grained DPM, the middle level reports the current clock
+        self.levels.clone_from(&clk.levels);
+
+        let Some(pos) = clk.levels.iter().position(|level| level.is_current) else { return };
+
+        self.time[pos] += interval;
+        self.total += interval;
+    }
+
+    pub fn percentages(&self) -> Vec<f64> {
+        if self.total.is_zero() {
+            return vec![0.0; self.time.len()];
+        }
+
+        let total = self.total.as_secs_f64();
+
+        self.time.iter().map(|t| t.as_secs_f64() * 100.0 / total).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DpmLevels {
+    sysfs_path: PathBuf,
+    pub clocks: Vec<DpmClock>,
+    pub residency: Vec<DpmResidency>,
+}
+
+impl DpmLevels {
+    pub fn get_from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let sysfs_path = sysfs_path.into();
+        let clocks = Self::read_clocks(&sysfs_path);
+
+        if clocks.is_empty() {
+            return None;
+        }
+
+        let residency = clocks.iter().map(DpmResidency::new).collect();
+
+        Some(Self { sysfs_path, clocks, residency })
+    }
+
+    fn read_clocks(sysfs_path: &Path) -> Vec<DpmClock> {
+        DpmClockType::ALL
+            .iter()
+            .filter_map(|type_| DpmClock::get_from_sysfs(sysfs_path, *type_))
+            .collect()
+    }
+
+    pub fn update(&mut self, interval: Duration) {
+        self.clocks = Self::read_clocks(&self.sysfs_path);
+
+        for clk in &self.clocks {
+            if let Some(r) = self.residency.iter_mut().find(|r| r.type_ == clk.type_) {
+                r.add(clk, interval);
+            } else {
+                let mut r = DpmResidency::new(clk);
+                r.add(clk, interval);
+                self.residency.push(r);
+            }
+        }
+    }
+
+    pub fn get(&self, type_: DpmClockType) -> Option<&DpmClock> {
+        self.clocks.iter().find(|clk| clk.type_ == type_)
+    }
+}
+
+#[test]
+fn test_dpm_clock_parse() {
+    let s = std::fs::read_to_string("src/stat/pp_dpm_sclk_sample.txt").unwrap();
+    let clk = DpmClock::parse(DpmClockType::SCLK, &s).unwrap();
+
+    assert_eq!(clk.levels, [
+        DpmLevel { index: Some(0), mhz: 500, is_current: false },
+        DpmLevel { index: Some(1), mhz: 2254, is_current: true },
+        DpmLevel { index: Some(2), mhz: 2600, is_current: false },
+    ]);
+    assert_eq!(clk.current_level().map(|l| l.mhz), Some(2254));
+
+    // deep sleep level on APUs
+    let s = std::fs::read_to_string("src/stat/pp_dpm_sclk_apu_sample.txt").unwrap();
+    let clk = DpmClock::parse(DpmClockType::SCLK, &s).unwrap();
+
+    assert_eq!(clk.levels.len(), 4);
+    assert_eq!(clk.levels[0], DpmLevel { index: None, mhz: 400, is_current: false });
+    assert_eq!(clk.levels[0].to_string(), "S: 400MHz");
+    assert_eq!(clk.levels[2].to_string(), "1: 1498MHz *");
+
+    assert!(DpmClock::parse(DpmClockType::MCLK, "").is_none());
+    assert!(DpmClock::parse(DpmClockType::MCLK, "0: 2.5GT/s, x16 *\n").is_none());
+}
+
+#[test]
+fn test_dpm_residency() {
+    let clk = |cur: usize, n: usize| DpmClock {
+        type_: DpmClockType::SCLK,
+        levels: (0..n).map(|i| DpmLevel { index: Some(i as u32), mhz: 500 * (i as u32 + 1), is_current: i == cur }).collect(),
+    };
+    let mut r = DpmResidency::new(&clk(0, 3));
+
+    assert_eq!(r.percentages(), [0.0; 3]);
+
+    r.add(&clk(0, 3), Duration::from_millis(300));
+    r.add(&clk(2, 3), Duration::from_millis(100));
+    assert_eq!(r.total, Duration::from_millis(400));
+    assert_eq!(r.percentages(), [75.0, 0.0, 25.0]);
+
+    // the DPM table has been changed
+    r.add(&clk(1, 2), Duration::from_millis(100));
+    assert_eq!(r.time, [Duration::ZERO, Duration::from_millis(100)]);
+    assert_eq!(r.percentages(), [0.0, 100.0]);
+}
@@ -0,0 +1,177 @@
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/include/kgd_pp_interface.h
+
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use crate::AMDGPU::{GpuMetrics, MetricsInfo};
+use super::{parse_hwmon, Sensors};
+
+// resolution of `energy_accumulator`: 15.259 uJ
+const ENERGY_ACC_UNIT: f64 = 15.259e-6; // J
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnergySource {
+    GpuMetrics, // energy_accumulator
+    Hwmon, // integrating power1_average or power1_input
+}
+
+impl fmt::Display for EnergySource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::GpuMetrics => write!(f, "energy_accumulator"),
+            Self::Hwmon => write!(f, "hwmon power"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EnergyMeter {
+    pub session: f64, // J
+    pub counter: f64, // J, resettable
+    pub source: Option<EnergySource>,
+    pre_acc: Option<u64>,
+    pre_time: Option<Instant>,
+}
+
+impl EnergyMeter {
+    fn read_hwmon_power_uw(hwmon_path: &Path) -> Option<u64> {
+        parse_hwmon::<u64, _>(hwmon_path.join("power1_average"))
+            .filter(|v| *v != 0)
+            .or_else(|| parse_hwmon::<u64, _>(hwmon_path.join("power1_input")))
+    }
+
+    pub fn update(
+        &mut self,
+        metrics: &Option<GpuMetrics>,
+        sensors: &Option<Sensors>,
+        interval: Duration,
+    ) {
+        let acc = metrics.as_ref().and_then(|m| m.get_energy_accumulator());
+
+        self.update_with(
+            Instant::now(),
+            acc,
+            || sensors.as_ref().and_then(|s| Self::read_hwmon_power_uw(&s.hwmon_path)),
+            interval,
+        );
+    }
+
+    fn update_with<F: FnOnce() -> Option<u64>>(
+        &mut self,
+        now: Instant,
+        acc: Option<u64>,
+        power_uw: F,
+        interval: Duration,
+    ) {
+        let acc = acc.filter(|v| *v != 0 && *v != u64::MAX);
+
+        let joules = match (self.pre_acc, acc) {
+            (Some(pre), Some(cur)) if pre <= cur => {
+                self.source = Some(EnergySource::GpuMetrics);
+
+                Some((cur - pre) as f64 * ENERGY_ACC_UNIT)
+            },
+            // the first sample, wraparound or not supported
+            _ => self.pre_time.and_then(|pre_time| {
+                let uw = power_uw()?;
+
+                if acc.is_none() {
+                    self.source = Some(EnergySource::Hwmon);
+                }
+
+                // The TUI updates only the selected device, so the gap since the previous sample
+                // can be much longer than the interval. The current power is not representative
+                // of the whole gap.
+                let gap = now.duration_since(pre_time).min(interval);
+
+                Some(uw as f64 / 1_000_000.0 * gap.as_secs_f64())
+            }),
+        };
+
+        if let Some(j) = joules {
+            self.session += j;
+            self.counter += j;
+        }
+
+        self.pre_acc = acc;
+        self.pre_time = Some(now);
+    }
+
+    // The device handle is dropped for runtime PM.
+    // The power consumption is treated as 0 W only while the device is actually suspended,
+    // otherwise (e.g. within the autosuspend delay) the interval is excluded.
+    pub fn update_for_idle(&mut self, sysfs_path: &Path) {
+        let suspended = std::fs::read_to_string(sysfs_path.join("power/runtime_status"))
+            .is_ok_and(|s| s.starts_with("suspended"));
+
+        self.pre_acc = None;
+        self.pre_time = suspended.then(Instant::now);
+    }
+
+    pub fn reset(&mut self) {
+        self.counter = 0.0;
+    }
+
+    pub fn session_wh(&self) -> f64 {
+        self.session / 3600.0
+    }
+
+    pub fn counter_wh(&self) -> f64 {
+        self.counter / 3600.0
+    }
+}
+
+#[test]
+fn test_energy_update_for_idle() {
+    let sysfs = crate::test_util::FakeSysfs::new("energy");
+    let dir = sysfs.path();
+    std::fs::create_dir_all(dir.join("power")).unwrap();
+
+    let mut energy = EnergyMeter::default();
+
+    // within the autosuspend delay, the interval is not counted as 0 W
+    std::fs::write(dir.join("power/runtime_status"), "active\n").unwrap();
+    energy.update_for_idle(dir);
+    assert!(energy.pre_time.is_none());
+
+    std::fs::write(dir.join("power/runtime_status"), "suspended\n").unwrap();
+    energy.update_for_idle(dir);
+    assert!(energy.pre_time.is_some());
+    assert!(energy.pre_acc.is_none());
+}
+
+#[test]
+fn test_energy_update_with() {
+    let interval = Duration::from_secs(1);
+    let t0 = Instant::now();
+    let mut energy = EnergyMeter::default();
+
+    // the first sample
+    energy.update_with(t0, Some(1_000), || Some(100_000_000), interval);
+    assert_eq!(energy.session, 0.0);
+
+    // accumulator delta
+    energy.update_with(t0 + interval, Some(1_000 + 65_536), || panic!(), interval);
+    assert_eq!(energy.source, Some(EnergySource::GpuMetrics));
+    assert!((energy.session - 65_536.0 * ENERGY_ACC_UNIT).abs() < 1e-9);
+
+    // wraparound, integrate the hwmon power instead
+    let pre = energy.session;
+    energy.update_with(t0 + interval * 2, Some(10), || Some(50_000_000), interval);
+    assert!((energy.session - pre - 50.0).abs() < 1e-9);
+    assert_eq!(energy.source, Some(EnergySource::GpuMetrics));
+
+    // hwmon only, the gap is clamped to the interval
+    let mut energy = EnergyMeter::default();
+    energy.update_with(t0, None, || Some(100_000_000), interval);
+    energy.update_with(t0 + interval / 2, None, || Some(100_000_000), interval);
+    assert_eq!(energy.source, Some(EnergySource::Hwmon));
+    assert!((energy.session - 50.0).abs() < 1e-9);
+
+    energy.update_with(t0 + interval * 60, None, || Some(100_000_000), interval);
+    assert!((energy.session - 150.0).abs() < 1e-9);
+
+    energy.reset();
+    assert_eq!(energy.counter, 0.0);
+    assert!((energy.session_wh() - 150.0 / 3600.0).abs() < 1e-9);
+}
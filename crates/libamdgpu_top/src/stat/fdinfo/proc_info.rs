@@ -1,6 +1,7 @@
 use std::io::Read;
 use std::fs;
 use std::time::Duration;
+use std::sync::Arc;
 use std::path::{Path, PathBuf};
 use crate::DevicePath;
 
@@ -109,13 +110,18 @@ pub fn update_index(vec_info: &mut Vec<ProcInfo>, device_path: &DevicePath) {
 }
 
 pub fn spawn_update_index_thread(
-    device_paths: Vec<DevicePath>,
+    mut device_paths: Vec<DevicePath>,
     interval: u64,
 ) {
     let mut buf_index: Vec<ProcInfo> = Vec::new();
     let interval = Duration::from_secs(interval);
 
     std::thread::spawn(move || loop {
+        // stop updating the index of removed (hot-unplugged) devices
+        device_paths.retain(|device_path| Arc::strong_count(&device_path.arc_proc_index) > 1);
+
+        if device_paths.is_empty() { break }
+
         let all_proc = get_process_list();
 
         for device_path in &device_paths {
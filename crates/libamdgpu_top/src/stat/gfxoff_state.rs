@@ -1,20 +1,33 @@
 // ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/thermal.html#gfxoff
 
-// AMD APU/GPU exits GFXOFF state by reading the performance counter (GRBM, GRBM2),  
-// so useful only in SMI mode.
+// AMD APU/GPU exits GFXOFF state by reading the performance counter (GRBM, GRBM2),
+// so the perf counter sampler is paused while the APU is idle.
 
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::os::fd::{IntoRawFd, RawFd};
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::{Arc, Once};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::{Duration, Instant};
 use crate::PCI;
 
 const BASE: &str = "/sys/kernel/debug/dri";
+const MAX_RESIDENCY_LOGGING: usize = 16;
+
+// opened `amdgpu_gfxoff_residency` files where this process started logging,
+// kept as raw fds to stop the logging from the signal handlers
+static RESIDENCY_LOGGING_FDS: [AtomicI32; MAX_RESIDENCY_LOGGING] =
+    [const { AtomicI32::new(-1) }; MAX_RESIDENCY_LOGGING];
+static SET_EXIT_HOOKS: Once = Once::new();
 
 #[derive(Debug, Clone)]
 pub struct GfxoffMonitor {
     debug_dri_path: PathBuf,
     pub mode: GfxoffMode,
     pub status: GfxoffStatus,
+    pub residency: GfxoffResidency,
+    residency_logging: Option<Arc<ResidencyLogging>>,
 }
 
 impl GfxoffMonitor {
@@ -22,8 +35,27 @@ impl GfxoffMonitor {
         let debug_dri_path = pci_bus.get_debug_dri_path()?;
         let mode = GfxoffMode::get_with_debug_dri_path(&debug_dri_path)?;
         let status = GfxoffStatus::get_with_debug_dri_path(&debug_dri_path)?;
+        let mut residency = GfxoffResidency::default();
+
+        residency.update(status, Instant::now());
+        residency.update_debugfs(&debug_dri_path);
 
-        Ok(Self { debug_dri_path, mode, status })
+        Ok(Self { debug_dri_path, mode, status, residency, residency_logging: None })
+    }
+
+    /// Start logging of `amdgpu_gfxoff_residency`, it is stopped when the last clone is dropped.
+    /// If the logging seems to be already running (residency > 0), it is left as it is.
+    pub fn start_residency_logging(&mut self) {
+        // There is no interface to read whether the logging is running, so this is a guess.
+        // The residency of the last logging interval is non-zero after another process
+        // (or a previous run that was killed) started the logging.
+        if self.residency_logging.is_some()
+        || self.residency.debugfs_residency.is_some_and(|v| v > 0.0)
+        {
+            return;
+        }
+
+        self.residency_logging = ResidencyLogging::start(&self.debug_dri_path).map(Arc::new);
     }
 
     pub fn update(&mut self) -> io::Result<()> {
@@ -34,8 +66,208 @@ impl GfxoffMonitor {
             GfxoffStatus::get_with_debug_dri_path(&self.debug_dri_path)?
         };
 
+        self.residency.update(self.status, Instant::now());
+        self.residency.update_debugfs(&self.debug_dri_path);
+
         Ok(())
     }
+
+    // The device is in runtime suspend, debugfs access would wake it up.
+    pub fn update_for_idle(&mut self) {
+        self.residency.pre = None;
+    }
+}
+
+// write 1 to start, 0 to stop
+// ref: drivers/gpu/drm/amd/amdgpu/amdgpu_debugfs.c
+#[derive(Debug)]
+struct ResidencyLogging {
+    fd: RawFd,
+}
+
+impl ResidencyLogging {
+    fn start(debug_dri_path: &Path) -> Option<Self> {
+        let path = debug_dri_path.join("amdgpu_gfxoff_residency");
+        let fd = fs::OpenOptions::new().write(true).open(path).ok()?.into_raw_fd();
+
+        if !Self::write(fd, 1) {
+            unsafe { libc::close(fd); }
+            return None;
+        }
+
+        let registered = RESIDENCY_LOGGING_FDS
+            .iter()
+            .any(|slot| slot.compare_exchange(-1, fd, Ordering::SeqCst, Ordering::SeqCst).is_ok());
+
+        if !registered {
+            Self::stop(fd);
+            return None;
+        }
+
+        SET_EXIT_HOOKS.call_once(set_exit_hooks);
+
+        Some(Self { fd })
+    }
+
+    // async-signal-safe
+    fn write(fd: RawFd, value: u32) -> bool {
+        let buf = value.to_le_bytes();
+
+        let len = unsafe { libc::pwrite(fd, buf.as_ptr() as *const libc::c_void, buf.len(), 0) };
+
+        len == buf.len() as isize
+    }
+
+    fn stop(fd: RawFd) {
+        Self::write(fd, 0);
+        unsafe { libc::close(fd); }
+    }
+}
+
+// the device is removed
+impl Drop for ResidencyLogging {
+    fn drop(&mut self) {
+        for slot in &RESIDENCY_LOGGING_FDS {
+            if slot.compare_exchange(self.fd, -1, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+                Self::stop(self.fd);
+            }
+        }
+    }
+}
+
+/// Stop the logging of `amdgpu_gfxoff_residency` started by this process.
+/// The apps live in the update threads and are not dropped on exit, so call this before exiting.
+/// This is async-signal-safe, and is also called from the panic hook and the signal handlers.
+pub fn stop_gfxoff_residency_logging() {
+    for slot in &RESIDENCY_LOGGING_FDS {
+        let fd = slot.swap(-1, Ordering::SeqCst);
+
+        if fd >= 0 {
+            ResidencyLogging::stop(fd);
+        }
+    }
+}
+
+extern "C" fn stop_residency_logging_and_raise(sig: libc::c_int) {
+    stop_gfxoff_residency_logging();
+
+    unsafe {
+        libc::signal(sig, libc::SIG_DFL);
+        libc::raise(sig);
+    }
+}
+
+// TUI (cursive) and GUI (eframe) may exit the process without returning to main.
+fn set_exit_hooks() {
+    let pre_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        stop_gfxoff_residency_logging();
+        pre_hook(info);
+    }));
+
+    for sig in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+        let mut old: libc::sigaction = unsafe { std::mem::zeroed() };
+
+        // keep the handler set by the app (`summary::set_terminate_handler`)
+        if unsafe { libc::sigaction(sig, std::ptr::null(), &mut old) } != 0
+        || old.sa_sigaction != libc::SIG_DFL
+        {
+            continue;
+        }
+
+        unsafe { libc::signal(sig, stop_residency_logging_and_raise as *const () as libc::sighandler_t); }
+    }
+}
+
+/// Time spent in each `GfxoffStatus` and the status changes, sampled at every update (refresh period).
+/// Short GFXOFF entries/exits between samples are not seen, use `entry_count` for the real count.
+#[derive(Debug, Clone, Default)]
+pub struct GfxoffResidency {
+    pub in_gfxoff: Duration,
+    pub out_gfxoff: Duration,
+    pub not_in_gfxoff: Duration,
+    pub into_gfxoff: Duration,
+    pub unknown: Duration,
+    pub observed_status_changes: u64,
+    pub entry_count: Option<u64>, // `amdgpu_gfxoff_count`, since boot
+    pub base_entry_count: Option<u64>,
+    pub debugfs_residency: Option<f64>, // `amdgpu_gfxoff_residency`, % in the last logging interval
+    pre: Option<(GfxoffStatus, Instant)>,
+}
+
+impl GfxoffResidency {
+    pub const STATUS_LIST: [GfxoffStatus; 4] = [
+        GfxoffStatus::InGFXOFF,
+        GfxoffStatus::OutGFXOFF,
+        GfxoffStatus::NotInGFXOFF,
+        GfxoffStatus::IntoGFXOFF,
+    ];
+
+    // the whole time since the previous sample is counted as the previous status
+    pub fn update(&mut self, status: GfxoffStatus, now: Instant) {
+        if let Some((pre_status, pre_time)) = self.pre {
+            *self.time_mut(pre_status) += now.saturating_duration_since(pre_time);
+
+            if pre_status != status {
+                self.observed_status_changes += 1;
+            }
+        }
+
+        self.pre = Some((status, now));
+    }
+
+    fn update_debugfs(&mut self, debug_dri_path: &Path) {
+        self.entry_count = read_gfxoff_u64(debug_dri_path.join("amdgpu_gfxoff_count")).ok();
+
+        if self.base_entry_count.is_none() {
+            self.base_entry_count = self.entry_count;
+        }
+
+        // residency % multiplied by 100
+        self.debugfs_residency = read_gfxoff(debug_dri_path.join("amdgpu_gfxoff_residency"))
+            .ok()
+            .map(|v| v as f64 / 100.0);
+    }
+
+    fn time_mut(&mut self, status: GfxoffStatus) -> &mut Duration {
+        match status {
+            GfxoffStatus::InGFXOFF => &mut self.in_gfxoff,
+            GfxoffStatus::OutGFXOFF => &mut self.out_gfxoff,
+            GfxoffStatus::NotInGFXOFF => &mut self.not_in_gfxoff,
+            GfxoffStatus::IntoGFXOFF => &mut self.into_gfxoff,
+            GfxoffStatus::Unknown(_) => &mut self.unknown,
+        }
+    }
+
+    pub fn time(&self, status: GfxoffStatus) -> Duration {
+        match status {
+            GfxoffStatus::InGFXOFF => self.in_gfxoff,
+            GfxoffStatus::OutGFXOFF => self.out_gfxoff,
+            GfxoffStatus::NotInGFXOFF => self.not_in_gfxoff,
+            GfxoffStatus::IntoGFXOFF => self.into_gfxoff,
+            GfxoffStatus::Unknown(_) => self.unknown,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.in_gfxoff + self.out_gfxoff + self.not_in_gfxoff + self.into_gfxoff + self.unknown
+    }
+
+    pub fn percentage(&self, status: GfxoffStatus) -> f64 {
+        let total = self.total().as_secs_f64();
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        self.time(status).as_secs_f64() * 100.0 / total
+    }
+
+    /// GFXOFF entries since the start, from `amdgpu_gfxoff_count`
+    pub fn entries(&self) -> Option<u64> {
+        Some(self.entry_count?.saturating_sub(self.base_entry_count?))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -98,6 +330,12 @@ impl GfxoffStatus {
     }
 }
 
+impl std::fmt::Display for GfxoffStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
 impl From<u32> for GfxoffStatus {
     fn from(val: u32) -> Self {
         match val {
@@ -112,9 +350,57 @@ impl From<u32> for GfxoffStatus {
 
 fn read_gfxoff<P: Into<PathBuf>>(path: P) -> io::Result<u32> {
     let mut buf = [0xFFu8; 4];
-    
+
     let mut f = fs::File::open(path.into())?;
     f.read_exact(&mut buf)?;
 
     Ok(u32::from_le_bytes(buf))
 }
+
+// `amdgpu_gfxoff_count` is u64
+fn read_gfxoff_u64<P: Into<PathBuf>>(path: P) -> io::Result<u64> {
+    let mut buf = [0xFFu8; 8];
+
+    let mut f = fs::File::open(path.into())?;
+    f.read_exact(&mut buf)?;
+
+    Ok(u64::from_le_bytes(buf))
+}
+
+#[test]
+fn test_gfxoff_residency() {
+    let mut r = GfxoffResidency::default();
+    let t = Instant::now();
+
+    r.update(GfxoffStatus::NotInGFXOFF, t);
+    r.update(GfxoffStatus::InGFXOFF, t + Duration::from_millis(250));
+    r.update(GfxoffStatus::InGFXOFF, t + Duration::from_millis(500));
+    r.update(GfxoffStatus::NotInGFXOFF, t + Duration::from_millis(1000));
+
+    assert_eq!(r.not_in_gfxoff, Duration::from_millis(250));
+    assert_eq!(r.in_gfxoff, Duration::from_millis(750));
+    assert_eq!(r.observed_status_changes, 2);
+    assert_eq!(r.percentage(GfxoffStatus::InGFXOFF), 75.0);
+
+    r.entry_count = Some(110);
+    r.base_entry_count = Some(100);
+    assert_eq!(r.entries(), Some(10));
+}
+
+#[test]
+fn test_residency_logging() {
+    let sysfs = crate::test_util::FakeSysfs::new("gfxoff");
+    let dir = sysfs.path();
+    let path = dir.join("amdgpu_gfxoff_residency");
+    fs::write(&path, [0; 4]).unwrap();
+
+    let logging = ResidencyLogging::start(dir).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), 1u32.to_le_bytes());
+
+    stop_gfxoff_residency_logging();
+    assert_eq!(fs::read(&path).unwrap(), 0u32.to_le_bytes());
+    assert!(RESIDENCY_LOGGING_FDS.iter().all(|slot| slot.load(Ordering::SeqCst) < 0));
+
+    // already stopped
+    drop(logging);
+}
@@ -90,6 +90,12 @@ pub(crate) const GFX12_GRBM2_INDEX: &[(&str, usize)] = &[
 mod perf_counter;
 pub use perf_counter::*;
 
+mod pc_config;
+pub use pc_config::*;
+
+mod pc_sampler;
+pub use pc_sampler::*;
+
 mod fdinfo;
 pub use fdinfo::*;
 
@@ -108,6 +114,24 @@ pub use gfxoff_state::*;
 mod gpu_activity;
 pub use gpu_activity::*;
 
+mod dpm_levels;
+pub use dpm_levels::*;
+
+mod ras;
+pub use ras::*;
+
+mod throttle_log;
+pub use throttle_log::*;
+
+mod energy;
+pub use energy::*;
+
+mod proc_power;
+pub use proc_power::*;
+
+mod display;
+pub use display::*;
+
 pub mod gpu_metrics_util;
 
 pub(crate) fn parse_hwmon<T: std::str::FromStr, P: Into<std::path::PathBuf>>(path: P) -> Option<T> {
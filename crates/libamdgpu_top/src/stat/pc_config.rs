@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::Path;
+use libdrm_amdgpu_sys::AMDGPU::CHIP_CLASS;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PCRegisterDef {
+    pub name: String,
+    pub offset: u32, // dword offset, the same as `GRBM_OFFSET`
+    pub bits: Vec<(String, usize)>,
+    pub min_chip_class: Option<CHIP_CLASS>,
+}
+
+/// Additional status registers shown as performance counters
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PCConfig {
+    pub registers: Vec<PCRegisterDef>,
+}
+
+impl PCConfig {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let s = fs::read_to_string(path).map_err(|e| format!("{path:?}: {e}"))?;
+
+        Self::parse(&s)
+    }
+
+    /// ```text
+    /// [SRBM_STATUS]                 # panel name
+    /// offset = 0x394                # dword offset of the register
+    /// bits = VMC_BUSY:8, MCB_BUSY:9, IH_BUSY:17, UVD_BUSY:19
+    ///
+    /// [CP_STAT@GFX10]               # overrides [CP_STAT] for GFX10 and later
+    /// offset = 0x21A0
+    /// bits = PFP_BUSY:15, MEQ_BUSY:16, ME_BUSY:17, CP_BUSY:31
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let err = |msg: String| format!("line {}: {msg}", i+1);
+            if line.is_empty() { continue }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let (name, min_chip_class) = match section.split_once('@') {
+                    Some((name, class)) => {
+                        let class = parse_chip_class(class.trim())
+                            .ok_or_else(|| err(format!("Unknown chip class: {class:?}")))?;
+                        (name.trim(), Some(class))
+                    },
+                    None => (section.trim(), None),
+                };
+
+                if name.is_empty() {
+                    return Err(err("Empty register name".to_string()));
+                }
+
+                config.registers.push(PCRegisterDef {
+                    name: name.to_string(),
+                    offset: 0,
+                    bits: Vec::new(),
+                    min_chip_class,
+                });
+
+                continue;
+            }
+
+            let (key, val) = line.split_once('=').ok_or_else(|| err(format!("Invalid line: {line:?}")))?;
+            let (key, val) = (key.trim(), val.trim());
+
+            let Some(reg) = config.registers.last_mut() else {
+                return Err(err(format!("{key:?} must be in a register section")));
+            };
+
+            match key {
+                "offset" => reg.offset = parse_u32(val)
+                    .ok_or_else(|| err(format!("Invalid offset: {val:?}")))?,
+                "bits" => reg.bits = parse_bits(val).map_err(err)?,
+                _ => return Err(err(format!("Unknown key: {key:?}"))),
+            }
+        }
+
+        if let Some(reg) = config.registers.iter().find(|r| r.offset == 0 || r.bits.is_empty()) {
+            return Err(format!("[{}]: missing \"offset\" or \"bits\"", reg.name));
+        }
+
+        Ok(config)
+    }
+
+    /// The registers for the chip class, `[NAME@CLASS]` with the highest matching class wins
+    pub fn registers_for(&self, chip_class: CHIP_CLASS) -> Vec<&PCRegisterDef> {
+        let mut list: Vec<&PCRegisterDef> = Vec::new();
+
+        for reg in &self.registers {
+            if reg.min_chip_class.is_some_and(|min| chip_class < min) { continue }
+
+            match list.iter_mut().find(|r| r.name == reg.name) {
+                Some(r) => if r.min_chip_class <= reg.min_chip_class { *r = reg },
+                None => list.push(reg),
+            }
+        }
+
+        list
+    }
+}
+
+fn parse_chip_class(s: &str) -> Option<CHIP_CLASS> {
+    let class = match s.to_ascii_uppercase().as_str() {
+        "GFX6" => CHIP_CLASS::GFX6,
+        "GFX7" => CHIP_CLASS::GFX7,
+        "GFX8" => CHIP_CLASS::GFX8,
+        "GFX9" => CHIP_CLASS::GFX9,
+        "GFX10" => CHIP_CLASS::GFX10,
+        "GFX10_3" | "GFX10.3" => CHIP_CLASS::GFX10_3,
+        "GFX11" => CHIP_CLASS::GFX11,
+        "GFX12" => CHIP_CLASS::GFX12,
+        _ => return None,
+    };
+
+    Some(class)
+}
+
+fn parse_u32(s: &str) -> Option<u32> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn parse_bits(s: &str) -> Result<Vec<(String, usize)>, String> {
+    s.split(',')
+        .map(|bit| {
+            let (name, index) = bit.split_once(':').ok_or_else(|| format!("Invalid bit: {bit:?}"))?;
+            let index = index.trim().parse::<usize>().ok()
+                .filter(|i| *i < 32)
+                .ok_or_else(|| format!("Invalid bit index: {bit:?}"))?;
+
+            Ok((name.trim().to_string(), index))
+        })
+        .collect()
+}
+
+#[test]
+fn test_pc_config_parse() {
+    let config = PCConfig::parse(concat!(
+        "[SRBM_STATUS]\n",
+        "offset = 0x394\n",
+        "bits = VMC_BUSY:8, MCB_BUSY:9 # comment\n",
+        "\n",
+        "[CP_STAT]\n",
+        "offset = 8608\n",
+        "bits = CP_BUSY:31\n",
+        "\n",
+        "[CP_STAT@GFX10]\n",
+        "offset = 0x21A0\n",
+        "bits = PFP_BUSY:15, CP_BUSY:31\n",
+    )).unwrap();
+
+    assert_eq!(config.registers.len(), 3);
+    assert_eq!(config.registers[0].offset, 0x394);
+    assert_eq!(config.registers[0].bits, [("VMC_BUSY".to_string(), 8), ("MCB_BUSY".to_string(), 9)]);
+
+    let gfx9 = config.registers_for(CHIP_CLASS::GFX9);
+    assert_eq!(gfx9.len(), 2);
+    assert_eq!(gfx9[1].bits.len(), 1);
+
+    let gfx11 = config.registers_for(CHIP_CLASS::GFX11);
+    assert_eq!(gfx11.len(), 2);
+    assert_eq!(gfx11[1].min_chip_class, Some(CHIP_CLASS::GFX10));
+    assert_eq!(gfx11[1].bits.len(), 2);
+}
+
+#[test]
+fn test_pc_config_parse_invalid() {
+    assert!(PCConfig::parse("offset = 0x394\n").is_err());
+    assert!(PCConfig::parse("[A]\noffset = 0x394\nbits = X:32\n").is_err());
+    assert!(PCConfig::parse("[A@GFX99]\noffset = 0x394\nbits = X:1\n").is_err());
+    assert!(PCConfig::parse("[A]\nbits = X:1\n").is_err());
+}
@@ -0,0 +1,157 @@
+use crate::DevicePath;
+use crate::AMDGPU::DeviceHandle;
+use super::PerfCounter;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+// Perf counters (GRBM, GRBM2, ...) are sampled in a separate thread,
+// so the sampling rate and window do not depend on the refresh period of TUI/GUI/JSON.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PCSamplerConfig {
+    pub interval: Duration, // between samples
+    pub window: Duration,
+}
+
+impl Default for PCSamplerConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(10), // 100 Hz
+            window: Duration::from_secs(1),
+        }
+    }
+}
+
+impl PCSamplerConfig {
+    pub const MAX_RATE: u32 = 10_000; // Hz
+
+    pub fn set_rate(&mut self, hz: u32) {
+        let hz = hz.clamp(1, Self::MAX_RATE);
+        self.interval = Duration::from_secs(1) / hz;
+    }
+
+    pub fn rate(&self) -> u32 {
+        (Duration::from_secs(1).as_nanos() / self.interval.as_nanos().max(1)) as u32
+    }
+
+    /// The number of samples in a window
+    pub fn window_samples(&self) -> usize {
+        ((self.window.as_nanos() / self.interval.as_nanos().max(1)) as usize).max(1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PCSampler {
+    pub pcs: Vec<PerfCounter>,
+    pub config: PCSamplerConfig,
+    pub samples: usize, // in the current window
+    pub pause: bool,
+}
+
+impl PCSampler {
+    pub fn spawn_sampler_thread(
+        device_path: DevicePath,
+        pcs: Vec<PerfCounter>,
+        config: PCSamplerConfig,
+    ) -> Arc<Mutex<Self>> {
+        let arc = Arc::new(Mutex::new(Self { pcs: pcs.clone(), config, samples: 0, pause: false }));
+        let weak = Arc::downgrade(&arc);
+        let mut buf_pcs = pcs;
+
+        for pc in buf_pcs.iter_mut() {
+            pc.set_window(config.window_samples());
+        }
+
+        std::thread::Builder::new()
+            .name(format!("pc_sampler {}", device_path.pci))
+            .spawn(move || sampler_loop(weak, device_path, buf_pcs, config.interval))
+            .unwrap();
+
+        arc
+    }
+}
+
+fn sampler_loop(
+    weak: Weak<Mutex<PCSampler>>,
+    device_path: DevicePath,
+    mut buf_pcs: Vec<PerfCounter>,
+    interval: Duration,
+) {
+    // not shared with `AppAmdgpuTop`, so it can be dropped for runtime PM independently
+    let mut amdgpu_dev: Option<DeviceHandle> = None;
+    let mut next = Instant::now();
+
+    loop {
+        // the app (device) has been dropped
+        let Some(arc) = weak.upgrade() else { return };
+        let pause = arc.lock().map(|s| s.pause).unwrap_or(true);
+
+        if pause {
+            if amdgpu_dev.take().is_some() {
+                for pc in buf_pcs.iter_mut() {
+                    pc.clear_pc();
+                    pc.update_pc_usage();
+                }
+
+                publish(&arc, &buf_pcs);
+            }
+
+            drop(arc);
+            std::thread::sleep(interval.max(Duration::from_millis(100)));
+            next = Instant::now();
+            continue;
+        }
+
+        if amdgpu_dev.is_none() {
+            amdgpu_dev = device_path.init().ok();
+        }
+
+        if let Some(dev) = &amdgpu_dev {
+            for pc in buf_pcs.iter_mut() {
+                pc.read_reg(dev);
+                pc.update_pc_usage();
+            }
+
+            publish(&arc, &buf_pcs);
+        }
+
+        drop(arc);
+
+        next += interval;
+        let now = Instant::now();
+
+        if let Some(d) = next.checked_duration_since(now) {
+            std::thread::sleep(d);
+        } else {
+            // too slow to keep the rate, skip the missed samples
+            next = now;
+        }
+    }
+}
+
+fn publish(arc: &Mutex<PCSampler>, buf_pcs: &[PerfCounter]) {
+    let Ok(mut sampler) = arc.lock() else { return };
+
+    for (dst, src) in sampler.pcs.iter_mut().zip(buf_pcs) {
+        for (d, s) in dst.pc_index.iter_mut().zip(&src.pc_index) {
+            d.usage = s.usage;
+        }
+    }
+
+    sampler.samples = buf_pcs.first().map_or(0, |pc| pc.samples());
+}
+
+#[test]
+fn test_pc_sampler_config() {
+    let mut config = PCSamplerConfig::default();
+    assert_eq!(config.rate(), 100);
+    assert_eq!(config.window_samples(), 100);
+
+    config.set_rate(1000);
+    config.window = Duration::from_millis(250);
+    assert_eq!(config.interval, Duration::from_millis(1));
+    assert_eq!(config.window_samples(), 250);
+
+    config.window = Duration::ZERO;
+    assert_eq!(config.window_samples(), 1);
+}
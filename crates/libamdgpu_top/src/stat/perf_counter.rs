@@ -4,7 +4,8 @@ use libdrm_amdgpu_sys::AMDGPU::{
     GRBM_OFFSET,
     GRBM2_OFFSET,
 };
-use crate::stat;
+use crate::stat::{self, PCRegisterDef};
+use std::collections::VecDeque;
 
 #[derive(Clone, Debug)]
 pub struct PCIndex {
@@ -16,6 +17,7 @@ pub struct PCIndex {
 #[derive(Clone, Debug)]
 pub struct PerfCounter {
     pub pc_type: PCType,
+    pub name: String,
     bits: PCAcc,
     pub pc_index: Vec<PCIndex>,
 }
@@ -43,13 +45,23 @@ impl PerfCounter {
                     stat::GRBM2_INDEX
                 }
             },
+            PCType::Custom(_) => &[],
         };
 
-        let pc_index = index
-            .iter()
-            .map(|(name, idx)| {
-                let index = *idx;
+        Self::new(pc_type, pc_type.to_string(), index.iter().map(|(name, idx)| (*name, *idx)))
+    }
+
+    pub fn from_register_def(def: &PCRegisterDef) -> Self {
+        Self::new(
+            PCType::Custom(def.offset),
+            def.name.clone(),
+            def.bits.iter().map(|(name, idx)| (name.as_str(), *idx)),
+        )
+    }
 
+    fn new<'a, I: Iterator<Item = (&'a str, usize)>>(pc_type: PCType, name: String, index: I) -> Self {
+        let pc_index = index
+            .map(|(name, index)| {
                 assert!(index < 32);
 
                 PCIndex {
@@ -62,6 +74,7 @@ impl PerfCounter {
 
         Self {
             pc_type,
+            name,
             bits: PCAcc::default(),
             pc_index,
         }
@@ -77,6 +90,15 @@ impl PerfCounter {
         self.bits.clear();
     }
 
+    /// The number of samples to keep, the oldest sample is dropped after that
+    pub fn set_window(&mut self, samples: usize) {
+        self.bits.set_window(samples);
+    }
+
+    pub fn samples(&self) -> usize {
+        self.bits.regs.len()
+    }
+
     pub fn update_pc_usage(&mut self) {
         for PCIndex { name: _name, index, usage } in self.pc_index.iter_mut() {
             *usage = self.bits.get(*index);
@@ -90,12 +112,16 @@ impl PerfCounter {
 pub enum PCType {
     GRBM,
     GRBM2,
+    Custom(u32), // register offset from `PCConfig`
 }
 
 use std::fmt;
 impl fmt::Display for PCType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::Custom(offset) => write!(f, "Custom({offset:#X})"),
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -104,14 +130,16 @@ impl PCType {
         match self {
             Self::GRBM => GRBM_OFFSET,
             Self::GRBM2 => GRBM2_OFFSET,
+            Self::Custom(offset) => *offset,
         }
     }
 
     pub fn check_reg_offset(&self, amdgpu_dev: &DeviceHandle) -> bool {
         let offset = self.offset();
         let reg_name = match self {
-            Self::GRBM => "GRBM_STATUS",
-            Self::GRBM2 => "GRBM2_STATUS2",
+            Self::GRBM => "GRBM_STATUS".to_string(),
+            Self::GRBM2 => "GRBM2_STATUS2".to_string(),
+            Self::Custom(_) => self.to_string(),
         };
 
         amdgpu_dev.read_mm_registers(offset).map_or_else(|err| {
@@ -121,39 +149,92 @@ impl PCType {
     }
 }
 
-#[derive(Clone, Default, Debug)]
-struct PCAcc([u8; 32]);
+// rolling window of the register values
+#[derive(Clone, Debug)]
+struct PCAcc {
+    regs: VecDeque<u32>,
+    window: usize,
+    count: [u32; 32], // the number of samples in which the bit is set
+}
+
+impl Default for PCAcc {
+    fn default() -> Self {
+        Self {
+            regs: VecDeque::new(),
+            window: 100,
+            count: [0; 32],
+        }
+    }
+}
 
 impl PCAcc {
     pub fn clear(&mut self) {
-        *self = Self([0u8; 32])
+        self.regs.clear();
+        self.count = [0; 32];
+    }
+
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window.max(1);
+
+        while self.window < self.regs.len() {
+            self.pop();
+        }
     }
 
     pub fn acc(&mut self, reg: u32) {
-        *self += Self::from(reg)
+        if self.window <= self.regs.len() {
+            self.pop();
+        }
+
+        for (i, c) in self.count.iter_mut().enumerate() {
+            *c += (reg >> i) & 0b1;
+        }
+
+        self.regs.push_back(reg);
     }
 
-    fn get(&self, index: usize) -> u8 {
-        self.0[index]
+    fn pop(&mut self) {
+        let Some(reg) = self.regs.pop_front() else { return };
+
+        for (i, c) in self.count.iter_mut().enumerate() {
+            *c -= (reg >> i) & 0b1;
+        }
     }
-}
 
-impl From<u32> for PCAcc {
-    fn from(val: u32) -> Self {
-        let mut out = [0u8; 32];
+    // %
+    fn get(&self, index: usize) -> u8 {
+        let len = self.regs.len() as u32;
 
-        for (i, o) in out.iter_mut().enumerate() {
-            *o = ((val >> i) & 0b1) as u8;
+        if len == 0 {
+            return 0;
         }
 
-        Self(out)
+        (self.count[index] * 100 / len) as u8
     }
 }
 
-impl std::ops::AddAssign for PCAcc {
-    fn add_assign(&mut self, other: Self) {
-        for (dst, src) in self.0.iter_mut().zip(other.0.iter()) {
-            *dst += src;
-        }
+#[test]
+fn test_pc_acc_window() {
+    let mut acc = PCAcc::default();
+    acc.set_window(4);
+
+    for reg in [0b01, 0b11, 0b01, 0b00] {
+        acc.acc(reg);
     }
+
+    assert_eq!(acc.get(0), 75);
+    assert_eq!(acc.get(1), 25);
+
+    // the first sample (0b01) is dropped
+    acc.acc(0b10);
+    assert_eq!(acc.regs.len(), 4);
+    assert_eq!(acc.get(0), 50);
+    assert_eq!(acc.get(1), 50);
+
+    acc.set_window(1);
+    assert_eq!(acc.get(0), 0);
+    assert_eq!(acc.get(1), 100);
+
+    acc.clear();
+    assert_eq!(acc.get(1), 0);
 }
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use super::{FdInfoUsage, ProcUsage};
+
+// The device power is split between processes in proportion to their engine usage.
+// This is only an estimate, the idle power and the memory power are also distributed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PowerAttributionModel {
+    #[default]
+    GfxWeighted, // GFX + Compute
+    EngineWeighted, // GFX + Compute + DMA + Media + VPE
+}
+
+impl PowerAttributionModel {
+    pub fn weight(&self, usage: &FdInfoUsage) -> i64 {
+        let w = match self {
+            Self::GfxWeighted => usage.gfx + usage.compute,
+            Self::EngineWeighted =>
+                usage.gfx + usage.compute + usage.dma + usage.media + usage.vpe,
+        };
+
+        w.max(0)
+    }
+}
+
+impl FromStr for PowerAttributionModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "gfx" => Ok(Self::GfxWeighted),
+            "engine" => Ok(Self::EngineWeighted),
+            _ => Err(format!("Unknown power attribution model: {s:?} (gfx, engine)")),
+        }
+    }
+}
+
+impl fmt::Display for PowerAttributionModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::GfxWeighted => write!(f, "GFX-weighted"),
+            Self::EngineWeighted => write!(f, "Engine-weighted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcPower {
+    pub power: f64, // W
+    pub energy: f64, // J
+}
+
+impl ProcPower {
+    pub fn energy_wh(&self) -> f64 {
+        self.energy / 3600.0
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcPowerStat {
+    pub model: PowerAttributionModel,
+    pub map: HashMap<i32, ProcPower>, // pid
+}
+
+impl ProcPowerStat {
+    pub fn new(model: PowerAttributionModel) -> Self {
+        Self { model, map: HashMap::new() }
+    }
+
+    pub fn update(&mut self, proc_usage: &[ProcUsage], device_power: Option<u32>, interval: Duration) {
+        let weights: Vec<(i32, i64)> = proc_usage
+            .iter()
+            .map(|pu| (pu.pid, self.model.weight(&pu.usage)))
+            .collect();
+        let total: i64 = weights.iter().map(|(_, w)| w).sum();
+        let device_power = device_power.unwrap_or(0) as f64;
+
+        self.map.retain(|pid, _| weights.iter().any(|(p, _)| p == pid));
+
+        for (pid, w) in weights {
+            let power = if total == 0 { 0.0 } else { device_power * w as f64 / total as f64 };
+            let proc_power = self.map.entry(pid).or_default();
+
+            proc_power.power = power;
+            proc_power.energy += power * interval.as_secs_f64();
+        }
+    }
+
+    pub fn get(&self, pid: i32) -> Option<&ProcPower> {
+        self.map.get(&pid)
+    }
+}
+
+#[test]
+fn test_proc_power_stat() {
+    let proc = |pid: i32, gfx: i64, compute: i64, media: i64| ProcUsage {
+        pid,
+        usage: FdInfoUsage { gfx, compute, media, ..Default::default() },
+        ..Default::default()
+    };
+    let sec = Duration::from_secs(1);
+    let mut stat = ProcPowerStat::new(PowerAttributionModel::GfxWeighted);
+
+    // 100 W split 3:1 by GFX + Compute, the media engine is ignored
+    let procs = [proc(1, 30, 30, 0), proc(2, 20, 0, 50)];
+    stat.update(&procs, Some(100), sec);
+    stat.update(&procs, Some(100), sec);
+    assert_eq!(stat.get(1).map(|p| p.power), Some(75.0));
+    assert_eq!(stat.get(2).map(|p| p.power), Some(25.0));
+    assert_eq!(stat.get(1).map(|p| p.energy), Some(150.0));
+
+    // all idle or unknown device power
+    stat.update(&[proc(1, 0, 0, 0), proc(2, 0, 0, 0)], Some(100), sec);
+    assert_eq!(stat.get(1).map(|p| (p.power, p.energy)), Some((0.0, 150.0)));
+    stat.update(&procs, None, sec);
+    assert_eq!(stat.get(2).map(|p| (p.power, p.energy)), Some((0.0, 50.0)));
+
+    // exited processes are removed
+    stat.update(&procs[..1], Some(100), sec);
+    assert_eq!(stat.get(1).map(|p| p.power), Some(100.0));
+    assert!(stat.get(2).is_none());
+
+    let mut stat = ProcPowerStat::new("engine".parse().unwrap());
+    stat.update(&procs, Some(130), sec);
+    assert_eq!(stat.get(2).map(|p| p.power), Some(70.0));
+    assert!("unknown".parse::<PowerAttributionModel>().is_err());
+}
@@ -0,0 +1,244 @@
+// ref: https://www.kernel.org/doc/html/latest/gpu/amdgpu/ras.html
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/amdgpu/amdgpu_ras.c
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+// same order as `enum amdgpu_ras_block`
+const RAS_BLOCKS: &[&str] = &[
+    "umc",
+    "sdma",
+    "gfx",
+    "mmhub",
+    "athub",
+    "pcie_bif",
+    "hdp",
+    "xgmi_wafl",
+    "df",
+    "smn",
+    "sem",
+    "mp0",
+    "mp1",
+    "fuse",
+    "mca",
+    "vcn",
+    "jpeg",
+    "ih",
+    "mpio",
+];
+
+#[derive(Debug, Clone)]
+pub struct RasBlockErrorCount {
+    pub name: String,
+    pub corrected: u64,
+    pub uncorrected: u64,
+    // since the start of monitoring
+    pub delta_corrected: u64,
+    pub delta_uncorrected: u64,
+    init_corrected: u64,
+    init_uncorrected: u64,
+}
+
+impl RasBlockErrorCount {
+    // "ue: 0\nce: 0\n"
+    fn parse(s: &str) -> Option<[u64; 2]> {
+        let mut ce = None;
+        let mut ue = None;
+
+        for line in s.lines() {
+            let Some((k, v)) = line.split_once(':') else { continue };
+            let v = v.trim().parse::<u64>().ok();
+
+            match k.trim() {
+                "ce" => ce = v,
+                "ue" => ue = v,
+                _ => {},
+            }
+        }
+
+        Some([ce?, ue?])
+    }
+
+    fn read(ras_path: &Path, block: &str) -> Option<[u64; 2]> {
+        let s = std::fs::read_to_string(ras_path.join(format!("{block}_err_count"))).ok()?;
+
+        Self::parse(&s)
+    }
+
+    fn new(name: &str, [ce, ue]: [u64; 2]) -> Self {
+        Self {
+            name: name.to_uppercase(),
+            corrected: ce,
+            uncorrected: ue,
+            delta_corrected: 0,
+            delta_uncorrected: 0,
+            init_corrected: ce,
+            init_uncorrected: ue,
+        }
+    }
+
+    fn set(&mut self, [ce, ue]: [u64; 2]) {
+        self.corrected = ce;
+        self.uncorrected = ue;
+        self.delta_corrected = ce.saturating_sub(self.init_corrected);
+        self.delta_uncorrected = ue.saturating_sub(self.init_uncorrected);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadPageStatus {
+    Reserved, // retired
+    Pending,
+    Failed,
+    Unknown,
+}
+
+impl From<&str> for BadPageStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "R" => Self::Reserved,
+            "P" => Self::Pending,
+            "F" => Self::Failed,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for BadPageStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadPage {
+    pub pfn: u64,
+    pub size: u64,
+    pub status: BadPageStatus,
+}
+
+impl BadPage {
+    // "0x00000001 : 0x00001000 : R"
+    pub fn parse_list(s: &str) -> Vec<Self> {
+        s.lines().filter_map(|line| {
+            let mut split = line.split(':').map(|v| v.trim());
+            let [pfn, size, status] = [split.next()?, split.next()?, split.next()?];
+            let pfn = u64::from_str_radix(pfn.trim_start_matches("0x"), 16).ok()?;
+            let size = u64::from_str_radix(size.trim_start_matches("0x"), 16).ok()?;
+
+            Some(Self { pfn, size, status: BadPageStatus::from(status) })
+        }).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RasErrorStat {
+    ras_path: PathBuf,
+    pub blocks: Vec<RasBlockErrorCount>,
+    pub bad_pages: Vec<BadPage>,
+}
+
+impl RasErrorStat {
+    pub fn get_from_sysfs<P: Into<PathBuf>>(sysfs_path: P) -> Option<Self> {
+        let ras_path = sysfs_path.into().join("ras");
+        let blocks: Vec<RasBlockErrorCount> = RAS_BLOCKS
+            .iter()
+            .filter_map(|block| {
+                let count = RasBlockErrorCount::read(&ras_path, block)?;
+
+                Some(RasBlockErrorCount::new(block, count))
+            })
+            .collect();
+
+        if blocks.is_empty() {
+            return None;
+        }
+
+        let bad_pages = Self::read_bad_pages(&ras_path);
+
+        Some(Self { ras_path, blocks, bad_pages })
+    }
+
+    fn read_bad_pages(ras_path: &Path) -> Vec<BadPage> {
+        std::fs::read_to_string(ras_path.join("gpu_vram_bad_pages"))
+            .map(|s| BadPage::parse_list(&s))
+            .unwrap_or_default()
+    }
+
+    pub fn update(&mut self) {
+        let mut changed = false;
+
+        for block in self.blocks.iter_mut() {
+            let Some(count) = RasBlockErrorCount::read(&self.ras_path, &block.name.to_lowercase()) else {
+                continue;
+            };
+
+            changed |= count != [block.corrected, block.uncorrected];
+            block.set(count);
+        }
+
+        // pages are retired on uncorrectable errors
+        if changed {
+            self.bad_pages = Self::read_bad_pages(&self.ras_path);
+        }
+    }
+
+    pub fn retired_pages(&self) -> impl Iterator<Item = &BadPage> {
+        self.bad_pages.iter().filter(|page| page.status == BadPageStatus::Reserved)
+    }
+
+    pub fn count_bad_pages(&self, status: BadPageStatus) -> usize {
+        self.bad_pages.iter().filter(|page| page.status == status).count()
+    }
+
+    pub fn total(&self) -> [u64; 2] {
+        self.blocks.iter().fold([0, 0], |[ce, ue], block| {
+            [ce + block.corrected, ue + block.uncorrected]
+        })
+    }
+}
+
+#[test]
+fn test_bad_page_parse_list() {
+    let s = std::fs::read_to_string("src/stat/gpu_vram_bad_pages_sample.txt").unwrap();
+    let pages = BadPage::parse_list(&s);
+
+    assert_eq!(pages, [
+        BadPage { pfn: 0x1, size: 0x1000, status: BadPageStatus::Reserved },
+        BadPage { pfn: 0x2, size: 0x1000, status: BadPageStatus::Reserved },
+        BadPage { pfn: 0xf3a5, size: 0x1000, status: BadPageStatus::Pending },
+        BadPage { pfn: 0x100000, size: 0x1000, status: BadPageStatus::Failed },
+    ]);
+
+    assert!(BadPage::parse_list("").is_empty());
+    assert_eq!(BadPage::parse_list("0x1 : 0x1000\ninvalid : 0x1000 : R\n0x3 : 0x1000 : ?\n"), [
+        BadPage { pfn: 0x3, size: 0x1000, status: BadPageStatus::Unknown },
+    ]);
+}
+
+#[test]
+fn test_ras_error_stat_fake_sysfs() {
+    let sysfs = crate::test_util::FakeSysfs::new("ras");
+    let dir = sysfs.path().to_path_buf();
+    let ras = dir.join("ras");
+    std::fs::create_dir_all(&ras).unwrap();
+    std::fs::write(ras.join("umc_err_count"), "ue: 0\nce: 2\n").unwrap();
+    std::fs::write(ras.join("gfx_err_count"), "ue: 1\nce: 0\n").unwrap();
+
+    let mut stat = RasErrorStat::get_from_sysfs(&dir).unwrap();
+    assert_eq!(stat.blocks.iter().map(|b| b.name.as_str()).collect::<Vec<_>>(), ["UMC", "GFX"]);
+    assert_eq!(stat.total(), [2, 1]);
+    assert!(stat.bad_pages.is_empty());
+
+    // bad pages are re-read when the counters change
+    std::fs::copy("src/stat/gpu_vram_bad_pages_sample.txt", ras.join("gpu_vram_bad_pages")).unwrap();
+    std::fs::write(ras.join("umc_err_count"), "ue: 3\nce: 2\n").unwrap();
+    stat.update();
+
+    assert_eq!((stat.blocks[0].delta_corrected, stat.blocks[0].delta_uncorrected), (0, 3));
+    assert_eq!(stat.retired_pages().count(), 2);
+    assert_eq!(stat.count_bad_pages(BadPageStatus::Pending), 1);
+
+    drop(sysfs);
+    assert!(RasErrorStat::get_from_sysfs(&dir).is_none());
+}
@@ -15,6 +15,7 @@ use libdrm_amdgpu_sys::{
     },
 };
 use super::{parse_hwmon, HwmonPower, PowerType};
+use crate::control::PerformanceLevel;
 
 #[derive(Clone, Debug)]
 pub struct Sensors {
@@ -43,6 +44,7 @@ pub struct Sensors {
     pub fan_max_rpm: Option<u32>,
     pub pci_power_state: Option<String>,
     pub power_profile: Option<PowerProfile>,
+    pub perf_level: Option<PerformanceLevel>,
 }
 
 impl Sensors {
@@ -115,6 +117,7 @@ impl Sensors {
                 s
             });
         let power_profile = PowerProfile::get_current_profile_from_sysfs(&sysfs_path);
+        let perf_level = PerformanceLevel::get_from_sysfs(&sysfs_path);
 
         Some(Self {
             hwmon_path,
@@ -142,6 +145,7 @@ impl Sensors {
             gpu_port_path,
             pci_power_state,
             power_profile,
+            perf_level,
         })
     }
 
@@ -175,6 +179,7 @@ impl Sensors {
 
         self.fan_rpm = parse_hwmon(self.hwmon_path.join("fan1_input"));
         self.power_profile = PowerProfile::get_current_profile_from_sysfs(&self.sysfs_path);
+        self.perf_level = PerformanceLevel::get_from_sysfs(&self.sysfs_path);
         self.update_pci_power_state();
     }
 
@@ -191,6 +196,7 @@ impl Sensors {
         self.vddgfx = None;
         self.fan_rpm = None;
         self.power_profile = None;
+        self.perf_level = None;
 
         self.update_pci_power_state();
     }
@@ -0,0 +1,206 @@
+// ref: https://github.com/torvalds/linux/blob/master/drivers/gpu/drm/amd/pm/swsmu/inc/smu_types.h
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::AMDGPU::{GpuMetrics, MetricsInfo, ThrottleStatus};
+use crate::get_local_time_string;
+use super::Sensors;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleCategory {
+    Power,
+    Current,
+    Thermal,
+    Prochot,
+    VR,
+    Other,
+}
+
+impl ThrottleCategory {
+    pub fn from_throttler_name(name: &str) -> Self {
+        let name = name.to_uppercase();
+
+        if name.contains("PPT") {
+            Self::Power
+        } else if name.contains("TDC") || name.contains("EDC") {
+            Self::Current
+        } else if name.contains("TEMP") {
+            Self::Thermal
+        } else if name.contains("PROCHOT") {
+            Self::Prochot
+        } else if name.contains("VR") {
+            Self::VR
+        } else {
+            Self::Other
+        }
+    }
+}
+
+impl fmt::Display for ThrottleCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// Sensors values at the onset of throttling
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleOnset {
+    pub sclk: Option<u32>, // MHz
+    pub mclk: Option<u32>, // MHz
+    pub edge_temp: Option<i64>, // C
+    pub junction_temp: Option<i64>, // C
+    pub power: Option<u32>, // W
+}
+
+impl ThrottleOnset {
+    fn from_sensors(sensors: &Option<Sensors>) -> Self {
+        let Some(sensors) = sensors else { return Self::default() };
+
+        Self {
+            sclk: sensors.sclk,
+            mclk: sensors.mclk,
+            edge_temp: sensors.edge_temp.as_ref().map(|t| t.current),
+            junction_temp: sensors.junction_temp.as_ref().map(|t| t.current),
+            power: sensors.average_power.as_ref()
+                .or(sensors.input_power.as_ref())
+                .map(|p| p.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ThrottleEvent {
+    pub throttler: String,
+    pub category: ThrottleCategory,
+    pub start_time: String, // local time
+    pub duration: Duration,
+    pub is_active: bool,
+    pub onset: ThrottleOnset,
+    start: Instant,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleEventLog {
+    pub events: VecDeque<ThrottleEvent>,
+}
+
+impl ThrottleEventLog {
+    const MAX_LEN: usize = 64;
+
+    fn get_throttlers(metrics: &Option<GpuMetrics>) -> Vec<String> {
+        let Some(thr_val) = metrics.as_ref().and_then(|m| m.get_indep_throttle_status()) else {
+            return Vec::new();
+        };
+
+        // not supported
+        if thr_val == u64::MAX { return Vec::new() }
+
+        ThrottleStatus::new(thr_val)
+            .get_all_throttler()
+            .into_iter()
+            .map(|thr| format!("{thr:?}"))
+            .collect()
+    }
+
+    pub fn update(&mut self, metrics: &Option<GpuMetrics>, sensors: &Option<Sensors>) {
+        let throttlers = Self::get_throttlers(metrics);
+
+        self.update_with_throttlers(Instant::now(), throttlers, || ThrottleOnset::from_sensors(sensors));
+    }
+
+    fn update_with_throttlers(
+        &mut self,
+        now: Instant,
+        throttlers: Vec<String>,
+        onset: impl Fn() -> ThrottleOnset,
+    ) {
+        for ev in self.events.iter_mut().filter(|ev| ev.is_active) {
+            ev.duration = now.duration_since(ev.start);
+            ev.is_active = throttlers.contains(&ev.throttler);
+        }
+
+        for thr in throttlers {
+            if self.events.iter().any(|ev| ev.is_active && ev.throttler == thr) {
+                continue;
+            }
+
+            if self.events.len() >= Self::MAX_LEN {
+                if let Some(pos) = self.events.iter().position(|ev| !ev.is_active) {
+                    self.events.remove(pos);
+                }
+            }
+
+            self.events.push_back(ThrottleEvent {
+                category: ThrottleCategory::from_throttler_name(&thr),
+                throttler: thr,
+                start_time: get_local_time_string(),
+                duration: Duration::ZERO,
+                is_active: true,
+                onset: onset(),
+                start: now,
+            });
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[test]
+fn test_throttle_category() {
+    for (name, category) in [
+        ("PPT0", ThrottleCategory::Power),
+        ("TDC_GFX", ThrottleCategory::Current),
+        ("EDC_CPU", ThrottleCategory::Current),
+        ("TEMP_HOTSPOT", ThrottleCategory::Thermal),
+        ("PROCHOT_GFX", ThrottleCategory::Prochot),
+        ("VR_MEM0", ThrottleCategory::VR),
+        ("FIT", ThrottleCategory::Other),
+    ] {
+        assert_eq!(ThrottleCategory::from_throttler_name(name), category, "{name}");
+    }
+}
+
+#[test]
+fn test_throttle_event_log() {
+    let t0 = Instant::now();
+    let ms = |ms: u64| t0 + Duration::from_millis(ms);
+    let thr = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let onset = || ThrottleOnset { sclk: Some(2500), ..Default::default() };
+    let mut log = ThrottleEventLog::default();
+
+    log.update_with_throttlers(ms(0), thr(&[]), onset);
+    assert!(log.is_empty());
+
+    log.update_with_throttlers(ms(0), thr(&["PPT0"]), onset);
+    log.update_with_throttlers(ms(100), thr(&["PPT0", "TEMP_HOTSPOT"]), onset);
+    assert_eq!(log.events.len(), 2);
+    assert_eq!(log.events[0].duration, Duration::from_millis(100));
+    assert_eq!(log.events[0].onset.sclk, Some(2500));
+    assert_eq!(log.events[1].category, ThrottleCategory::Thermal);
+
+    // PPT0 has ended, the duration is kept
+    log.update_with_throttlers(ms(300), thr(&["TEMP_HOTSPOT"]), onset);
+    assert!(!log.events[0].is_active);
+    assert_eq!(log.events[0].duration, Duration::from_millis(300));
+    log.update_with_throttlers(ms(400), thr(&["TEMP_HOTSPOT"]), onset);
+    assert_eq!(log.events[0].duration, Duration::from_millis(300));
+    assert_eq!(log.events[1].duration, Duration::from_millis(300));
+
+    // a new event for the same throttler
+    log.update_with_throttlers(ms(500), thr(&["PPT0", "TEMP_HOTSPOT"]), onset);
+    assert_eq!(log.events.len(), 3);
+    assert!(log.events[2].is_active && log.events[2].throttler == "PPT0");
+
+    // the oldest inactive event is dropped
+    for i in 0..ThrottleEventLog::MAX_LEN as u64 {
+        log.update_with_throttlers(ms(600 + i * 2), thr(&["TEMP_HOTSPOT", "FIT"]), onset);
+        log.update_with_throttlers(ms(601 + i * 2), thr(&["TEMP_HOTSPOT"]), onset);
+    }
+
+    assert_eq!(log.events.len(), ThrottleEventLog::MAX_LEN);
+    assert_eq!(log.events[0].throttler, "TEMP_HOTSPOT");
+    assert!(log.events.iter().all(|ev| ev.throttler != "PPT0"));
+}
@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use crate::app::AppAmdgpuTopStat;
+use crate::stat::PerfCounter;
+use crate::{DevicePath, PCI};
+
+pub type SessionSummaryList = Arc<Mutex<Vec<Arc<Mutex<SessionSummary>>>>>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SummaryValues {
+    pub min: f32,
+    pub mean: f32,
+    pub max: f32,
+    pub p50: f32,
+    pub p95: f32,
+    pub p99: f32,
+}
+
+// Running min/max/sum and P² quantile estimators, so the memory usage does not grow
+// with the session length.
+// ref: R. Jain and I. Chlamtac, "The P² algorithm for dynamic calculation of quantiles
+//      and histograms without storing observations", CACM 28(10), 1985
+#[derive(Debug, Clone, Default)]
+pub struct SummaryStat {
+    count: usize,
+    min: f32,
+    max: f32,
+    sum: f64,
+    p50: P2Quantile,
+    p95: P2Quantile,
+    p99: P2Quantile,
+}
+
+impl SummaryStat {
+    pub fn push(&mut self, val: f32) {
+        if self.count == 0 {
+            self.min = val;
+            self.max = val;
+            self.p50 = P2Quantile::new(0.50);
+            self.p95 = P2Quantile::new(0.95);
+            self.p99 = P2Quantile::new(0.99);
+        } else {
+            self.min = self.min.min(val);
+            self.max = self.max.max(val);
+        }
+
+        self.count += 1;
+        self.sum += val as f64;
+        self.p50.push(val as f64);
+        self.p95.push(val as f64);
+        self.p99.push(val as f64);
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn calc(&self) -> Option<SummaryValues> {
+        if self.count == 0 { return None }
+
+        Some(SummaryValues {
+            min: self.min,
+            mean: (self.sum / self.count as f64) as f32,
+            max: self.max,
+            p50: self.p50.get() as f32,
+            p95: self.p95.get() as f32,
+            p99: self.p99.get() as f32,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct P2Quantile {
+    p: f64,
+    count: usize,
+    q: [f64; 5], // marker heights, the first 5 samples until `count` reaches 5
+    n: [f64; 5], // marker positions
+    np: [f64; 5], // desired marker positions
+}
+
+impl P2Quantile {
+    fn new(p: f64) -> Self {
+        Self { p, ..Default::default() }
+    }
+
+    // nearest-rank method
+    fn percentile(sorted: &[f64], p: f64) -> f64 {
+        let rank = (p * sorted.len() as f64).ceil() as usize;
+
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    fn push(&mut self, x: f64) {
+        if self.count < 5 {
+            self.q[self.count] = x;
+            self.count += 1;
+
+            if self.count == 5 {
+                self.q.sort_by(|a, b| a.total_cmp(b));
+                self.n = [1.0, 2.0, 3.0, 4.0, 5.0];
+                self.np = [
+                    1.0,
+                    1.0 + 2.0 * self.p,
+                    1.0 + 4.0 * self.p,
+                    3.0 + 2.0 * self.p,
+                    5.0,
+                ];
+            }
+
+            return;
+        }
+
+        self.count += 1;
+
+        let k = if x < self.q[0] {
+            self.q[0] = x;
+            0
+        } else if x >= self.q[4] {
+            self.q[4] = x;
+            3
+        } else {
+            (1..5).find(|i| x < self.q[*i]).unwrap() - 1
+        };
+
+        for n in self.n[k+1..].iter_mut() {
+            *n += 1.0;
+        }
+
+        let dn = [0.0, self.p / 2.0, self.p, (1.0 + self.p) / 2.0, 1.0];
+
+        for (np, dn) in self.np.iter_mut().zip(dn) {
+            *np += dn;
+        }
+
+        for i in 1..4 {
+            let d = self.np[i] - self.n[i];
+
+            if (d >= 1.0 && self.n[i+1] - self.n[i] > 1.0)
+            || (d <= -1.0 && self.n[i-1] - self.n[i] < -1.0)
+            {
+                let d = d.signum();
+                let q = self.parabolic(i, d);
+
+                self.q[i] = if self.q[i-1] < q && q < self.q[i+1] {
+                    q
+                } else {
+                    self.linear(i, d)
+                };
+                self.n[i] += d;
+            }
+        }
+    }
+
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        let (q, n) = (&self.q, &self.n);
+
+        q[i] + d / (n[i+1] - n[i-1]) * (
+            (n[i] - n[i-1] + d) * (q[i+1] - q[i]) / (n[i+1] - n[i])
+            + (n[i+1] - n[i] - d) * (q[i] - q[i-1]) / (n[i] - n[i-1])
+        )
+    }
+
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let j = if d < 0.0 { i - 1 } else { i + 1 };
+
+        self.q[i] + d * (self.q[j] - self.q[i]) / (self.n[j] - self.n[i])
+    }
+
+    fn get(&self) -> f64 {
+        if self.count < 5 {
+            let mut sorted = self.q[..self.count].to_vec();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+
+            Self::percentile(&sorted, self.p)
+        } else {
+            self.q[2]
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SummaryItem {
+    pub label: String,
+    pub unit: &'static str,
+    pub stat: SummaryStat,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcGpuTime {
+    pub pid: i32,
+    pub name: String,
+    pub gpu_time: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub device_name: String,
+    pub pci: PCI::BUS_INFO,
+    pub duration: Duration,
+    pub items: Vec<SummaryItem>,
+    pub procs: HashMap<i32, ProcGpuTime>,
+}
+
+impl SessionSummary {
+    pub fn new(device_path: &DevicePath) -> Self {
+        Self {
+            device_name: device_path.device_name.clone(),
+            pci: device_path.pci,
+            duration: Duration::ZERO,
+            items: Vec::new(),
+            procs: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, label: &str, unit: &'static str, val: Option<f32>) {
+        let Some(val) = val else { return };
+
+        if let Some(item) = self.items.iter_mut().find(|item| item.label == label) {
+            item.stat.push(val);
+        } else {
+            let mut stat = SummaryStat::default();
+            stat.push(val);
+            self.items.push(SummaryItem { label: label.to_string(), unit, stat });
+        }
+    }
+
+    pub fn update(&mut self, stat: &AppAmdgpuTopStat, interval: Duration) {
+        self.duration += interval;
+
+        if let Some(sensors) = &stat.sensors {
+            for (label, unit, val) in [
+                ("SCLK", "MHz", sensors.sclk.map(|v| v as f32)),
+                ("MCLK", "MHz", sensors.mclk.map(|v| v as f32)),
+                ("VDDGFX", "mV", sensors.vddgfx.map(|v| v as f32)),
+                ("VDDNB", "mV", sensors.vddnb.map(|v| v as f32)),
+                ("Edge Temp.", "C", sensors.edge_temp.as_ref().map(|t| t.current as f32)),
+                ("Junction Temp.", "C", sensors.junction_temp.as_ref().map(|t| t.current as f32)),
+                ("Memory Temp.", "C", sensors.memory_temp.as_ref().map(|t| t.current as f32)),
+                ("Average Power", "W", sensors.average_power.as_ref().map(|p| p.value as f32)),
+                ("Input Power", "W", sensors.input_power.as_ref().map(|p| p.value as f32)),
+                ("Fan", "RPM", sensors.fan_rpm.map(|v| v as f32)),
+            ] {
+                self.push(label, unit, val);
+            }
+        }
+
+        for (label, val) in [
+            ("GFX Activity", stat.activity.gfx),
+            ("Memory Activity", stat.activity.umc),
+            ("Media Activity", stat.activity.media),
+        ] {
+            self.push(label, "%", val.map(|v| v as f32));
+        }
+
+        for (label, val) in [
+            ("VRAM Usage", stat.vram_usage.0.vram.heap_usage),
+            ("GTT Usage", stat.vram_usage.0.gtt.heap_usage),
+        ] {
+            self.push(label, "MiB", Some((val >> 20) as f32));
+        }
+
+        for pu in &stat.fdinfo.proc_usage {
+            let usage = pu.usage.gfx + pu.usage.compute + pu.usage.dma + pu.usage.media + pu.usage.vpe;
+            let proc = self.procs.entry(pu.pid).or_insert_with(|| ProcGpuTime {
+                pid: pu.pid,
+                name: pu.name.clone(),
+                gpu_time: Duration::ZERO,
+            });
+
+            proc.gpu_time += interval.mul_f64(usage.max(0) as f64 / 100.0);
+        }
+    }
+
+    // The performance counters are only read when enabled.
+    pub fn update_pc<'a, I: IntoIterator<Item = &'a PerfCounter>>(&mut self, pcs: I) {
+        for pc in pcs {
+            for pc_index in &pc.pc_index {
+                self.push(
+                    &format!("{} {}", pc.name, pc_index.name),
+                    "%",
+                    Some(pc_index.usage as f32),
+                );
+            }
+        }
+    }
+
+    pub fn get(&self, label: &str) -> Option<&SummaryStat> {
+        self.items.iter().find(|item| item.label == label).map(|item| &item.stat)
+    }
+
+    pub fn top_processes(&self, n: usize) -> Vec<&ProcGpuTime> {
+        let mut procs: Vec<&ProcGpuTime> = self.procs
+            .values()
+            .filter(|proc| !proc.gpu_time.is_zero())
+            .collect();
+
+        procs.sort_by(|a, b| b.gpu_time.cmp(&a.gpu_time));
+        procs.truncate(n);
+
+        procs
+    }
+}
+
+impl fmt::Display for SessionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} ({}), {:.1}s",
+            self.device_name,
+            self.pci,
+            self.duration.as_secs_f32(),
+        )?;
+        writeln!(
+            f,
+            " {:<32} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}",
+            "", "Min", "Mean", "Max", "P50", "P95", "P99",
+        )?;
+
+        for item in &self.items {
+            let Some(v) = item.stat.calc() else { continue };
+            let label = format!("{} ({})", item.label, item.unit);
+
+            writeln!(
+                f,
+                " {label:<32} {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>9.1}",
+                v.min, v.mean, v.max, v.p50, v.p95, v.p99,
+            )?;
+        }
+
+        let procs = self.top_processes(10);
+
+        if !procs.is_empty() {
+            writeln!(f, " Top processes by GPU time:")?;
+
+            for proc in procs {
+                writeln!(
+                    f,
+                    "  {:<16} {:>7} {:>9.1}s",
+                    proc.name,
+                    proc.pid,
+                    proc.gpu_time.as_secs_f32(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn print_session_summary_list(list: &SessionSummaryList) {
+    let list = list.lock().unwrap();
+
+    for summary in list.iter() {
+        println!("{}", summary.lock().unwrap());
+    }
+}
+
+static TERMINATE: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn signal_handler(_: libc::c_int) {
+    TERMINATE.store(true, Ordering::SeqCst);
+    // the main loop may not end soon (e.g. blocked on opening the FIFO)
+    crate::stat::stop_gfxoff_residency_logging();
+}
+
+// SIGINT/SIGTERM/SIGHUP stop the main loop instead of killing the process,
+// so that the summary can be printed (or the fan control restored) at the end.
+pub fn set_terminate_handler() {
+    for sig in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+        unsafe { libc::signal(sig, signal_handler as *const () as libc::sighandler_t); }
+    }
+}
+
+pub fn is_terminated() -> bool {
+    TERMINATE.load(Ordering::SeqCst)
+}
+
+#[test]
+fn test_summary_stat_empty() {
+    let stat = SummaryStat::default();
+
+    assert!(stat.is_empty());
+    assert!(stat.calc().is_none());
+}
+
+#[test]
+fn test_summary_stat_percentile() {
+    // exact (nearest-rank) for a few samples
+    let mut stat = SummaryStat::default();
+
+    for v in [30.0, 10.0, 20.0, 40.0] {
+        stat.push(v);
+    }
+
+    let v = stat.calc().unwrap();
+    assert_eq!((v.min, v.mean, v.max), (10.0, 25.0, 40.0));
+    assert_eq!((v.p50, v.p95, v.p99), (20.0, 40.0, 40.0));
+
+    // estimated for 1..=1000 in a scrambled order
+    let mut stat = SummaryStat::default();
+
+    for i in 0..1000u32 {
+        stat.push(((i * 389) % 1000 + 1) as f32);
+    }
+
+    let v = stat.calc().unwrap();
+    assert_eq!(stat.len(), 1000);
+    assert_eq!((v.min, v.max), (1.0, 1000.0));
+    assert!((v.mean - 500.5).abs() < 0.01);
+    assert!((v.p50 - 500.0).abs() < 20.0, "{}", v.p50);
+    assert!((v.p95 - 950.0).abs() < 20.0, "{}", v.p95);
+    assert!((v.p99 - 990.0).abs() < 10.0, "{}", v.p99);
+}
@@ -0,0 +1,343 @@
+// ref: https://www.kernel.org/doc/html/latest/core-api/kobject.html#uevents
+// ref: https://github.com/torvalds/linux/blob/master/lib/kobject_uevent.c
+// ref: https://www.kernel.org/doc/html/latest/gpu/drm-uapi.html#device-wedging
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::sync::mpsc;
+use crate::{get_local_time_string, PCI};
+
+const UEVENT_BUFFER_SIZE: usize = 8192;
+const KERNEL_EVENTS_GROUP: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UeventAction {
+    Add,
+    Remove,
+    Change,
+    Other(String),
+}
+
+impl From<&str> for UeventAction {
+    fn from(s: &str) -> Self {
+        match s {
+            "add" => Self::Add,
+            "remove" => Self::Remove,
+            "change" => Self::Change,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Uevent {
+    pub action: UeventAction,
+    pub devpath: String,
+    pub subsystem: String,
+    pub props: HashMap<String, String>,
+}
+
+impl Uevent {
+    // "<action>@<devpath>\0ACTION=<action>\0DEVPATH=<devpath>\0SUBSYSTEM=<subsystem>\0..."
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        let mut fields = buf
+            .split(|b| *b == 0)
+            .filter(|f| !f.is_empty())
+            .map(|f| std::str::from_utf8(f).ok());
+
+        let header = fields.next()??;
+
+        // messages from udevd start with "libudev"
+        if !header.contains('@') { return None }
+
+        let props: HashMap<String, String> = fields
+            .flatten()
+            .filter_map(|f| f.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let action = UeventAction::from(props.get("ACTION")?.as_str());
+        let devpath = props.get("DEVPATH")?.clone();
+        let subsystem = props.get("SUBSYSTEM").cloned().unwrap_or_default();
+
+        Some(Self { action, devpath, subsystem, props })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.props.get(key).map(|s| s.as_str())
+    }
+
+    // "/devices/pci0000:00/0000:00:01.1/0000:01:00.0/0000:02:00.0/0000:03:00.0/drm/card1"
+    pub fn pci_bus(&self) -> Option<PCI::BUS_INFO> {
+        if let Some(pci) = self.get("PCI_SLOT_NAME").and_then(|s| s.parse().ok()) {
+            return Some(pci);
+        }
+
+        self.devpath
+            .rsplit('/')
+            .filter(|s| s.len() == 12)
+            .find_map(|s| s.parse().ok())
+    }
+
+    pub fn is_drm_card(&self) -> bool {
+        self.subsystem == "drm"
+        && self.get("DEVNAME").is_some_and(|name| name.starts_with("dri/card"))
+    }
+}
+
+pub struct UeventMonitor {
+    fd: OwnedFd,
+}
+
+impl UeventMonitor {
+    pub fn new() -> io::Result<Self> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_KOBJECT_UEVENT,
+            )
+        };
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_groups = KERNEL_EVENTS_GROUP;
+
+        let r = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+
+        if r < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { fd })
+    }
+
+    pub fn recv(&self) -> io::Result<Option<Uevent>> {
+        let mut buf = [0u8; UEVENT_BUFFER_SIZE];
+        let len = unsafe {
+            libc::recv(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+            )
+        };
+
+        if len < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Uevent::parse(&buf[..len as usize]))
+    }
+
+    pub fn spawn_thread(self) -> mpsc::Receiver<Uevent> {
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || loop {
+            let uevent = match self.recv() {
+                Ok(Some(uevent)) => uevent,
+                Ok(None) => continue,
+                // ENOBUFS: some events have been dropped
+                Err(e) if e.kind() == io::ErrorKind::Interrupted
+                    || e.raw_os_error() == Some(libc::ENOBUFS) => continue,
+                Err(_) => break,
+            };
+
+            if !matches!(uevent.subsystem.as_str(), "drm" | "pci") { continue }
+
+            if tx.send(uevent).is_err() { break }
+        });
+
+        rx
+    }
+}
+
+/// Returns `None` if the netlink socket cannot be opened.
+pub fn spawn_uevent_monitor() -> Option<mpsc::Receiver<Uevent>> {
+    UeventMonitor::new().ok().map(|monitor| monitor.spawn_thread())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceEventKind {
+    Added,
+    Removed,
+    Reset,
+    Wedged(String), // recovery method
+    Hotplug, // connector
+}
+
+impl DeviceEventKind {
+    pub fn from_uevent(uevent: &Uevent) -> Option<Self> {
+        match uevent.action {
+            UeventAction::Add if uevent.is_drm_card() => Some(Self::Added),
+            UeventAction::Remove if uevent.is_drm_card() || uevent.subsystem == "pci" =>
+                Some(Self::Removed),
+            UeventAction::Change if uevent.subsystem == "drm" => {
+                if let Some(method) = uevent.get("WEDGED") {
+                    Some(Self::Wedged(method.to_string()))
+                } else if uevent.get("RESET") == Some("1") {
+                    Some(Self::Reset)
+                } else if uevent.get("HOTPLUG") == Some("1") {
+                    Some(Self::Hotplug)
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DeviceEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Added => write!(f, "Added"),
+            Self::Removed => write!(f, "Removed"),
+            Self::Reset => write!(f, "GPU Reset"),
+            Self::Wedged(method) => write!(f, "Wedged (recovery: {method})"),
+            Self::Hotplug => write!(f, "Hotplug"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeviceEvent {
+    pub time: String,
+    pub kind: DeviceEventKind,
+}
+
+impl fmt::Display for DeviceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.time, self.kind)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeviceEventLog {
+    pub events: VecDeque<DeviceEvent>,
+}
+
+impl DeviceEventLog {
+    const MAX_LEN: usize = 32;
+
+    pub fn push(&mut self, kind: DeviceEventKind) {
+        if self.events.len() >= Self::MAX_LEN {
+            self.events.pop_front();
+        }
+
+        self.events.push_back(DeviceEvent { time: get_local_time_string(), kind });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[cfg(test)]
+fn uevent_buf(fields: &[&str]) -> Vec<u8> {
+    fields.iter().flat_map(|f| f.bytes().chain([0])).collect()
+}
+
+#[test]
+fn test_uevent_parse() {
+    let devpath = "/devices/pci0000:00/0000:00:01.1/0000:01:00.0/0000:02:00.0/0000:03:00.0/drm/card1";
+    let add = uevent_buf(&[
+        &format!("add@{devpath}"),
+        "ACTION=add",
+        &format!("DEVPATH={devpath}"),
+        "SUBSYSTEM=drm",
+        "DEVNAME=dri/card1",
+        "SEQNUM=1234",
+    ]);
+    let uevent = Uevent::parse(&add).unwrap();
+    let pci: PCI::BUS_INFO = "0000:03:00.0".parse().unwrap();
+
+    assert_eq!(uevent.action, UeventAction::Add);
+    assert_eq!(uevent.devpath, devpath);
+    assert_eq!(uevent.get("SEQNUM"), Some("1234"));
+    assert!(uevent.is_drm_card());
+    assert_eq!(uevent.pci_bus(), Some(pci));
+    assert_eq!(DeviceEventKind::from_uevent(&uevent), Some(DeviceEventKind::Added));
+
+    let remove = uevent_buf(&[
+        "remove@/devices/pci0000:00/0000:00:01.1/0000:01:00.0",
+        "ACTION=remove",
+        "DEVPATH=/devices/pci0000:00/0000:00:01.1/0000:01:00.0",
+        "SUBSYSTEM=pci",
+        "PCI_SLOT_NAME=0000:01:00.0",
+    ]);
+    let uevent = Uevent::parse(&remove).unwrap();
+
+    assert_eq!(uevent.action, UeventAction::Remove);
+    assert!(!uevent.is_drm_card());
+    assert_eq!(uevent.pci_bus(), "0000:01:00.0".parse().ok());
+    assert_eq!(DeviceEventKind::from_uevent(&uevent), Some(DeviceEventKind::Removed));
+
+    // "online" is not handled
+    let online = uevent_buf(&[
+        "online@/devices/system/cpu/cpu1",
+        "ACTION=online",
+        "DEVPATH=/devices/system/cpu/cpu1",
+        "SUBSYSTEM=cpu",
+    ]);
+    let uevent = Uevent::parse(&online).unwrap();
+
+    assert_eq!(uevent.action, UeventAction::Other("online".to_string()));
+    assert_eq!(uevent.pci_bus(), None);
+    assert_eq!(DeviceEventKind::from_uevent(&uevent), None);
+}
+
+#[test]
+fn test_uevent_change() {
+    let devpath = "/devices/pci0000:00/0000:00:08.1/0000:c5:00.0/drm/card0";
+    let change = |props: &[&str]| {
+        let mut fields = vec![
+            format!("change@{devpath}"),
+            "ACTION=change".to_string(),
+            format!("DEVPATH={devpath}"),
+            "SUBSYSTEM=drm".to_string(),
+            "DEVNAME=dri/card0".to_string(),
+        ];
+        fields.extend(props.iter().map(|s| s.to_string()));
+        let fields: Vec<&str> = fields.iter().map(|s| s.as_str()).collect();
+
+        DeviceEventKind::from_uevent(&Uevent::parse(&uevent_buf(&fields)).unwrap())
+    };
+
+    assert_eq!(change(&["WEDGED=bus-reset"]), Some(DeviceEventKind::Wedged("bus-reset".to_string())));
+    assert_eq!(change(&["RESET=1", "PID=1234"]), Some(DeviceEventKind::Reset));
+    assert_eq!(change(&["HOTPLUG=1", "CONNECTOR=91"]), Some(DeviceEventKind::Hotplug));
+    assert_eq!(change(&[]), None);
+}
+
+#[test]
+fn test_uevent_parse_malformed() {
+    // from udevd
+    assert!(Uevent::parse(&uevent_buf(&["libudev", "ACTION=add", "DEVPATH=/devices/x"])).is_none());
+    // no ACTION or DEVPATH
+    assert!(Uevent::parse(&uevent_buf(&["add@/devices/x", "DEVPATH=/devices/x"])).is_none());
+    assert!(Uevent::parse(&uevent_buf(&["add@/devices/x", "ACTION=add"])).is_none());
+    assert!(Uevent::parse(&[]).is_none());
+    assert!(Uevent::parse(&[0xFF, 0xFE, 0]).is_none());
+
+    // invalid fields are skipped
+    let mut buf = uevent_buf(&["add@/devices/x", "ACTION=add", "DEVPATH=/devices/x", "NOVALUE"]);
+    buf.extend([0xFF, b'=', b'1', 0]);
+    let uevent = Uevent::parse(&buf).unwrap();
+
+    assert_eq!(uevent.subsystem, "");
+    assert_eq!(uevent.props.len(), 2);
+}
@@ -1,4 +1,8 @@
 use libamdgpu_top::{GuiWgpuBackend, PCI};
+use libamdgpu_top::stat::{PCSamplerConfig, PowerAttributionModel};
+use libamdgpu_top::control::{OdCommand, PerformanceLevel};
+use crate::device_control::ControlOpt;
+use crate::dump_pp_table::PPTableSpec;
 
 pub struct MainOpt {
     pub instance: Option<usize>, // index
@@ -16,6 +20,15 @@ pub struct MainOpt {
     pub decode_gpu_metrics: Option<String>,
     pub hide_fdinfo: bool,
     pub wgpu_backend: GuiWgpuBackend,
+    pub power_attribution: PowerAttributionModel,
+    pub summary: bool,
+    pub exec_cmd: Vec<String>,
+    pub control: ControlOpt,
+    pub fan_curve_config: Option<std::path::PathBuf>,
+    pub pc_config: Option<std::path::PathBuf>,
+    pub pc_sampler: PCSamplerConfig,
+    pub pp_table_diff: Option<[PPTableSpec; 2]>,
+    pub dump_vbios: Option<std::path::PathBuf>,
 }
 
 impl Default for MainOpt {
@@ -36,6 +49,15 @@ impl Default for MainOpt {
             decode_gpu_metrics: None,
             hide_fdinfo: false,
             wgpu_backend: GuiWgpuBackend::Gl,
+            power_attribution: PowerAttributionModel::default(),
+            summary: false,
+            exec_cmd: Vec::new(),
+            control: ControlOpt::default(),
+            fan_curve_config: None,
+            pc_config: None,
+            pc_sampler: PCSamplerConfig::default(),
+            pp_table_diff: None,
+            dump_vbios: None,
         }
     }
 }
@@ -71,6 +93,7 @@ pub enum OptDumpMode {
     NoOptDump,
     GpuMetrics,
     DrmInfo,
+    VbiosTables,
 }
 
 const HELP_MSG: &str = concat!(
@@ -84,6 +107,8 @@ const HELP_MSG: &str = concat!(
     "   -d, --dump\n",
     "       Dump AMDGPU info. (Specifications, VRAM, PCI, ResizableBAR, VBIOS, Video caps)\n",
     "       This option can be combined with the \"-J\" option.\n",
+    "       The loaded firmware is compared with the linux-firmware blobs,\n",
+    "       compressed blobs (.zst, .xz) require the \"zstd\" or \"xz\" command.\n",
     "   --list\n",
     "       Display a list of AMDGPU devices.\n",
     "   -J, --json\n",
@@ -109,9 +134,20 @@ const HELP_MSG: &str = concat!(
     "   --pp_table, --pp-table\n",
     "       Dump pp_table from sysfs and VBIOS for all AMD GPUs.\n",
     "       (only support Navi1x and Navi2x, Navi3x)\n",
+    "   --pp-table-diff [<A>,]<B>\n",
+    "       Compare two pp_tables field by field. (default: sysfs,vbios)\n",
+    "       <A>/<B> is \"sysfs\", \"vbios\", \"sysfs@<PCI>\", \"vbios@<PCI>\" or a path to a saved pp_table.\n",
+    "       If only <B> is given, <A> is the sysfs pp_table of the selected device.\n",
+    "       This option can be combined with the \"-J\" option.\n",
     "   --drm_info, --drm-info\n",
     "       Dump DRM info.\n",
     "       Inspired by https://gitlab.freedesktop.org/emersion/drm_info\n",
+    "   --vbios-tables\n",
+    "       Dump ATOM BIOS data tables. (firmware info, VRAM info, display objects,\n",
+    "       integrated system info, voltage objects)\n",
+    "       This option can be combined with the \"-d\" and \"-J\" options.\n",
+    "   --dump-vbios <Path>\n",
+    "       Save the VBIOS image (ROM) of the selected device to <Path>.\n",
     "   --xdna\n",
     "       Dump XDNA NPU info.\n",
     "   --dark, --dark-mode\n",
@@ -125,6 +161,15 @@ const HELP_MSG: &str = concat!(
     "   --vk, --vulkan\n",
     "       Use Vulkan API to the GUI backend,\n",
     "       and use APU/iGPU for GUI rendering if it is available.\n",
+    "   --summary\n",
+    "       Print the statistics (min/mean/max/p50/p95/p99) for the whole run on exit.\n",
+    "       In JSON mode (including \"--json-fifo\"), a summary object (DPM residency, energy,\n",
+    "       statistics) is output after the last iteration or on SIGINT/SIGTERM.\n",
+    "   --dry-run\n",
+    "       Print the sysfs writes of the \"--set-*\" options instead of writing.\n",
+    "   --restore-on-exit\n",
+    "       Apply the \"--set-*\" options, launch the selected mode,\n",
+    "       and restore the previous values on exit.\n",
     "   -V, --version\n",
     "       Print version information.\n",
     "   -h, --help\n",
@@ -145,7 +190,39 @@ const HELP_MSG: &str = concat!(
     "   --json_fifo, --json-fifo <String>\n",
     "       Output JSON formatted data to FIFO (named pipe) for other application and scripts.\n",
     "   --decode-gm <Path>, --decode-gpu-metrics <Path>\n",
-    "       Decode the specified gpu_metrics file.\n",
+    "       Decode the specified gpu_metrics file, every file in a directory,\n",
+    "       or stdin (\"-\"). Hex (plain, xxd, hexdump -C) and base64 text dumps are also accepted.\n",
+    "       This option can be combined with the \"-J\" option.\n",
+    "   --power-attribution <String>\n",
+    "       Model for estimating per-process power/energy from the device power. (default: gfx)\n",
+    "       (gfx: GFX/Compute usage weighted, engine: all engines usage weighted)\n",
+    "   --set-power-cap <u32>\n",
+    "       Set the power cap (power1_cap) in W for the selected device. (requires root)\n",
+    "   --set-perf-level <String>\n",
+    "       Set power_dpm_force_performance_level for the selected device. (requires root)\n",
+    "       (auto, low, high, manual, profile_standard, profile_min_sclk, profile_min_mclk,\n",
+    "        profile_peak, perf_determinism)\n",
+    "   --set-power-profile <String>\n",
+    "       Set pp_power_profile_mode by the profile name or index. (requires root)\n",
+    "   --set-od <String>\n",
+    "       Write the command to pp_od_clk_voltage and commit it. (requires root)\n",
+    "       (e.g. \"s 1 2500\", \"m 1 1000\", \"vc 2 2000 1100\", \"vo -50\")\n",
+    "       This option can be specified multiple times.\n",
+    "   --fan-curve <Path>\n",
+    "       Run as a daemon that applies the fan curve in the config file. (requires root)\n",
+    "       The automatic fan control is restored on exit.\n",
+    "   --pc-config <Path>\n",
+    "       Read the additional status registers in the config file as the performance counters,\n",
+    "       and show them next to GRBM/GRBM2. (TUI, GUI, JSON)\n",
+    "   --pc-rate <u32>\n",
+    "       Sampling rate in Hz of the performance counters. (default: 100Hz, max: 10000Hz)\n",
+    "   --pc-window <u64>, --pc-window <u64>ms\n",
+    "       Window length in milliseconds of the performance counters. (default: 1000ms)\n",
+    "       The usage is the rolling percentage over the window, independent of the refresh period.\n",
+    "   --exec -- <Command> [Args...]\n",
+    "       Launch the command, and report the GPU usage of the process tree,\n",
+    "       peak VRAM/GTT, clocks, temperature, energy and throttling when it exits.\n",
+    "       The exit code of the command is passed through.\n",
 );
 
 impl MainOpt {
@@ -315,6 +392,21 @@ impl MainOpt {
                 "--pp-table" | "--pp_table" => {
                     opt.dump_mode = DumpMode::PPTable;
                 },
+                "--pp-table-diff" | "--pp_table_diff" => {
+                    let s = match args.get(idx+1) {
+                        Some(s) if !s.starts_with('-') => {
+                            skip = true;
+                            s.as_str()
+                        },
+                        _ => "sysfs,vbios",
+                    };
+                    let (a, b) = s.split_once(',').unwrap_or(("sysfs", s));
+                    let [a, b] = [a, b].map(|s| s.parse::<PPTableSpec>().unwrap_or_else(|err| {
+                        eprintln!("{err}");
+                        std::process::exit(1);
+                    }));
+                    opt.pp_table_diff = Some([a, b]);
+                },
                 "--single" | "--single-gpu" => {
                     opt.single_gpu = true;
                 },
@@ -337,6 +429,17 @@ impl MainOpt {
                 "--drm-info" | "--drm_info" => {
                     opt.opt_dump_mode = OptDumpMode::DrmInfo;
                 },
+                "--vbios-tables" | "--vbios_tables" => {
+                    opt.opt_dump_mode = OptDumpMode::VbiosTables;
+                },
+                "--dump-vbios" | "--dump_vbios" => {
+                    let s = args.get(idx+1).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--dump-vbios <Path>\"");
+                        std::process::exit(1);
+                    });
+                    opt.dump_vbios = Some(s.into());
+                    skip = true;
+                },
                 "--dark" | "--dark-mode" => {
                     opt.is_dark_mode = Some(true);
                 },
@@ -344,11 +447,113 @@ impl MainOpt {
                     opt.is_dark_mode = Some(false);
                 },
                 "--hide-fdinfo" => opt.hide_fdinfo = true,
+                "--summary" => opt.summary = true,
+                "--power-attribution" => {
+                    let s = args.get(idx+1).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--power-attribution <String>\"");
+                        std::process::exit(1);
+                    });
+                    opt.power_attribution = s.parse::<PowerAttributionModel>().unwrap_or_else(|err| {
+                        eprintln!("{err}");
+                        std::process::exit(1);
+                    });
+                    skip = true;
+                },
                 "--gl" | "--opengl" => opt.wgpu_backend = GuiWgpuBackend::Gl,
                 "--vk" | "--vulkan" => opt.wgpu_backend = GuiWgpuBackend::Vulkan,
                 "--xdna" => {
                     opt.dump_mode = DumpMode::Xdna;
                 },
+                "--set-power-cap" => {
+                    let val = args.get(idx+1).and_then(|v| v.parse::<u32>().ok()).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--set-power-cap <u32>\"");
+                        std::process::exit(1);
+                    });
+                    opt.control.power_cap = Some(val);
+                    skip = true;
+                },
+                "--set-perf-level" => {
+                    let s = args.get(idx+1).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--set-perf-level <String>\"");
+                        std::process::exit(1);
+                    });
+                    let level = s.parse::<PerformanceLevel>().unwrap_or_else(|err| {
+                        eprintln!("{err}");
+                        std::process::exit(1);
+                    });
+                    opt.control.perf_level = Some(level);
+                    skip = true;
+                },
+                "--set-power-profile" => {
+                    let s = args.get(idx+1).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--set-power-profile <String>\"");
+                        std::process::exit(1);
+                    });
+                    opt.control.power_profile = Some(s.to_string());
+                    skip = true;
+                },
+                "--set-od" => {
+                    let s = args.get(idx+1).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--set-od <String>\"");
+                        std::process::exit(1);
+                    });
+                    let cmd = s.parse::<OdCommand>().unwrap_or_else(|err| {
+                        eprintln!("{err}");
+                        std::process::exit(1);
+                    });
+                    opt.control.od_commands.push(cmd);
+                    skip = true;
+                },
+                "--fan-curve" => {
+                    let s = args.get(idx+1).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--fan-curve <Path>\"");
+                        std::process::exit(1);
+                    });
+                    opt.fan_curve_config = Some(s.into());
+                    skip = true;
+                },
+                "--pc-config" => {
+                    let s = args.get(idx+1).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--pc-config <Path>\"");
+                        std::process::exit(1);
+                    });
+                    opt.pc_config = Some(s.into());
+                    skip = true;
+                },
+                "--pc-rate" => {
+                    let val = args.get(idx+1).and_then(|v| v.parse::<u32>().ok()).unwrap_or_else(|| {
+                        eprintln!("missing argument: \"--pc-rate <u32>\"");
+                        std::process::exit(1);
+                    });
+                    opt.pc_sampler.set_rate(val);
+                    skip = true;
+                },
+                "--pc-window" => {
+                    let val = args.get(idx+1)
+                        .and_then(|v| v.strip_suffix("ms").unwrap_or(v).parse::<u64>().ok())
+                        .unwrap_or_else(|| {
+                            eprintln!("missing argument: \"--pc-window <u64>\"");
+                            std::process::exit(1);
+                        });
+                    opt.pc_sampler.window = std::time::Duration::from_millis(val);
+                    skip = true;
+                },
+                "--dry-run" => opt.control.dry_run = true,
+                "--restore-on-exit" => opt.control.restore_on_exit = true,
+                "--exec" => {
+                    let cmd = match args.get(idx+1).map(|s| s.as_str()) {
+                        Some("--") => &args[idx+2..],
+                        _ => &args[idx+1..],
+                    };
+
+                    if cmd.is_empty() {
+                        eprintln!("missing argument: \"--exec -- <Command> [Args...]\"");
+                        std::process::exit(1);
+                    }
+
+                    opt.exec_cmd = cmd.to_vec();
+                    break;
+                },
                 _ => {
                     eprintln!("Unknown option: {arg}");
                     std::process::exit(1);
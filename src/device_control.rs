@@ -0,0 +1,99 @@
+use libamdgpu_top::DevicePath;
+use libamdgpu_top::control::{ControlError, DeviceControl, OdCommand, PerformanceLevel};
+
+#[derive(Debug, Clone, Default)]
+pub struct ControlOpt {
+    pub power_cap: Option<u32>, // W
+    pub perf_level: Option<PerformanceLevel>,
+    pub power_profile: Option<String>,
+    pub od_commands: Vec<OdCommand>,
+    pub dry_run: bool,
+    pub restore_on_exit: bool,
+}
+
+impl ControlOpt {
+    pub fn is_empty(&self) -> bool {
+        self.power_cap.is_none()
+        && self.perf_level.is_none()
+        && self.power_profile.is_none()
+        && self.od_commands.is_empty()
+    }
+}
+
+fn apply(ctrl: &mut DeviceControl, opt: &ControlOpt) -> Result<(), ControlError> {
+    // some ASICs require the "manual" performance level for changing the power profile and OD
+    if let Some(level) = opt.perf_level {
+        ctrl.set_perf_level(level)?;
+    }
+
+    if let Some(profile) = &opt.power_profile {
+        ctrl.set_power_profile(profile)?;
+    }
+
+    if let Some(watts) = opt.power_cap {
+        ctrl.set_power_cap(watts)?;
+    }
+
+    for cmd in &opt.od_commands {
+        ctrl.set_od(*cmd)?;
+    }
+
+    if !opt.od_commands.is_empty() {
+        ctrl.commit_od()?;
+    }
+
+    Ok(())
+}
+
+/// Returns `DeviceControl` to restore the previous values when it is dropped,
+/// if `--restore-on-exit` is specified.
+pub fn apply_device_control(device_path: &DevicePath, opt: &ControlOpt) -> Option<DeviceControl> {
+    let mut ctrl = DeviceControl::from_device_path(device_path);
+    ctrl.dry_run = opt.dry_run;
+
+    let r = apply(&mut ctrl, opt);
+
+    if opt.dry_run {
+        println!("# {} ({})", device_path.device_name, device_path.pci);
+
+        for w in ctrl.history() {
+            println!("{w}");
+        }
+    }
+
+    if let Err(e) = r {
+        eprintln!("{e}");
+
+        if ctrl.has_saved_values() {
+            eprintln!("Restoring the previous values.");
+
+            if let Err(e) = ctrl.restore() {
+                eprintln!("{e}");
+            }
+        }
+
+        std::process::exit(1);
+    }
+
+    if opt.dry_run {
+        if opt.restore_on_exit {
+            let len = ctrl.history().len();
+            let _ = ctrl.restore();
+
+            println!("# restore on exit");
+
+            for w in &ctrl.history()[len..] {
+                println!("{w}");
+            }
+        }
+
+        std::process::exit(0);
+    }
+
+    if opt.restore_on_exit {
+        ctrl.restore_on_drop = true;
+        Some(ctrl)
+    } else {
+        None
+    }
+}
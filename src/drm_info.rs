@@ -3,6 +3,10 @@ use libamdgpu_top::{
     drmModePropType,
     // ConnectorInfo,
     ModeProp,
+    edid::Edid,
+    DrmModeObjects,
+    fourcc_string,
+    modifier_string,
 };
 
 pub fn dump_all_drm_info(device_path_list: &[DevicePath]) {
@@ -57,6 +61,10 @@ pub fn dump_drm_info(device_path: &DevicePath) {
             }
         }
 
+        if let Some(edid) = &conn.edid {
+            dump_edid(edid, last);
+        }
+
         let props_len = conn.mode_props.len() - 1;
 
         for (j, mode_prop) in conn.mode_props.iter().enumerate() {
@@ -64,6 +72,171 @@ pub fn dump_drm_info(device_path: &DevicePath) {
             dump_mode_prop(mode_prop, last, last_prop);
         }
     }
+
+    if let Some(objects) = libamdgpu_top::drm_mode_objects(device_path) {
+        dump_drm_mode_objects(&objects);
+    }
+}
+
+fn tree_branch(last: bool) -> &'static str {
+    if last { "└" } else { "├" }
+}
+
+fn fourcc_list(formats: &[u32]) -> String {
+    formats.iter().map(|f| fourcc_string(*f)).collect::<Vec<_>>().join(" ")
+}
+
+pub fn dump_drm_mode_objects(objects: &DrmModeObjects) {
+    println!(
+        "\nFB Size: min {}x{}, max {}x{}",
+        objects.min_size.0,
+        objects.min_size.1,
+        objects.max_size.0,
+        objects.max_size.1,
+    );
+
+    println!("\nCRTCs");
+
+    for (i, crtc) in objects.crtcs.iter().enumerate() {
+        let mode = crtc.mode.as_ref().map_or("inactive".to_string(), |m| format!(
+            "{}x{}@{:.2}",
+            m.hdisplay,
+            m.vdisplay,
+            m.refresh_rate,
+        ));
+
+        println!(
+            "{}───CRTC {} (id = {}): {mode}, gamma size: {}, FB: {}, position: ({}, {})",
+            tree_branch(i + 1 == objects.crtcs.len()),
+            crtc.index,
+            crtc.crtc_id,
+            crtc.gamma_size,
+            crtc.fb_id,
+            crtc.position.0,
+            crtc.position.1,
+        );
+    }
+
+    println!("\nEncoders");
+
+    for (i, encoder) in objects.encoders.iter().enumerate() {
+        println!(
+            "{}───Encoder (id = {}): {}, CRTC: {}, possible CRTCs: {:#06b}",
+            tree_branch(i + 1 == objects.encoders.len()),
+            encoder.encoder_id,
+            encoder.type_name(),
+            encoder.crtc_id,
+            encoder.possible_crtcs,
+        );
+    }
+
+    println!("\nPlanes");
+
+    for (i, plane) in objects.planes.iter().enumerate() {
+        let last = i + 1 == objects.planes.len();
+        let pad = if last { " " } else { "│" };
+
+        println!(
+            "{}───Plane (id = {}): {}, CRTC: {}, possible CRTCs: {:#06b}",
+            tree_branch(last),
+            plane.plane_id,
+            plane.plane_type.map_or("Unknown".to_string(), |t| t.to_string()),
+            plane.crtc_id,
+            plane.possible_crtcs,
+        );
+
+        if let Some(fb) = &plane.fb {
+            println!(
+                "{pad}    ├───FB (id = {}): {}x{}, {}{}",
+                fb.fb_id,
+                fb.width,
+                fb.height,
+                fourcc_string(fb.pixel_format),
+                fb.modifier.map_or(String::new(), |m| format!(", {}", modifier_string(m))),
+            );
+        }
+
+        println!(
+            "{pad}    {}───Formats: {}",
+            tree_branch(plane.in_formats.is_empty()),
+            fourcc_list(&plane.formats),
+        );
+
+        if plane.in_formats.is_empty() { continue }
+
+        println!("{pad}    └───IN_FORMATS");
+
+        for (j, (modifier, formats)) in plane.in_formats.iter().enumerate() {
+            println!(
+                "{pad}         {}───{}: {}",
+                tree_branch(j + 1 == plane.in_formats.len()),
+                modifier_string(*modifier),
+                fourcc_list(formats),
+            );
+        }
+    }
+}
+
+pub fn dump_edid(edid: &Edid, last: bool) {
+    let pad = if last { " " } else { "│" };
+    let mut lines = vec![
+        format!(
+            "Serial: {:#010X}{}",
+            edid.serial_number,
+            edid.serial_string.as_ref().map_or(String::new(), |s| format!(", {s:?}")),
+        ),
+        format!("Manufactured: week {}, {}", edid.week, edid.year),
+        format!("Version: {}.{}", edid.version.0, edid.version.1),
+    ];
+
+    if let Some((h, v)) = edid.physical_size {
+        lines.push(format!("Physical Size: {h}x{v} cm"));
+    }
+
+    if let Some(r) = &edid.range_limits {
+        lines.push(format!(
+            "Range Limits: {}-{} Hz (V), {}-{} kHz (H), {} MHz",
+            r.min_v_rate,
+            r.max_v_rate,
+            r.min_h_rate,
+            r.max_h_rate,
+            r.max_pixel_clock,
+        ));
+    }
+
+    if let Some((min, max)) = edid.vrr_range() {
+        lines.push(format!("FreeSync/VRR Range: {min}-{max} Hz"));
+    }
+
+    if let Some(hdr) = edid.hdr_static_metadata() {
+        let lum = |v: Option<f32>| v.map_or("_".to_string(), |v| format!("{v:.4}"));
+
+        lines.push(format!(
+            "HDR Static Metadata: EOTF {:?}, Max: {} cd/m^2, Max Frame-Avg: {} cd/m^2, Min: {} cd/m^2",
+            hdr.eotf_list(),
+            lum(hdr.max_luminance),
+            lum(hdr.max_frame_avg_luminance),
+            lum(hdr.min_luminance),
+        ));
+    }
+
+    println!("{pad}    ├───EDID: {}", edid.name());
+
+    for line in lines {
+        println!("{pad}    │    ├───{line}");
+    }
+
+    println!("{pad}    │    └───Timings");
+
+    let timings: Vec<_> = edid.all_timings().collect();
+    let timings_len = timings.len().saturating_sub(1);
+
+    for (i, timing) in timings.iter().enumerate() {
+        println!(
+            "{pad}    │         {}───{timing}",
+            if i == timings_len { "└" } else { "├" },
+        );
+    }
 }
 
 pub fn dump_mode_prop((mode_prop, value): &(ModeProp, u64), last: bool, last_prop: bool) {
@@ -1,8 +1,7 @@
 use libamdgpu_top::{
     AMDGPU::{
         VIDEO_CAPS::CODEC,
-        FW_VERSION::{FW_TYPE, FwVer},
-        DeviceHandle,
+        FW_VERSION::FW_TYPE,
         GPU_INFO,
         GpuMetrics,
         MetricsInfo,
@@ -10,9 +9,11 @@ use libamdgpu_top::{
     AppDeviceInfo,
     // DeviceHandle,
     DevicePath,
+    firmware::{self, FirmwareCheck, FirmwareIpVersions, FirmwareKind},
+    gpu_metrics::GpuMetricsDecode,
     stat::Sensors,
 };
-use crate::{OptDumpMode, drm_info};
+use crate::{OptDumpMode, drm_info, dump_vbios};
 
 pub fn dump_gpu_metrics(title: &str, device_path_list: &[DevicePath]) {
     println!("{title}");
@@ -91,7 +92,7 @@ pub fn dump(device_path: &DevicePath, opt_dump_mode: OptDumpMode) {
     if !info.ip_die_entries.is_empty() {
         info.ip_discovery_table();
     }
-    fw_info(&amdgpu_dev);
+    fw_info(&info);
     info.codec_info();
     info.vbios_info();
 
@@ -117,6 +118,10 @@ pub fn dump(device_path: &DevicePath, opt_dump_mode: OptDumpMode) {
     if let OptDumpMode::DrmInfo = opt_dump_mode {
         drm_info::dump_drm_info(device_path);
     }
+
+    if let OptDumpMode::VbiosTables = opt_dump_mode {
+        dump_vbios::dump_vbios_tables(device_path);
+    }
 }
 
 fn sensors_info(sensors: &Sensors) {
@@ -174,60 +179,67 @@ fn sensors_info(sensors: &Sensors) {
     }
 }
 
-fn fw_info(amdgpu_dev: &DeviceHandle) {
-    const FW_LIST: &[FW_TYPE] = &[
-        FW_TYPE::VCE,
-        FW_TYPE::UVD,
-        FW_TYPE::GMC,
-        FW_TYPE::GFX_ME,
-        FW_TYPE::GFX_PFP,
-        FW_TYPE::GFX_CE,
-        FW_TYPE::GFX_RLC,
-        FW_TYPE::GFX_MEC,
-        FW_TYPE::SMC,
-        FW_TYPE::SDMA,
-        FW_TYPE::SOS,
-        FW_TYPE::ASD,
-        FW_TYPE::VCN,
-        FW_TYPE::GFX_RLC_RESTORE_LIST_CNTL,
-        FW_TYPE::GFX_RLC_RESTORE_LIST_GPM_MEM,
-        FW_TYPE::GFX_RLC_RESTORE_LIST_SRM_MEM,
-        FW_TYPE::DMCU,
-        FW_TYPE::TA,
-        FW_TYPE::DMCUB,
-        FW_TYPE::TOC,
-    ];
-
+fn fw_info(info: &AppDeviceInfo) {
     println!("\nFirmware info:");
 
-    for fw_type in FW_LIST {
-        let fw_info = match amdgpu_dev.query_firmware_version(*fw_type, 0, 0) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        fw_info_dump(&fw_info);
-    }
-
-    /* MEC2 */
-    if let Ok(mec2) = amdgpu_dev.query_firmware_version(FW_TYPE::GFX_MEC, 0, 1) {
+    for fw in &info.firmware.list {
         println!(
             "    {:<8} feature: {:>3}, ver: {:>#10X}",
-            "GFX_MEC2",
-            mec2.feature,
-            mec2.version,
+            fw.name,
+            fw.feature,
+            fw.version,
         );
     }
-}
 
-fn fw_info_dump(fw_info: &FwVer) {
-    let (ver, ftr) = (fw_info.version, fw_info.feature);
+    let loaded: Vec<(FirmwareKind, u32)> = info.firmware.list
+        .iter()
+        .filter(|fw| fw.index == 0 || matches!(fw.fw_type, FW_TYPE::GFX_MEC))
+        .filter_map(|fw| Some((fw.kind()?, fw.version)))
+        .collect();
 
-    if ver == 0 { return; }
+    linux_firmware_check(info, &loaded);
+}
 
-    println!(
-        "    {fw_type:<8} feature: {ftr:>3}, ver: {ver:>#10X}",
-        fw_type = fw_info.fw_type.to_string(),
-    );
+fn linux_firmware_check(info: &AppDeviceInfo, loaded: &[(FirmwareKind, u32)]) {
+    let dir = std::path::Path::new(firmware::LINUX_FIRMWARE_DIR);
+
+    if !dir.is_dir() { return }
+
+    let ip_list: Vec<(String, (u32, u32, u32))> = info.ip_die_entries
+        .first()
+        .map(|die| die.ip_hw_ids.iter().filter_map(|ip_hw| {
+            let inst = ip_hw.instances.first()?;
+
+            Some((
+                ip_hw.hw_id.to_string(),
+                (u32::from(inst.major), u32::from(inst.minor), u32::from(inst.revision)),
+            ))
+        }).collect())
+        .unwrap_or_default();
+    let ip = FirmwareIpVersions::from_ip_list(ip_list.iter().map(|(name, ver)| (name.as_str(), *ver)));
+    // Polaris, Vega10/12/20 and Raven do not have the IP discovery table
+    let ip = if ip_list.is_empty() {
+        ip.with_chip_name(&info.asic_name.to_string())
+    } else {
+        ip
+    };
+
+    println!("\nlinux-firmware ({}):", dir.display());
+
+    for (kind, version) in loaded {
+        let check = FirmwareCheck::check(dir, &ip, *kind, *version);
+        let blob = check.blob
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map_or(String::new(), |name| name.to_string_lossy().to_string());
+        let installed = check.installed.map_or("_".to_string(), |h| format!("{:#X}", h.ucode_version));
+
+        println!(
+            "    {:<8} loaded: {version:>#10X}, installed: {installed:>10} {blob:<28} [{}]",
+            kind.to_string(),
+            check.status,
+        );
+    }
 }
 
 trait DumpInfo {
@@ -408,8 +420,28 @@ impl DumpInfo for AppDeviceInfo {
     }
 }
 
-pub fn decode_gpu_metrics(path: &str) -> GpuMetrics {
-    let mut buf = Vec::with_capacity(128);
+pub fn dump_decoded_gpu_metrics(list: &[GpuMetricsDecode]) {
+    for d in list {
+        println!("--------\n{}", d.source);
+
+        match &d.metrics {
+            Ok(m) => println!("{m:#?}"),
+            Err(err) => {
+                println!("Error: {err}");
+
+                if let Some(h) = d.header {
+                    println!(
+                        "Header: {h} (structure_size: {}, format_revision: {}, content_revision: {})",
+                        h.structure_size,
+                        h.format_revision,
+                        h.content_revision,
+                    );
+                }
 
-    GpuMetrics::read_file_with_buffer(&mut buf, path).unwrap()
+                for (offset, val) in d.raw_fields() {
+                    println!("    {offset:#06X}: {val:#010X} ({val})");
+                }
+            },
+        }
+    }
 }
@@ -1,11 +1,8 @@
+use std::path::PathBuf;
 use libamdgpu_top::{
-    AMDGPU::{
-        self,
-        HwId,
-        IpHwId,
-        PPTable,
-    },
+    pp_table::{self, DebugValue, PPTableFieldDiff, PPTableSource, PPTables},
     DevicePath,
+    PCI,
 };
 
 pub fn dump_all_pp_table(title: &str, device_path_list: &[DevicePath]) {
@@ -28,45 +25,122 @@ fn dump_pp_table(device_path: &DevicePath) {
         );
     }
 
-    let sysfs = &device_path.sysfs_path;
-    let smu = IpHwId::get_from_die_id_sysfs(HwId::MP1, sysfs.join("ip_discovery/die/0/")).ok().and_then(|smu| smu.instances.first().cloned());
+    let pp_tables = PPTables::get(device_path, &amdgpu_dev);
 
-    if let Some(smu) = &smu {
-        println!("    SMU (MP1) version: {}.{}.{}", smu.major, smu.minor, smu.revision);
+    if let Some((major, minor, rev)) = pp_tables.smu_version {
+        println!("    SMU (MP1) version: {major}.{minor}.{rev}");
     }
 
-    let pp_table_bytes_sysfs = std::fs::read(sysfs.join("pp_table")).ok();
-    let pp_table_bytes_vbios = amdgpu_dev.get_vbios_image().ok().and_then(|vbios_image| {
-        use AMDGPU::VBIOS::VbiosParser;
+    for dump in &pp_tables.tables {
+        if let Some(pp_table) = &dump.table {
+            println!("    from {}: {pp_table:#?}", dump.source);
+        } else {
+            println!("    from {}: N/A", dump.source);
+        }
+    }
 
-        let vbios_parser = VbiosParser::new(vbios_image);
-        let rom_header = vbios_parser.get_atom_rom_header()?;
-        let data_table = vbios_parser.get_atom_data_table(&rom_header)?;
+    println!();
+}
 
-        Some(vbios_parser.get_powerplay_table_bytes(&data_table)?.to_vec())
-    });
+#[derive(Debug, Clone)]
+pub enum PPTableSpec {
+    Device { source: PPTableSource, pci: Option<PCI::BUS_INFO> },
+    File(PathBuf),
+}
 
-    for (bytes, src) in [
-        (pp_table_bytes_sysfs, "sysfs"),
-        (pp_table_bytes_vbios, "VBIOS"),
-    ] {
-        let Some(bytes) = bytes else {
-            println!("    from {src}: N/A");
-            continue;
-        };
+impl std::str::FromStr for PPTableSpec {
+    type Err = String;
 
-        let pp_table = if let Some(smu) = &smu {
-            PPTable::decode_with_smu_version(&bytes, smu.version())
-        } else {
-            PPTable::decode(&bytes)
+    // "sysfs", "vbios", "sysfs@<PCI>", "vbios@<PCI>" or a path to a saved pp_table
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, pci) = s.split_once('@').map_or((s, None), |(n, p)| (n, Some(p)));
+        let source = match name.to_ascii_lowercase().as_str() {
+            "sysfs" => PPTableSource::Sysfs,
+            "vbios" => PPTableSource::Vbios,
+            _ if pci.is_none() => return Ok(Self::File(PathBuf::from(s))),
+            _ => return Err(format!("Unknown pp_table source: {name:?}")),
         };
+        let pci = pci
+            .map(|p| p.parse::<PCI::BUS_INFO>().map_err(|_| format!("Failed to parse from {p:?} to `PCI::BUS_INFO`")))
+            .transpose()?;
 
-        if let Ok (pp_table) = &pp_table {
-            println!("    from {src}: {pp_table:#?}");
-        } else {
-            println!("    from {src}: N/A");
+        Ok(Self::Device { source, pci })
+    }
+}
+
+impl PPTableSpec {
+    fn load(&self, selected: &DevicePath) -> Result<(String, DebugValue), String> {
+        match self {
+            Self::Device { source, pci } => {
+                let device_path = match pci {
+                    Some(pci) => DevicePath::try_from(*pci).map_err(|err| err.to_string())?,
+                    None => selected.clone(),
+                };
+                let label = format!("{source} ({})", device_path.pci);
+                let amdgpu_dev = device_path.init().map_err(|err| format!("{label}: {err:?}"))?;
+                let tree = PPTables::get(&device_path, &amdgpu_dev)
+                    .tables
+                    .iter()
+                    .find(|dump| dump.source == *source)
+                    .and_then(|dump| dump.tree())
+                    .ok_or(format!("{label}: N/A"))?;
+
+                Ok((label, tree))
+            },
+            Self::File(path) => {
+                let label = path.display().to_string();
+                let bytes = std::fs::read(path).map_err(|err| format!("{label}: {err}"))?;
+                let tree = pp_table::decode_pp_table(&bytes, &selected.sysfs_path)
+                    .and_then(|table| pp_table::pp_table_tree(&table))
+                    .ok_or(format!("{label}: failed to decode pp_table"))?;
+
+                Ok((label, tree))
+            },
         }
     }
+}
 
-    println!();
+pub fn pp_table_diff(
+    specs: &[PPTableSpec; 2],
+    selected: &DevicePath,
+) -> Result<(String, String, Vec<PPTableFieldDiff>), String> {
+    let (label_a, a) = specs[0].load(selected)?;
+    let (label_b, b) = specs[1].load(selected)?;
+
+    Ok((label_a, label_b, pp_table::diff_pp_table(&a, &b)))
+}
+
+pub fn dump_pp_table_diff(title: &str, specs: &[PPTableSpec; 2], selected: &DevicePath) {
+    let (label_a, label_b, diff) = pp_table_diff(specs, selected).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+
+    println!("{title}\n");
+    println!("pp_table diff");
+    println!("    A: {label_a}");
+    println!("    B: {label_b}");
+
+    if diff.is_empty() {
+        println!("\nNo differences.");
+        return;
+    }
+
+    let mut category = None;
+
+    for d in &diff {
+        if category != Some(d.category) {
+            println!("\n{}", d.category);
+            category = Some(d.category);
+        }
+
+        println!(
+            "    {}: {} -> {}",
+            d.path,
+            d.a.as_ref().map_or("(none)".to_string(), |v| v.to_string()),
+            d.b.as_ref().map_or("(none)".to_string(), |v| v.to_string()),
+        );
+    }
+
+    println!("\n{} field(s) differ", diff.len());
 }
@@ -0,0 +1,172 @@
+use std::path::Path;
+use libamdgpu_top::{
+    atom::AtomBios,
+    DevicePath,
+};
+
+pub fn save_vbios(device_path: &DevicePath, path: &Path) {
+    let amdgpu_dev = device_path.init().unwrap_or_else(|err| {
+        eprintln!("{err:?}");
+        std::process::exit(1);
+    });
+    let vbios_image = amdgpu_dev.get_vbios_image().unwrap_or_else(|err| {
+        eprintln!("Failed to get the VBIOS image: {err:?}");
+        std::process::exit(1);
+    });
+
+    if let Err(err) = std::fs::write(path, &vbios_image) {
+        eprintln!("{}: {err}", path.display());
+        std::process::exit(1);
+    }
+
+    println!("Saved VBIOS of {} ({} bytes) to {}", device_path.pci, vbios_image.len(), path.display());
+}
+
+pub fn atom_bios(device_path: &DevicePath) -> Option<AtomBios> {
+    let amdgpu_dev = device_path.init().ok()?;
+    let vbios_image = amdgpu_dev.get_vbios_image().ok()?;
+
+    AtomBios::parse(&vbios_image)
+}
+
+pub fn dump_all_vbios_tables(title: &str, device_path_list: &[DevicePath]) {
+    println!("{title}\n");
+
+    for (i, device_path) in device_path_list.iter().enumerate() {
+        println!("\n--------\n#{i} {}", device_path.pci);
+        dump_vbios_tables(device_path);
+    }
+}
+
+pub fn dump_vbios_tables(device_path: &DevicePath) {
+    let Some(atom) = atom_bios(device_path) else {
+        println!("\nATOM BIOS tables: N/A");
+        return;
+    };
+
+    println!("\nATOM BIOS tables:");
+    println!("    ROM header: {}", atom.rom_header);
+    println!("    Subsystem : {:04X}:{:04X}", atom.subsystem_vendor_id, atom.subsystem_id);
+    println!("    Data tables:");
+
+    for t in &atom.data_tables {
+        println!("        [{:2}] {:<22} {:#06X}, {}", t.index, t.name, t.offset, t.header);
+    }
+
+    if let Some(fw) = &atom.firmware_info {
+        println!("\n    Firmware info ({}):", fw.header);
+        println!("        firmware revision: {:#010X}", fw.firmware_revision);
+        println!("        bootup SCLK      : {:5} MHz", fw.bootup_sclk_mhz);
+        println!("        bootup MCLK      : {:5} MHz", fw.bootup_mclk_mhz);
+
+        for (label, mv) in [
+            ("bootup VDDC      ", fw.bootup_vddc_mv),
+            ("bootup VDDCI     ", fw.bootup_vddci_mv),
+            ("bootup MVDDC     ", fw.bootup_mvddc_mv),
+            ("bootup VDDGFX    ", fw.bootup_vddgfx_mv),
+        ] {
+            let Some(mv) = mv else { continue };
+            println!("        {label}: {mv:5} mV");
+        }
+
+        if let Some(id) = fw.mem_module_id {
+            println!("        mem module id    : {id}");
+        }
+
+        if let Some(id) = fw.cooling_solution_id {
+            println!("        cooling solution : {id}");
+        }
+
+        if let Some(cap) = fw.firmware_capability {
+            println!("        capability       : {cap:#010X} {:?}", fw.capabilities());
+        }
+    }
+
+    if let Some(vram) = &atom.vram_info {
+        println!("\n    VRAM info ({}):", vram.header);
+
+        for (i, m) in vram.modules.iter().enumerate() {
+            println!(
+                "        module #{i}: {}, {} (rev {}), {}ch x {}-bit, density: {:#04X}, [{}]",
+                m.memory_type_name(),
+                m.vendor_name(),
+                m.revision_id,
+                m.channel_num,
+                m.channel_width,
+                m.density,
+                m.part_number,
+            );
+
+            if let Some(size) = m.memory_size_mib {
+                println!("            size: {size} MiB");
+            }
+
+            if let Some(clk) = m.max_mem_clk_mhz {
+                println!("            max MCLK: {clk} MHz");
+            }
+
+            if let Some(mv) = m.mem_voltage_mv {
+                println!("            voltage: {mv} mV");
+            }
+        }
+
+        for t in &vram.gddr6_ac_timings {
+            let timings: Vec<String> = t.timings.iter().map(|(name, val)| format!("{name}={val}")).collect();
+
+            println!(
+                "        GDDR6 AC timing (block {}, <= {} MHz): {}",
+                t.mem_block_id,
+                t.max_memclk_mhz,
+                timings.join(" "),
+            );
+        }
+    }
+
+    if let Some(disp) = &atom.display_object_info {
+        println!("\n    Display object info ({}):", disp.header);
+        println!("        supported devices: {:#06X}", disp.supported_devices);
+
+        for p in &disp.paths {
+            println!(
+                "        {:<20} <- {:<18} device tag: {:#06X}",
+                p.connector_name(),
+                p.encoder_name(),
+                p.device_tag,
+            );
+        }
+    }
+
+    if let Some(igp) = &atom.integrated_system_info {
+        println!("\n    Integrated system info ({}):", igp.header);
+        println!("        vbios misc       : {:#010X}", igp.vbios_misc);
+        println!("        GPU cap info     : {:#010X}", igp.gpu_cap_info);
+        println!("        system config    : {:#010X}", igp.system_config);
+        println!("        CPU cap info     : {:#010X}", igp.cpu_cap_info);
+        println!("        GPUCLK SS        : {:.2} %", igp.gpuclk_ss_percentage as f32 / 100.0);
+        println!("        memory type      : {:#04X}", igp.memory_type);
+        println!("        UMA channels     : {}", igp.uma_channel_number);
+
+        if let [Some(tmp), Some(hyst)] = [igp.htc_tmp_limit, igp.htc_hyst_limit] {
+            println!("        HTC limit        : {tmp} C (hysteresis {hyst} C)");
+        }
+    }
+
+    if !atom.voltage_objects.is_empty() {
+        println!("\n    Voltage objects:");
+
+        for obj in &atom.voltage_objects {
+            let details: Vec<String> = obj.details.iter().map(|(name, val)| format!("{name}={val}")).collect();
+
+            println!(
+                "        {:<12} {:<16} {}",
+                obj.voltage_type_name(),
+                obj.voltage_mode_name(),
+                details.join(" "),
+            );
+
+            if !obj.gpio_lut_mv.is_empty() {
+                println!("            GPIO LUT: {:?} mV", obj.gpio_lut_mv);
+            }
+        }
+    }
+}
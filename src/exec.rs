@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+use libamdgpu_top::{stat, summary, DevicePath};
+use libamdgpu_top::app::{AppAmdgpuTop, AppOption};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Default)]
+struct EngineTime {
+    gfx: Duration,
+    compute: Duration,
+    dma: Duration,
+    media: Duration,
+    vpe: Duration,
+}
+
+#[derive(Default)]
+struct ExecStat {
+    tree: HashSet<i32>,
+    engine: EngineTime,
+    peak_vram: u64, // KiB
+    peak_gtt: u64, // KiB
+}
+
+fn get_ppid(pid: i32) -> Option<i32> {
+    let s = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // "<pid> (<comm>) <state> <ppid> ..."
+    let (_, s) = s.rsplit_once(") ")?;
+
+    s.split(' ').nth(1)?.parse().ok()
+}
+
+fn get_all_pids() -> Vec<i32> {
+    let Ok(proc_dir) = std::fs::read_dir("/proc") else { return Vec::new() };
+
+    proc_dir
+        .filter_map(|dir_entry| dir_entry.ok()?.file_name().to_str()?.parse::<i32>().ok())
+        .collect()
+}
+
+impl ExecStat {
+    fn new(pid: i32) -> Self {
+        Self { tree: HashSet::from([pid]), ..Default::default() }
+    }
+
+    // exited processes are kept in the tree
+    fn update_tree(&mut self) {
+        let ppids: Vec<(i32, i32)> = get_all_pids()
+            .into_iter()
+            .filter(|pid| !self.tree.contains(pid))
+            .filter_map(|pid| Some((pid, get_ppid(pid)?)))
+            .collect();
+
+        loop {
+            let len = self.tree.len();
+
+            for (pid, ppid) in &ppids {
+                if self.tree.contains(ppid) {
+                    self.tree.insert(*pid);
+                }
+            }
+
+            if len == self.tree.len() { break }
+        }
+    }
+
+    fn update_index(&self, app: &AppAmdgpuTop) {
+        // amdgpu_top itself is included to avoid closing the device handle
+        let mut pids: Vec<i32> = self.tree.iter().copied().collect();
+        pids.push(std::process::id() as i32);
+
+        let device_path = &app.device_path;
+        let mut proc_index = app.stat.arc_proc_index.lock().unwrap();
+
+        stat::update_index_by_all_proc(
+            &mut proc_index,
+            &[&device_path.render, &device_path.card],
+            &pids,
+        );
+    }
+
+    fn sample(&mut self, app: &mut AppAmdgpuTop, pre_sample: &mut Instant) {
+        let now = Instant::now();
+        let interval = now.duration_since(*pre_sample);
+        *pre_sample = now;
+
+        self.update_tree();
+        self.update_index(app);
+        app.update(interval);
+        self.update_usage(app, interval);
+    }
+
+    fn update_usage(&mut self, app: &AppAmdgpuTop, interval: Duration) {
+        let self_pid = std::process::id() as i32;
+        let mut vram = 0;
+        let mut gtt = 0;
+        let per = |usage: i64| interval.mul_f64(usage.max(0) as f64 / 100.0);
+
+        for pu in app.stat.fdinfo.proc_usage.iter().filter(|pu| pu.pid != self_pid) {
+            self.engine.gfx += per(pu.usage.gfx);
+            self.engine.compute += per(pu.usage.compute);
+            self.engine.dma += per(pu.usage.dma);
+            self.engine.media += per(pu.usage.media);
+            self.engine.vpe += per(pu.usage.vpe);
+            vram += pu.usage.vram_usage;
+            gtt += pu.usage.gtt_usage;
+        }
+
+        self.peak_vram = self.peak_vram.max(vram);
+        self.peak_gtt = self.peak_gtt.max(gtt);
+    }
+}
+
+fn exit_code(status: &ExitStatus) -> i32 {
+    status.code().or_else(|| status.signal().map(|sig| 128 + sig)).unwrap_or(1)
+}
+
+fn print_report(
+    cmd: &[String],
+    app: &AppAmdgpuTop,
+    exec_stat: &ExecStat,
+    wall_time: Duration,
+    status: &ExitStatus,
+) {
+    let wall = wall_time.as_secs_f64();
+    let label = app.device_path.menu_entry();
+
+    eprintln!();
+    eprintln!(" GPU usage stats for '{}' on {label}:", cmd.join(" "));
+    eprintln!();
+    eprintln!(" {:<24} {wall:>10.3} s", "Wall time");
+    eprintln!(" {:<24} {:>10}", "Exit status", exit_code(status));
+    eprintln!();
+    eprintln!(" Engine usage (process tree)       Busy time");
+
+    for (name, t, flag) in [
+        ("GFX", exec_stat.engine.gfx, true),
+        ("Compute", exec_stat.engine.compute, true),
+        ("DMA", exec_stat.engine.dma, true),
+        ("Media", exec_stat.engine.media, true),
+        ("VPE", exec_stat.engine.vpe, app.stat.fdinfo.has_vpe),
+    ] {
+        if !flag { continue }
+
+        let t = t.as_secs_f64();
+        let per = if wall == 0.0 { 0.0 } else { t * 100.0 / wall };
+
+        eprintln!("  {name:<23} {per:>9.1}% {t:>10.3} s");
+    }
+
+    eprintln!();
+    eprintln!(" {:<24} {:>10} MiB", "Peak VRAM", exec_stat.peak_vram >> 10);
+    eprintln!(" {:<24} {:>10} MiB", "Peak GTT", exec_stat.peak_gtt >> 10);
+
+    if let Some(s) = &app.summary {
+        let s = s.lock().unwrap();
+
+        for (label, unit) in [
+            ("SCLK", "MHz"),
+            ("MCLK", "MHz"),
+            ("Edge Temp.", "C"),
+            ("Junction Temp.", "C"),
+        ] {
+            let Some(v) = s.get(label).and_then(|stat| stat.calc()) else { continue };
+
+            eprintln!(
+                " {:<24} {:>10.0} {unit} (max: {:.0} {unit})",
+                format!("{label} (avg)"),
+                v.mean,
+                v.max,
+            );
+        }
+    }
+
+    if let Some(source) = app.stat.energy.source {
+        eprintln!(
+            " {:<24} {:>10.3} Wh ({source})",
+            "Energy",
+            app.stat.energy.session_wh(),
+        );
+    }
+
+    let mut throttlers: Vec<(&str, Duration)> = Vec::new();
+
+    for ev in &app.stat.throttle_log.events {
+        if let Some((_, d)) = throttlers.iter_mut().find(|(name, _)| *name == ev.throttler) {
+            *d += ev.duration;
+        } else {
+            throttlers.push((&ev.throttler, ev.duration));
+        }
+    }
+
+    if throttlers.is_empty() {
+        eprintln!(" {:<24} {:>10}", "Throttling", "No");
+    } else {
+        eprintln!(" {:<24} {:>10}", "Throttling", "Yes");
+
+        for (name, d) in throttlers {
+            eprintln!("  {name:<23} {:>10.3} s", d.as_secs_f64());
+        }
+    }
+
+    eprintln!();
+}
+
+pub fn run_exec(device_path: &DevicePath, cmd: &[String], refresh_period: u64) -> i32 {
+    let Ok(amdgpu_dev) = device_path.init() else {
+        eprintln!("{}: failed to open the device", device_path.menu_entry());
+        return 1;
+    };
+    let app_opt = AppOption { summary: Some(Default::default()), ..Default::default() };
+    let Some(mut app) = AppAmdgpuTop::new(amdgpu_dev, device_path.clone(), &app_opt) else {
+        eprintln!("{}: failed to initialize", device_path.menu_entry());
+        return 1;
+    };
+    let period = Duration::from_millis(refresh_period);
+
+    // the signal is also sent to the command, wait for it to exit and print the report
+    summary::set_terminate_handler();
+
+    let start = Instant::now();
+    let mut child = match Command::new(&cmd[0]).args(&cmd[1..]).spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            eprintln!("{}: {err}", cmd[0]);
+            return if err.kind() == io::ErrorKind::NotFound { 127 } else { 126 };
+        },
+    };
+    let mut exec_stat = ExecStat::new(child.id() as i32);
+    let mut pre_sample = start;
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {},
+            Err(err) => {
+                eprintln!("{}: {err}", cmd[0]);
+                return 1;
+            },
+        }
+
+        if period <= pre_sample.elapsed() {
+            exec_stat.sample(&mut app, &mut pre_sample);
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    let wall_time = start.elapsed();
+
+    exec_stat.sample(&mut app, &mut pre_sample);
+    print_report(cmd, &app, &exec_stat, wall_time, &status);
+
+    exit_code(&status)
+}
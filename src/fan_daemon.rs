@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+use libamdgpu_top::{summary, DevicePath};
+use libamdgpu_top::AMDGPU::{HwmonTemp, HwmonTempType};
+use libamdgpu_top::control::{FanCurveConfig, FanCurveController, FanTempSensor};
+
+struct FanDevice {
+    name: String,
+    hwmon_path: PathBuf,
+    sensor: FanTempSensor,
+    ctrl: FanCurveController,
+}
+
+fn hwmon_temp_type(sensor: FanTempSensor) -> HwmonTempType {
+    match sensor {
+        FanTempSensor::Edge => HwmonTempType::Edge,
+        FanTempSensor::Junction => HwmonTempType::Junction,
+        FanTempSensor::Memory => HwmonTempType::Memory,
+    }
+}
+
+fn print_writes(dev: &mut FanDevice, dry_run: bool) {
+    for w in dev.ctrl.take_writes() {
+        if dry_run { println!("{w}") }
+    }
+}
+
+pub fn run_fan_daemon(title: &str, config_path: &Path, device_path_list: &[DevicePath], dry_run: bool) {
+    let config = FanCurveConfig::from_file(config_path).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+
+    let mut devices: Vec<FanDevice> = Vec::new();
+
+    for device_path in device_path_list {
+        let Some(entry) = config.get_entry(&device_path.pci) else { continue };
+        let name = device_path.menu_entry();
+        let Some(hwmon_path) = device_path.pci.get_hwmon_path() else {
+            eprintln!("{name}: hwmon is not found");
+            continue;
+        };
+        let ctrl = FanCurveController::new(
+            &device_path.sysfs_path,
+            &hwmon_path,
+            entry.curve.clone(),
+            entry.hysteresis,
+        );
+        let mut ctrl = match ctrl {
+            Ok(ctrl) => ctrl,
+            Err(err) => {
+                eprintln!("{name}: {err}");
+                continue;
+            },
+        };
+        ctrl.dry_run = dry_run;
+
+        let mut dev = FanDevice { name, hwmon_path, sensor: entry.sensor, ctrl };
+        let r = dev.ctrl.enable();
+        print_writes(&mut dev, dry_run);
+
+        if let Err(err) = r {
+            eprintln!("{}: {err}", dev.name);
+            continue;
+        }
+
+        println!("{}: {:?}, {:?}", dev.name, dev.ctrl.method, entry.curve.points);
+        devices.push(dev);
+    }
+
+    if devices.is_empty() {
+        eprintln!("There are no devices to control the fan.");
+        std::process::exit(1);
+    }
+
+    // `FanCurveController` restores the automatic fan control when dropped
+    summary::set_terminate_handler();
+
+    println!("{title}: fan curve daemon started ({}ms)", config.interval.as_millis());
+
+    while !summary::is_terminated() && !devices.is_empty() {
+        devices.retain_mut(|dev| {
+            let temp = HwmonTemp::from_hwmon_path(&dev.hwmon_path, hwmon_temp_type(dev.sensor))
+                .map(|t| t.current);
+            let r = match temp {
+                Some(temp) => dev.ctrl.update(temp).map_err(|err| err.to_string()).map(|speed| {
+                    if let Some(speed) = speed {
+                        println!("{}: {temp} C => {speed} %", dev.name);
+                    }
+                }),
+                None => Err("failed to read the temperature".to_string()),
+            };
+
+            let keep = if let Err(err) = r {
+                eprintln!("{}: {err}, restoring the automatic fan control", dev.name);
+                let _ = dev.ctrl.restore_auto();
+                false
+            } else {
+                true
+            };
+
+            print_writes(dev, dry_run);
+
+            keep
+        });
+
+        std::thread::sleep(config.interval);
+    }
+
+    for dev in devices.iter_mut() {
+        let _ = dev.ctrl.restore_auto();
+        print_writes(dev, dry_run);
+    }
+
+    println!("{title}: fan curve daemon stopped");
+}
@@ -12,24 +12,37 @@ mod dump_process;
 use dump_process::dump_process;
 mod dump_xdna_device;
 mod drm_info;
+mod dump_vbios;
+mod device_control;
+mod fan_daemon;
+mod exec;
 
 fn main() {
     let main_opt = MainOpt::parse();
 
     if let Some(path) = &main_opt.decode_gpu_metrics {
-        let gm = dump_info::decode_gpu_metrics(path);
+        let list = libamdgpu_top::gpu_metrics::decode_gpu_metrics_inputs(path).unwrap_or_else(|err| {
+            eprintln!("Failed to read {path:?}: {err}");
+            std::process::exit(1);
+        });
 
         #[cfg(feature = "json")]
         if let AppMode::JSON = main_opt.app_mode {
-            use amdgpu_top_json::OutputJson;
-            println!("{}", gm.json());
+            amdgpu_top_json::decoded_gpu_metrics_json(&list);
             return;
         }
 
-        println!("{gm:#?}");
+        dump_info::dump_decoded_gpu_metrics(&list);
         return;
     }
 
+    let pc_config = main_opt.pc_config.as_ref().map_or_else(Default::default, |path| {
+        libamdgpu_top::stat::PCConfig::from_file(path).unwrap_or_else(|err| {
+            eprintln!("Failed to load the performance counter config: {err}");
+            std::process::exit(1);
+        })
+    });
+
     let (device_path_list, device_path) = {
         let list = DevicePath::get_device_path_list();
 
@@ -51,6 +64,53 @@ fn main() {
         }
     };
 
+    if let Some(path) = &main_opt.dump_vbios {
+        dump_vbios::save_vbios(&device_path, path);
+        return;
+    }
+
+    if let Some(specs) = &main_opt.pp_table_diff {
+        #[cfg(feature = "json")]
+        if let AppMode::JSON = main_opt.app_mode {
+            match dump_pp_table::pp_table_diff(specs, &device_path) {
+                Ok((a, b, diff)) => amdgpu_top_json::pp_table_diff_json(&a, &b, &diff),
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                },
+            }
+
+            return;
+        }
+
+        dump_pp_table::dump_pp_table_diff(TITLE, specs, &device_path);
+        return;
+    }
+
+    if let Some(config_path) = &main_opt.fan_curve_config {
+        fan_daemon::run_fan_daemon(TITLE, config_path, &device_path_list, main_opt.control.dry_run);
+        return;
+    }
+
+    // restore the previous values when dropped
+    let _device_control = if main_opt.control.is_empty() {
+        None
+    } else {
+        let ctrl = device_control::apply_device_control(&device_path, &main_opt.control);
+
+        if !main_opt.control.restore_on_exit {
+            return;
+        }
+
+        ctrl
+    };
+
+    if !main_opt.exec_cmd.is_empty() {
+        let code = exec::run_exec(&device_path, &main_opt.exec_cmd, main_opt.refresh_period);
+        drop(_device_control);
+        std::process::exit(code);
+    }
+
     #[cfg(feature = "json")]
     if let AppMode::JSON = main_opt.app_mode { match main_opt.dump_mode {
         DumpMode::Info => {
@@ -61,7 +121,10 @@ fn main() {
             amdgpu_top_json::version_json(TITLE);
             return;
         },
-        DumpMode::PPTable => {},
+        DumpMode::PPTable => {
+            amdgpu_top_json::pp_table_json(&device_path_list);
+            return;
+        },
         DumpMode::NoDump => {
             match main_opt.opt_dump_mode {
                 OptDumpMode::GpuMetrics => {
@@ -72,6 +135,14 @@ fn main() {
                     amdgpu_top_json::drm_info_json(&device_path_list);
                     return;
                 },
+                OptDumpMode::VbiosTables => {
+                    let list: Vec<_> = device_path_list
+                        .iter()
+                        .map(|device_path| (device_path.clone(), dump_vbios::atom_bios(device_path)))
+                        .collect();
+                    amdgpu_top_json::vbios_tables_json(&list);
+                    return;
+                },
                 _ => {},
             }
 
@@ -82,9 +153,18 @@ fn main() {
                 main_opt.update_process_index,
                 main_opt.json_iterations,
                 main_opt.no_pc,
+                libamdgpu_top::app::AppOption {
+                    power_attribution: main_opt.power_attribution,
+                    summary: main_opt.summary.then(Default::default),
+                    gfxoff_residency_logging: true,
+                    pc_config,
+                    pc_sampler: (!main_opt.no_pc).then_some(main_opt.pc_sampler),
+                    ..Default::default()
+                },
             );
 
             j.run();
+            libamdgpu_top::stat::stop_gfxoff_residency_logging();
 
             return;
         },
@@ -129,6 +209,10 @@ fn main() {
                 drm_info::dump_all_drm_info(&device_path_list);
                 return;
             },
+            OptDumpMode::VbiosTables => {
+                dump_vbios::dump_all_vbios_tables(TITLE, &device_path_list);
+                return;
+            },
             _ => {},
         },
     }
@@ -141,6 +225,10 @@ fn main() {
         is_dark_mode: main_opt.is_dark_mode,
         hide_fdinfo: main_opt.hide_fdinfo,
         gui_wgpu_backend: main_opt.wgpu_backend,
+        power_attribution: main_opt.power_attribution,
+        summary: main_opt.summary,
+        pc_config,
+        pc_sampler: main_opt.pc_sampler,
     };
 
     match main_opt.app_mode {
@@ -188,6 +276,14 @@ fn main() {
                 ui_args.update_process_index,
                 main_opt.json_iterations,
                 ui_args.no_pc,
+                libamdgpu_top::app::AppOption {
+                    power_attribution: ui_args.power_attribution,
+                    summary: ui_args.summary.then(Default::default),
+                    gfxoff_residency_logging: true,
+                    pc_config: ui_args.pc_config.clone(),
+                    pc_sampler: (!ui_args.no_pc).then_some(ui_args.pc_sampler),
+                    ..Default::default()
+                },
             );
 
             j.run_fifo(path);
@@ -195,6 +291,8 @@ fn main() {
         #[cfg(feature = "tui")]
         AppMode::SMI => amdgpu_top_tui::run_smi(TITLE, ui_args),
     }
+
+    libamdgpu_top::stat::stop_gfxoff_residency_logging();
 }
 
 pub fn device_list(list: &[DevicePath]) {